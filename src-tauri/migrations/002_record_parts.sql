@@ -0,0 +1,24 @@
+-- Migration: 002_record_parts.sql
+-- Description: 多表示剪贴板记录 (一次复制事件保存所有可用格式)
+-- Created: 2026-10-18
+-- Version: 1.1
+--
+-- 包含：
+-- - record_parts 表：记录的附加表示 (按 MIME 类型区分)
+-- - 级联删除：主记录删除时自动清理附加表示
+
+-- ============================================================================
+-- 表：record_parts - 记录的附加表示
+-- ============================================================================
+-- records 中保存"主表示"(文件 > 图片 > HTML > 文本)，
+-- 同一次复制事件中的其它格式保存在这里，用于搜索和原样恢复
+CREATE TABLE IF NOT EXISTS record_parts (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    record_id INTEGER NOT NULL REFERENCES records(id) ON DELETE CASCADE,
+    mime TEXT NOT NULL,      -- MIME 类型 (text/plain, text/html, image/png, text/uri-list ...)
+    content_text TEXT,       -- 文本类表示的内容
+    content_blob BLOB,       -- 二进制表示的内容 (如 PNG 编码的图片)
+    UNIQUE(record_id, mime)
+);
+
+CREATE INDEX IF NOT EXISTS idx_record_parts_record ON record_parts(record_id);
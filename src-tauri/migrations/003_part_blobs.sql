@@ -0,0 +1,12 @@
+-- Migration: 003_part_blobs.sql
+-- Description: 附加表示的二进制内容改为存放在磁盘上 (与图片目录并列)
+-- Created: 2026-10-18
+-- Version: 1.2
+--
+-- 包含：
+-- - record_parts.blob_path：blobs 目录下的相对路径
+-- - content_blob 仅保留给旧数据读取
+
+ALTER TABLE record_parts ADD COLUMN blob_path TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_record_parts_blob ON record_parts(blob_path);
@@ -0,0 +1,11 @@
+-- Migration: 004_external_text.sql
+-- Description: 超大文本压缩后存放到磁盘，数据库中只保留可索引的前缀
+-- Created: 2026-10-18
+-- Version: 1.3
+--
+-- 包含：
+-- - content_path：texts 目录下 zstd 压缩文件的相对路径
+-- - content_size：完整文本的字节数 (content_text 只是前缀)
+
+ALTER TABLE records ADD COLUMN content_path TEXT;
+ALTER TABLE records ADD COLUMN content_size INTEGER;
@@ -0,0 +1,9 @@
+-- Migration: 005_code_language.sql
+-- Description: 记录代码片段的编程语言，用于语法高亮
+-- Created: 2026-10-18
+-- Version: 1.4
+--
+-- 包含：
+-- - language：检测出的语言标识 (rust / python / javascript …)，非代码为 NULL
+
+ALTER TABLE records ADD COLUMN language TEXT;
@@ -0,0 +1,9 @@
+-- Migration: 006_color_value.sql
+-- Description: 颜色记录保存规范化后的 RGBA 值，原文仍保存在 content_text
+-- Created: 2026-10-18
+-- Version: 1.5
+--
+-- 包含：
+-- - color_rgba：#rrggbbaa 格式的 sRGB 颜色，非颜色记录为 NULL
+
+ALTER TABLE records ADD COLUMN color_rgba TEXT;
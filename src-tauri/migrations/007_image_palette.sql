@@ -0,0 +1,9 @@
+-- Migration: 007_image_palette.sql
+-- Description: 图片记录保存提取出的调色板
+-- Created: 2026-10-18
+-- Version: 1.6
+--
+-- 包含：
+-- - palette：JSON 数组 [{"color": "#rrggbb", "ratio": 0.42}, ...]，按占比降序；旧图片为 NULL，首次读取时补算
+
+ALTER TABLE records ADD COLUMN palette TEXT;
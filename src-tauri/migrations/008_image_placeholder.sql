@@ -0,0 +1,11 @@
+-- Migration: 008_image_placeholder.sql
+-- Description: 图片记录保存占位信息，列表在缩略图加载前即可渲染
+-- Created: 2026-10-18
+-- Version: 1.7
+--
+-- 包含：
+-- - blurhash：BlurHash 字符串
+-- - average_color：#rrggbb / #rrggbbaa 格式的平均色
+
+ALTER TABLE records ADD COLUMN blurhash TEXT;
+ALTER TABLE records ADD COLUMN average_color TEXT;
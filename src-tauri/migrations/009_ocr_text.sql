@@ -0,0 +1,50 @@
+-- Migration: 009_ocr_text.sql
+-- Description: 图片记录保存 OCR 识别出的文字，并纳入全文索引
+-- Created: 2026-10-18
+-- Version: 1.8
+--
+-- 包含：
+-- - ocr_text：识别结果；NULL 表示尚未识别，空字符串表示识别过但没有文字
+-- - 重建 records_fts (新增 ocr_text 列) 及同步触发器
+
+ALTER TABLE records ADD COLUMN ocr_text TEXT;
+
+DROP TRIGGER IF EXISTS records_ai;
+DROP TRIGGER IF EXISTS records_ad;
+DROP TRIGGER IF EXISTS records_au;
+DROP TABLE IF EXISTS records_fts;
+
+-- ============================================================================
+-- 虚拟表：records_fts - FTS5 全文搜索索引
+-- ============================================================================
+CREATE VIRTUAL TABLE records_fts USING fts5(
+    content_text,
+    tag,
+    ocr_text,
+    content='records',
+    content_rowid='id'
+);
+
+-- ============================================================================
+-- 触发器：自动同步 FTS 索引
+-- ============================================================================
+
+CREATE TRIGGER records_ai AFTER INSERT ON records BEGIN
+    INSERT INTO records_fts(rowid, content_text, tag, ocr_text)
+    VALUES (new.id, new.content_text, new.tag, new.ocr_text);
+END;
+
+CREATE TRIGGER records_ad AFTER DELETE ON records BEGIN
+    INSERT INTO records_fts(records_fts, rowid, content_text, tag, ocr_text)
+    VALUES ('delete', old.id, old.content_text, old.tag, old.ocr_text);
+END;
+
+CREATE TRIGGER records_au AFTER UPDATE ON records BEGIN
+    INSERT INTO records_fts(records_fts, rowid, content_text, tag, ocr_text)
+    VALUES ('delete', old.id, old.content_text, old.tag, old.ocr_text);
+    INSERT INTO records_fts(rowid, content_text, tag, ocr_text)
+    VALUES (new.id, new.content_text, new.tag, new.ocr_text);
+END;
+
+-- 用现有记录重建索引
+INSERT INTO records_fts(records_fts) VALUES ('rebuild');
@@ -0,0 +1,12 @@
+-- Migration: 010_derived_clips.sql
+-- Description: 记录之间的派生关系 (如图片中识别出的二维码内容、由文本生成的二维码)
+-- Created: 2026-10-18
+-- Version: 1.9
+--
+-- 包含：
+-- - source_id：派生记录的来源记录 ID，来源删除后置为 NULL
+-- - 来源索引
+
+ALTER TABLE records ADD COLUMN source_id INTEGER REFERENCES records(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_records_source ON records(source_id);
@@ -0,0 +1,15 @@
+-- Migration: 011_source_app.sql
+-- Description: 记录复制内容时的来源应用
+-- Created: 2026-10-18
+-- Version: 1.10
+--
+-- 包含：
+-- - app_context (001 中已有) 保存应用名
+-- - app_title：窗口标题
+-- - app_path：可执行文件路径
+-- - 按应用过滤用的索引
+
+ALTER TABLE records ADD COLUMN app_title TEXT;
+ALTER TABLE records ADD COLUMN app_path TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_records_app ON records(app_context);
@@ -0,0 +1,44 @@
+-- Migration: 012_sensitive_clips.sql
+-- Description: 敏感内容 (密钥、信用卡号等) 标记、自动过期，并从全文索引中排除
+-- Created: 2026-10-18
+-- Version: 1.11
+--
+-- 包含：
+-- - is_sensitive：是否为敏感内容；敏感记录不进入 records_fts
+-- - sensitive_kind：检测到的类型，如 aws_key / credit_card
+-- - sensitive_override：用户手动指定；NULL 表示按检测结果，0 / 1 表示强制不敏感 / 敏感
+-- - expires_at：到期时间 (微秒时间戳)，到期后未置顶的记录会被删除
+-- - 重建 FTS 同步触发器，并为 records_fts 开启 secure-delete，删除的词条不在索引中残留
+
+ALTER TABLE records ADD COLUMN is_sensitive INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE records ADD COLUMN sensitive_kind TEXT;
+ALTER TABLE records ADD COLUMN sensitive_override INTEGER;
+ALTER TABLE records ADD COLUMN expires_at INTEGER;
+
+CREATE INDEX IF NOT EXISTS idx_records_expires ON records(expires_at) WHERE expires_at IS NOT NULL;
+
+INSERT INTO records_fts(records_fts, rank) VALUES ('secure-delete', 1);
+
+-- ============================================================================
+-- 触发器：只为非敏感记录维护 FTS 索引
+-- ============================================================================
+DROP TRIGGER IF EXISTS records_ai;
+DROP TRIGGER IF EXISTS records_ad;
+DROP TRIGGER IF EXISTS records_au;
+
+CREATE TRIGGER records_ai AFTER INSERT ON records WHEN new.is_sensitive = 0 BEGIN
+    INSERT INTO records_fts(rowid, content_text, tag, ocr_text)
+    VALUES (new.id, new.content_text, new.tag, new.ocr_text);
+END;
+
+CREATE TRIGGER records_ad AFTER DELETE ON records WHEN old.is_sensitive = 0 BEGIN
+    INSERT INTO records_fts(records_fts, rowid, content_text, tag, ocr_text)
+    VALUES ('delete', old.id, old.content_text, old.tag, old.ocr_text);
+END;
+
+CREATE TRIGGER records_au AFTER UPDATE ON records BEGIN
+    INSERT INTO records_fts(records_fts, rowid, content_text, tag, ocr_text)
+    SELECT 'delete', old.id, old.content_text, old.tag, old.ocr_text WHERE old.is_sensitive = 0;
+    INSERT INTO records_fts(rowid, content_text, tag, ocr_text)
+    SELECT new.id, new.content_text, new.tag, new.ocr_text WHERE new.is_sensitive = 0;
+END;
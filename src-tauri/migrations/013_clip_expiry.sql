@@ -0,0 +1,10 @@
+-- Migration: 013_clip_expiry.sql
+-- Description: 记录的存活时间和一次性记录
+-- Created: 2026-10-18
+-- Version: 1.12
+--
+-- 包含：
+-- - expires_at (012 中已有)：用户设置的到期时间同样写入该列
+-- - delete_after_use：一次性记录，粘贴回剪贴板后短时间内删除
+
+ALTER TABLE records ADD COLUMN delete_after_use INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,20 @@
+-- Migration: 014_vault.sql
+-- Description: 保险库：用单独口令逐条加密的置顶机密
+-- Created: 2026-10-18
+-- Version: 1.13
+--
+-- 包含：
+-- - in_vault：记录已移入保险库，不出现在列表和搜索结果中
+-- - vault_payload：保险库密钥加密后的内容，明文列随之清空
+-- - vault_label：保险库列表中显示的名称
+-- - vault_meta：口令派生密钥用的盐，以及用于校验口令的加密数据
+
+ALTER TABLE records ADD COLUMN in_vault INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE records ADD COLUMN vault_payload BLOB;
+ALTER TABLE records ADD COLUMN vault_label TEXT;
+
+CREATE TABLE IF NOT EXISTS vault_meta (
+    id INTEGER PRIMARY KEY CHECK (id = 1), -- 只有一行
+    salt BLOB NOT NULL,
+    verifier BLOB NOT NULL
+);
@@ -0,0 +1,13 @@
+-- Migration: 015_expiry_source.sql
+-- Description: 区分敏感标记自动设置的到期时间和用户设置的到期时间
+-- Created: 2026-10-18
+-- Version: 1.14
+--
+-- 包含：
+-- - expiry_auto：expires_at 由敏感标记自动设置；敏感标记取消时只清除这种到期时间，
+--   用户设置的存活时间和一次性记录的宽限期保持不变
+-- - 已有的敏感记录的到期时间视为自动设置
+
+ALTER TABLE records ADD COLUMN expiry_auto INTEGER NOT NULL DEFAULT 0;
+
+UPDATE records SET expiry_auto = 1 WHERE is_sensitive = 1 AND expires_at IS NOT NULL;
@@ -0,0 +1,157 @@
+//! 二维码 / 条码：识别图片中的码并保存为关联的文本记录；把文本生成为二维码图片
+
+use anyhow::{anyhow, Context, Result};
+use base64::{engine::general_purpose, Engine as _};
+use image::{DynamicImage, Luma, RgbaImage};
+use qrcode::QrCode;
+use regex::Regex;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{LazyLock, Mutex};
+
+use crate::persist::Storage;
+
+/// 生成的二维码图片的最小边长 (像素)
+const QR_MIN_SIZE: u32 = 512;
+
+/// 识别出的一个码
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct DecodedCode {
+    pub symbology: String, // 码制，如 QR-Code / EAN-13 / CODE-128
+    pub text: String,
+}
+
+impl DecodedCode {
+    /// 派生记录使用的标签
+    pub fn tag(&self) -> &'static str {
+        if self.symbology.eq_ignore_ascii_case("QR-Code") { "qrcode" } else { "barcode" }
+    }
+}
+
+/// 条码识别器
+pub trait CodeDecoder: Send + Sync {
+    fn name(&self) -> &'static str;
+    /// 识别图片文件中的所有码，没有时返回空数组
+    fn decode(&self, image: &Path) -> Result<Vec<DecodedCode>>;
+}
+
+/// 通过命令行调用 zbarimg (zbar-tools)
+pub struct ZbarCli {
+    program: PathBuf,
+}
+
+impl ZbarCli {
+    pub fn new() -> Self {
+        Self { program: PathBuf::from("zbarimg") }
+    }
+
+    /// 指定可执行文件路径
+    pub fn with_program<P: Into<PathBuf>>(mut self, program: P) -> Self {
+        self.program = program.into();
+        self
+    }
+
+    /// zbarimg 已安装时返回识别器
+    pub fn detect() -> Option<Self> {
+        let decoder = Self::new();
+        let available = Command::new(&decoder.program)
+            .arg("--version")
+            .output()
+            .map(|out| out.status.success())
+            .unwrap_or(false);
+        available.then_some(decoder)
+    }
+}
+
+impl Default for ZbarCli {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CodeDecoder for ZbarCli {
+    fn name(&self) -> &'static str {
+        "zbarimg"
+    }
+
+    fn decode(&self, image: &Path) -> Result<Vec<DecodedCode>> {
+        let output = Command::new(&self.program)
+            .args(["--quiet", "--xml"])
+            .arg(image)
+            .output()
+            .context("Failed to run zbarimg")?;
+        // 退出码 4 表示没有找到任何码
+        match output.status.code() {
+            Some(0) | Some(4) => Ok(parse_zbar_xml(&String::from_utf8_lossy(&output.stdout))),
+            _ => Err(anyhow!(
+                "zbarimg 退出码 {}: {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )),
+        }
+    }
+}
+
+static SYMBOL_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?s)<symbol\s[^>]*type=['"]([^'"]+)['"][^>]*>.*?<data(\s[^>]*)?><!\[CDATA\[(.*?)\]\]></data>"#).unwrap()
+});
+
+/// 解析 zbarimg --xml 的输出，二进制内容以 base64 给出
+pub fn parse_zbar_xml(xml: &str) -> Vec<DecodedCode> {
+    SYMBOL_RE
+        .captures_iter(xml)
+        .filter_map(|caps| {
+            let attrs = caps.get(2).map_or("", |m| m.as_str());
+            let data = &caps[3];
+            let text = if attrs.contains("base64") {
+                let bytes = general_purpose::STANDARD.decode(data.split_whitespace().collect::<String>()).ok()?;
+                String::from_utf8(bytes).ok()?
+            } else {
+                data.to_string()
+            };
+            Some(DecodedCode { symbology: caps[1].to_string(), text })
+        })
+        .filter(|code| !code.text.trim().is_empty())
+        .collect()
+}
+
+/// 识别一条图片记录中的码，保存为关联到该图片的文本记录，返回新记录的 ID
+///
+/// 与 OCR 一样只在读写数据库时持锁；未设置识别器时直接返回
+pub fn process(storage: &Mutex<Storage>, id: i64) -> Result<Vec<i64>> {
+    let (decoder, image) = {
+        let store = storage.lock().map_err(|_| anyhow!("Lock error"))?;
+        match store.code_decoder() {
+            Some(decoder) => (decoder, store.image_file(id)?),
+            None => return Ok(Vec::new()),
+        }
+    };
+
+    let codes = decoder.decode(&image)?;
+    if codes.is_empty() {
+        return Ok(Vec::new());
+    }
+    println!("🔳 {} 在记录 {} 中识别到 {} 个码", decoder.name(), id, codes.len());
+
+    let mut store = storage.lock().map_err(|_| anyhow!("Lock error"))?;
+    let mut ids = Vec::new();
+    for code in codes {
+        let derived = store.add_derived_text(id, code.text.clone(), code.tag())?;
+        if derived != 0 {
+            ids.push(derived);
+        }
+    }
+    Ok(ids)
+}
+
+/// 把文本渲染为黑白二维码图片 (带静区)
+pub fn render_qr(text: &str) -> Result<RgbaImage> {
+    let code = QrCode::new(text.as_bytes()).map_err(|e| anyhow!("无法生成二维码: {}", e))?;
+    let img = code
+        .render::<Luma<u8>>()
+        .quiet_zone(true)
+        .min_dimensions(QR_MIN_SIZE, QR_MIN_SIZE)
+        .build();
+    Ok(DynamicImage::ImageLuma8(img).to_rgba8())
+}
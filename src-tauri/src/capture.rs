@@ -0,0 +1,104 @@
+//! 记录状态：正常记录、暂停 (定时 / 无限期) 和隐身模式
+//!
+//! 剪贴板监听线程在读取内容前检查状态，界面、托盘和快捷键通过同一个 [`SharedCapture`] 修改
+
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// 剪贴板监听线程和界面共享的记录状态
+pub type SharedCapture = Arc<Mutex<CaptureControl>>;
+
+/// 记录状态
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum CaptureState {
+    /// 正常记录
+    #[default]
+    Active,
+    /// 暂停到指定时间 (微秒时间戳)，之后自动恢复
+    PausedUntil { until: i64 },
+    /// 暂停，直到手动恢复
+    PausedIndefinitely,
+    /// 隐身模式：只在内存中保留，退出隐身或关闭程序后丢弃
+    Incognito,
+}
+
+/// 本次复制事件的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureMode {
+    /// 保存到数据库
+    Record,
+    /// 只保存在内存中
+    Memory,
+    /// 不读取剪贴板
+    Skip,
+}
+
+/// 记录状态机
+#[derive(Debug, Default)]
+pub struct CaptureControl {
+    state: CaptureState,
+}
+
+impl CaptureControl {
+    /// 当前状态；定时暂停到期后视为已恢复
+    pub fn state(&mut self) -> CaptureState {
+        self.refresh();
+        self.state
+    }
+
+    /// 定时暂停到期时恢复记录，状态发生变化时返回 true
+    pub fn refresh(&mut self) -> bool {
+        match self.state {
+            CaptureState::PausedUntil { until } if until <= Utc::now().timestamp_micros() => {
+                println!("▶️ 暂停已到期，恢复记录");
+                self.state = CaptureState::Active;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn mode(&mut self) -> CaptureMode {
+        match self.state() {
+            CaptureState::Active => CaptureMode::Record,
+            CaptureState::Incognito => CaptureMode::Memory,
+            CaptureState::PausedUntil { .. } | CaptureState::PausedIndefinitely => CaptureMode::Skip,
+        }
+    }
+
+    /// 切换状态，返回之前的状态
+    pub fn set(&mut self, state: CaptureState) -> CaptureState {
+        let previous = self.state();
+        println!("⏯️ 记录状态: {:?} -> {:?}", previous, state);
+        self.state = state;
+        previous
+    }
+
+    /// 暂停一段时间；`duration` 为 None 时无限期暂停，时长超出范围时返回错误且状态不变
+    pub fn pause(&mut self, duration: Option<Duration>) -> anyhow::Result<CaptureState> {
+        let state = match duration {
+            Some(duration) => {
+                let until = Utc::now()
+                    .checked_add_signed(duration)
+                    .ok_or_else(|| anyhow::anyhow!("暂停时长超出范围"))?;
+                CaptureState::PausedUntil { until: until.timestamp_micros() }
+            }
+            None => CaptureState::PausedIndefinitely,
+        };
+        Ok(self.set(state))
+    }
+
+    pub fn resume(&mut self) -> CaptureState {
+        self.set(CaptureState::Active)
+    }
+
+    /// 快捷键：正在记录时无限期暂停，其它状态 (包括隐身) 恢复正常记录
+    pub fn toggle_pause(&mut self) -> CaptureState {
+        match self.state() {
+            CaptureState::Active => self.set(CaptureState::PausedIndefinitely),
+            _ => self.resume(),
+        }
+    }
+}
@@ -0,0 +1,332 @@
+//! 内容分类：为文本记录生成智能标签 (url / email / json / code …)
+//!
+//! 每种类型由一个 [`Detector`] 负责，可以注册自定义检测器；
+//! 标签名一旦发布就不再修改，前端和搜索依赖这些名字做过滤
+
+use regex::Regex;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::LazyLock;
+
+/// 内容检测器
+pub trait Detector: Send + Sync {
+    /// 命中时写入的标签名
+    fn tag(&self) -> &'static str;
+    /// 判断文本 (已去除首尾空白) 是否属于该类型
+    fn detect(&self, text: &str) -> bool;
+}
+
+/// 超过该长度的文本只做结构类检测 (json / xml / sql / code)
+const MAX_VALUE_LEN: usize = 2048;
+
+/// 按注册顺序依次运行检测器
+pub struct Classifier {
+    detectors: Vec<Box<dyn Detector>>,
+}
+
+impl Default for Classifier {
+    fn default() -> Self {
+        let mut classifier = Self::empty();
+        classifier.register(Box::new(UrlDetector));
+        classifier.register(Box::new(EmailDetector));
+        classifier.register(Box::new(PhoneDetector));
+        classifier.register(Box::new(PathDetector));
+        classifier.register(Box::new(JsonDetector));
+        classifier.register(Box::new(XmlDetector));
+        classifier.register(Box::new(SqlDetector));
+        classifier.register(Box::new(UuidDetector));
+        classifier.register(Box::new(IpDetector));
+        classifier.register(Box::new(TimestampDetector));
+        classifier.register(Box::new(NumberDetector));
+        classifier.register(Box::new(CodeDetector));
+        classifier
+    }
+}
+
+impl Classifier {
+    /// 不含任何检测器
+    pub fn empty() -> Self {
+        Self { detectors: Vec::new() }
+    }
+
+    pub fn register(&mut self, detector: Box<dyn Detector>) {
+        self.detectors.push(detector);
+    }
+
+    /// 返回命中的标签，顺序与检测器注册顺序一致，不重复
+    pub fn classify(&self, text: &str) -> Vec<String> {
+        let text = text.trim();
+        let mut tags: Vec<String> = Vec::new();
+        if text.is_empty() {
+            return tags;
+        }
+        for detector in &self.detectors {
+            let tag = detector.tag();
+            if !tags.iter().any(|t| t == tag) && detector.detect(text) {
+                tags.push(tag.to_string());
+            }
+        }
+        tags
+    }
+}
+
+/// 单个值 (不含换行、长度有限)
+fn is_single_value(text: &str) -> bool {
+    text.len() <= MAX_VALUE_LEN && !text.contains('\n')
+}
+
+static URL_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^(?:(?:https?|ftp|wss?)://[^\s/?#]+|www\.[^\s/?#]+\.[a-z]{2,})[^\s]*$").unwrap()
+});
+
+static EMAIL_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?:mailto:)?[A-Za-z0-9._%+-]+@[A-Za-z0-9-]+(?:\.[A-Za-z0-9-]+)*\.[A-Za-z]{2,}$").unwrap()
+});
+
+static PHONE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?:\+\d{1,3}[ .-]?)?(?:\(\d{1,4}\)[ .-]?)?\d{2,4}(?:[ .-]?\d{2,4}){1,4}$").unwrap()
+});
+
+static UUID_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\{?[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}\}?$").unwrap()
+});
+
+static NUMBER_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^[+-]?(?:\d{1,3}(?:,\d{3})+|\d+)(?:\.\d+)?(?:[eE][+-]?\d+)?$|^0[xX][0-9a-fA-F]+$").unwrap()
+});
+
+static SQL_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?is)^(?:SELECT\s.+?\sFROM\s|INSERT\s+INTO\s|UPDATE\s+\S+\s+SET\s|DELETE\s+FROM\s|CREATE\s+(?:TEMP(?:ORARY)?\s+)?(?:TABLE|(?:UNIQUE\s+)?INDEX|VIEW|TRIGGER)\s|ALTER\s+TABLE\s|DROP\s+(?:TABLE|INDEX|VIEW|TRIGGER)\s|WITH\s+(?:RECURSIVE\s+)?\w+\s+AS\s*\()",
+    )
+    .unwrap()
+});
+
+static CODE_KEYWORD_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?m)^\s*(?:fn|pub|impl|use|mod|struct|enum|def|class|import|from|function|const|let|var|return|if|for|while|package|func|#include|#define|public|private|static|async|export)\b|=>|::|->|\+\+|&&|\|\|",
+    )
+    .unwrap()
+});
+
+/// http(s) / ftp / ws 链接，或以 www. 开头的域名
+pub struct UrlDetector;
+
+impl Detector for UrlDetector {
+    fn tag(&self) -> &'static str { "url" }
+    fn detect(&self, text: &str) -> bool {
+        is_single_value(text) && URL_RE.is_match(text)
+    }
+}
+
+pub struct EmailDetector;
+
+impl Detector for EmailDetector {
+    fn tag(&self) -> &'static str { "email" }
+    fn detect(&self, text: &str) -> bool {
+        is_single_value(text) && EMAIL_RE.is_match(text)
+    }
+}
+
+/// 国际格式 (+86 …) 或带分隔符的号码，以及中国大陆手机号
+pub struct PhoneDetector;
+
+impl Detector for PhoneDetector {
+    fn tag(&self) -> &'static str { "phone" }
+    fn detect(&self, text: &str) -> bool {
+        if text.len() > 32 || !PHONE_RE.is_match(text) {
+            return false;
+        }
+        let digits: String = text.chars().filter(char::is_ascii_digit).collect();
+        if !(7..=15).contains(&digits.len()) {
+            return false;
+        }
+        // 日期 (2024-01-15) 形式上和号码相同
+        if is_iso_date(text) {
+            return false;
+        }
+        if text.starts_with('+') {
+            return true;
+        }
+        // 点号分隔容易和版本号 / IP 混淆，不作为号码分隔符
+        if text.contains([' ', '-', '(', ')']) {
+            return !text.contains('.');
+        }
+        // 纯数字容易和普通数字混淆，只接受手机号格式
+        digits.len() == text.len()
+            && digits.len() == 11
+            && digits.starts_with('1')
+            && matches!(digits.as_bytes()[1], b'3'..=b'9')
+    }
+}
+
+fn is_iso_date(text: &str) -> bool {
+    let parts: Vec<&str> = text.split('-').collect();
+    parts.len() == 3
+        && parts[0].len() == 4
+        && parts[1].len() == 2
+        && parts[2].len() == 2
+        && parts.iter().all(|p| p.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Unix 绝对 / 相对路径、~ 路径、Windows 盘符和 UNC 路径
+pub struct PathDetector;
+
+impl Detector for PathDetector {
+    fn tag(&self) -> &'static str { "path" }
+    fn detect(&self, text: &str) -> bool {
+        if !is_single_value(text) || text.contains("://") {
+            return false;
+        }
+        let unix_prefix = (text.starts_with('/') && !text.starts_with("//"))
+            || text.starts_with("~/")
+            || text.starts_with("./")
+            || text.starts_with("../");
+        // 含空格时要求至少两级目录，避免把 "/ 注释" 之类误判为路径
+        let unix = unix_prefix
+            && text.len() > 1
+            && (!text.contains(char::is_whitespace) || text.matches('/').count() >= 2);
+        let bytes = text.as_bytes();
+        let windows = bytes.len() >= 3
+            && bytes[0].is_ascii_alphabetic()
+            && bytes[1] == b':'
+            && (bytes[2] == b'\\' || bytes[2] == b'/');
+        let unc = text.starts_with("\\\\") && text.len() > 2;
+        unix || windows || unc
+    }
+}
+
+/// JSON 对象或数组
+pub struct JsonDetector;
+
+impl Detector for JsonDetector {
+    fn tag(&self) -> &'static str { "json" }
+    fn detect(&self, text: &str) -> bool {
+        let looks_like = (text.starts_with('{') && text.ends_with('}'))
+            || (text.starts_with('[') && text.ends_with(']'));
+        looks_like && serde_json::from_str::<serde_json::Value>(text).is_ok()
+    }
+}
+
+/// XML / HTML 片段：首个开始标签与结尾的结束标签匹配
+pub struct XmlDetector;
+
+impl Detector for XmlDetector {
+    fn tag(&self) -> &'static str { "xml" }
+    fn detect(&self, text: &str) -> bool {
+        if text.starts_with("<?xml") {
+            return true;
+        }
+        if !text.starts_with('<') || !text.ends_with('>') {
+            return false;
+        }
+        let name: String = text[1..]
+            .chars()
+            .take_while(|c| c.is_alphanumeric() || matches!(c, ':' | '-' | '_' | '.'))
+            .collect();
+        if name.is_empty() || !name.starts_with(|c: char| c.is_alphabetic()) {
+            return false;
+        }
+        text.ends_with(&format!("</{}>", name)) || (text.ends_with("/>") && !text[1..].contains('<'))
+    }
+}
+
+pub struct SqlDetector;
+
+impl Detector for SqlDetector {
+    fn tag(&self) -> &'static str { "sql" }
+    fn detect(&self, text: &str) -> bool {
+        SQL_RE.is_match(text)
+    }
+}
+
+pub struct UuidDetector;
+
+impl Detector for UuidDetector {
+    fn tag(&self) -> &'static str { "uuid" }
+    fn detect(&self, text: &str) -> bool {
+        UUID_RE.is_match(text)
+    }
+}
+
+/// IPv4 / IPv6 地址，可带端口或 CIDR 前缀
+pub struct IpDetector;
+
+impl Detector for IpDetector {
+    fn tag(&self) -> &'static str { "ip" }
+    fn detect(&self, text: &str) -> bool {
+        if text.len() > 64 {
+            return false;
+        }
+        if text.parse::<IpAddr>().is_ok() || text.parse::<SocketAddr>().is_ok() {
+            return true;
+        }
+        match text.split_once('/') {
+            Some((addr, prefix)) => {
+                let max = match addr.parse::<IpAddr>() {
+                    Ok(IpAddr::V4(_)) => 32,
+                    Ok(IpAddr::V6(_)) => 128,
+                    Err(_) => return false,
+                };
+                prefix.parse::<u8>().is_ok_and(|p| p <= max)
+            }
+            None => false,
+        }
+    }
+}
+
+/// 2000 年至 2100 年之间的 Unix 时间戳 (秒或毫秒)
+pub struct TimestampDetector;
+
+const TIMESTAMP_MIN: i64 = 946_684_800; // 2000-01-01
+const TIMESTAMP_MAX: i64 = 4_102_444_800; // 2100-01-01
+
+impl Detector for TimestampDetector {
+    fn tag(&self) -> &'static str { "timestamp" }
+    fn detect(&self, text: &str) -> bool {
+        if !text.bytes().all(|b| b.is_ascii_digit()) {
+            return false;
+        }
+        let seconds = match text.len() {
+            10 => text.parse::<i64>().ok(),
+            13 => text.parse::<i64>().ok().map(|ms| ms / 1000),
+            _ => None,
+        };
+        seconds.is_some_and(|s| (TIMESTAMP_MIN..TIMESTAMP_MAX).contains(&s))
+    }
+}
+
+/// 整数、小数、千分位、科学计数法和十六进制数
+pub struct NumberDetector;
+
+impl Detector for NumberDetector {
+    fn tag(&self) -> &'static str { "number" }
+    fn detect(&self, text: &str) -> bool {
+        text.len() <= 64 && NUMBER_RE.is_match(text)
+    }
+}
+
+/// 源代码：关键字 / 运算符和语句结尾符号的启发式评分
+pub struct CodeDetector;
+
+impl Detector for CodeDetector {
+    fn tag(&self) -> &'static str { "code" }
+    fn detect(&self, text: &str) -> bool {
+        let lines: Vec<&str> = text.lines().filter(|l| !l.trim().is_empty()).collect();
+        if lines.is_empty() {
+            return false;
+        }
+        let keyword_hits = CODE_KEYWORD_RE.find_iter(text).count();
+        let syntax_lines = lines
+            .iter()
+            .filter(|l| {
+                let l = l.trim_end();
+                l.ends_with(';') || l.ends_with('{') || l.ends_with('}') || l.ends_with(':') && l.starts_with(char::is_whitespace)
+            })
+            .count();
+        let indented = lines.iter().filter(|l| l.starts_with("    ") || l.starts_with('\t')).count();
+
+        let score = keyword_hits.min(lines.len() * 2) + syntax_lines + indented / 2;
+        // 单行至少 2 分，多行按行数要求更多特征
+        score >= 2 && score * 3 >= lines.len().min(30)
+    }
+}
@@ -1,19 +1,102 @@
-use arboard::Clipboard;
+use arboard::{Clipboard, ImageData};
 use clipboard_master::{CallbackResult, ClipboardHandler};
 use crossbeam_channel::Sender;
+use std::borrow::Cow;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
+use crate::capture::{CaptureControl, CaptureMode, SharedCapture};
+use crate::conceal::{self, ConcealedPolicy, SharedPolicy};
+use crate::mime::{self, MimeBackend};
+use crate::privacy::{PrivacyGuard, SharedGuard};
+use crate::setting::SharedFormats;
+use crate::source_app::{self, AppDetector, SourceApp};
+use crate::persist::{ClipData, ClipPart, MIME_FILES, MIME_HTML, MIME_PNG, MIME_RTF, MIME_TEXT};
+
+/// 本程序写入剪贴板、不应再被记录的文本指纹 (临时记录、保险库)
+///
+/// 隐藏标记只有 X11 后端能一起写入，其它平台靠监听线程比对指纹跳过
+pub type SharedSelfWrite = Arc<Mutex<Option<String>>>;
+
+/// 剪贴板上 RTF 的常见 MIME 名称
+const RTF_TARGETS: &[&str] = &["text/rtf", "application/rtf", "text/richtext"];
+
 // 定义传递给主线程的数据类型
 #[derive(Debug)]
 pub enum ClipEvent {
     Text(String),
     Image { width: usize, height: usize, rgba_data: Vec<u8> }, // RGBA 原始数据
     Html(String),
+    Rtf(String),
     FileList(Vec<std::path::PathBuf>),
+    Custom { mime: String, data: Vec<u8> }, // 白名单中的应用私有格式
+    Bundle { parts: Vec<ClipEvent>, source: Option<SourceApp> }, // 同一次复制事件中的所有可用表示及来源应用
+    Ephemeral { text: String, source: Option<SourceApp>, session: bool }, // 只在内存中暂存的文本；session 为 true 表示隐身模式，否则为带隐藏标记的内容
     Error(String),
 }
 
+impl ClipEvent {
+    /// 主表示优先级：文件 > 图片 > HTML > RTF > 文本 > 私有格式
+    fn priority(&self) -> u8 {
+        match self {
+            ClipEvent::FileList(_) => 0,
+            ClipEvent::Image { .. } => 1,
+            ClipEvent::Html(_) => 2,
+            ClipEvent::Rtf(_) => 3,
+            ClipEvent::Text(_) => 4,
+            ClipEvent::Custom { .. } => 5,
+            _ => u8::MAX,
+        }
+    }
+
+    /// 对应的 MIME 类型
+    fn to_mime(&self) -> Option<&str> {
+        match self {
+            ClipEvent::Text(_) => Some(MIME_TEXT),
+            ClipEvent::Html(_) => Some(MIME_HTML),
+            ClipEvent::Rtf(_) => Some(MIME_RTF),
+            ClipEvent::FileList(_) => Some(MIME_FILES),
+            ClipEvent::Image { .. } => Some(MIME_PNG),
+            ClipEvent::Custom { mime, .. } => Some(mime),
+            ClipEvent::Bundle { .. } | ClipEvent::Ephemeral { .. } | ClipEvent::Error(_) => None,
+        }
+    }
+
+    /// 转换为可存储的附加表示
+    pub fn to_part(&self) -> Option<ClipPart> {
+        match self {
+            ClipEvent::Text(text) => Some(ClipPart::text(MIME_TEXT, text.clone())),
+            ClipEvent::Html(html) => Some(ClipPart::text(MIME_HTML, html.clone())),
+            ClipEvent::Rtf(rtf) => Some(ClipPart::text(MIME_RTF, rtf.clone())),
+            ClipEvent::FileList(files) => {
+                let paths = files.iter()
+                    .map(|p| p.to_string_lossy().to_string())
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                Some(ClipPart::text(MIME_FILES, paths))
+            }
+            ClipEvent::Image { width, height, rgba_data } => {
+                let img = image::RgbaImage::from_raw(*width as u32, *height as u32, rgba_data.clone())?;
+                let mut png = Vec::new();
+                img.write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png).ok()?;
+                Some(ClipPart::binary(MIME_PNG, png))
+            }
+            ClipEvent::Custom { mime, data } => Some(ClipPart::binary(mime, data.clone())),
+            ClipEvent::Bundle { .. } | ClipEvent::Ephemeral { .. } | ClipEvent::Error(_) => None,
+        }
+    }
+}
+
+/// 拆分多表示事件：返回主表示和其余表示
+pub fn split_primary(mut parts: Vec<ClipEvent>) -> Option<(ClipEvent, Vec<ClipEvent>)> {
+    parts.sort_by_key(|p| p.priority());
+    if parts.is_empty() {
+        return None;
+    }
+    let primary = parts.remove(0);
+    Some((primary, parts))
+}
+
 // 监听器结构体
 pub struct SystemHook {
     // 通信管道发送端
@@ -21,6 +104,22 @@ pub struct SystemHook {
     // 用于防抖 (Debounce)：记录上一次内容的哈希和时间
     pub last_hash: Arc<Mutex<String>>,
     pub last_update: Arc<Mutex<Instant>>,
+    // 读取 arboard 不支持的格式 (RTF 等)
+    pub backend: Box<dyn MimeBackend>,
+    // 需要原样捕获的私有格式白名单，与界面共享
+    pub custom_formats: SharedFormats,
+    // 识别复制时的前台应用
+    pub detector: Box<dyn AppDetector>,
+    // 隐私黑名单，与界面共享以便修改规则和查看计数
+    pub privacy: SharedGuard,
+    // 带隐藏标记的内容跳过还是暂存，与界面共享
+    pub concealed_policy: SharedPolicy,
+    // 记录状态 (暂停 / 隐身)，与界面共享
+    pub capture: SharedCapture,
+    // 本程序写入的机密文本指纹，与界面共享
+    pub self_write: SharedSelfWrite,
+    // 无法列出剪贴板格式的提示只输出一次
+    pub targets_warned: bool,
 }
 
 impl SystemHook {
@@ -29,9 +128,79 @@ impl SystemHook {
             sender,
             last_hash: Arc::new(Mutex::new(String::new())),
             last_update: Arc::new(Mutex::new(Instant::now())),
+            backend: mime::default_backend(),
+            custom_formats: Arc::new(Mutex::new(Vec::new())),
+            detector: source_app::default_detector(),
+            privacy: Arc::new(Mutex::new(PrivacyGuard::default())),
+            concealed_policy: Arc::new(Mutex::new(ConcealedPolicy::default())),
+            capture: Arc::new(Mutex::new(CaptureControl::default())),
+            self_write: Arc::new(Mutex::new(None)),
+            targets_warned: false,
         }
     }
 
+    pub fn with_custom_formats(mut self, formats: SharedFormats) -> Self {
+        self.custom_formats = formats;
+        self
+    }
+
+    pub fn with_detector(mut self, detector: Box<dyn AppDetector>) -> Self {
+        self.detector = detector;
+        self
+    }
+
+    pub fn with_privacy(mut self, privacy: SharedGuard) -> Self {
+        self.privacy = privacy;
+        self
+    }
+
+    pub fn with_concealed_policy(mut self, policy: SharedPolicy) -> Self {
+        self.concealed_policy = policy;
+        self
+    }
+
+    pub fn with_capture(mut self, capture: SharedCapture) -> Self {
+        self.capture = capture;
+        self
+    }
+
+    pub fn with_self_write(mut self, self_write: SharedSelfWrite) -> Self {
+        self.self_write = self_write;
+        self
+    }
+
+    /// 文本是否为本程序刚写入的机密内容
+    fn is_self_write(&self, text: &str) -> bool {
+        let fingerprint = text_fingerprint(text);
+        self.self_write.lock().is_ok_and(|last| last.as_deref() == Some(fingerprint.as_str()))
+    }
+
+    /// 前台应用命中隐私黑名单时返回 true (并计数)
+    pub fn is_suppressed(&self, source: Option<&SourceApp>) -> bool {
+        self.privacy.lock().map(|mut guard| guard.should_suppress(source)).unwrap_or(false)
+    }
+
+    /// 读取白名单中的私有格式，跳过已由其它表示覆盖的类型和超出大小上限的内容
+    fn read_custom_formats(&self, targets: &[String], captured: &[String]) -> Vec<ClipEvent> {
+        let mut events = Vec::new();
+        let Ok(formats) = self.custom_formats.lock().map(|f| f.clone()) else { return events };
+        for target in targets {
+            if captured.contains(target) {
+                continue;
+            }
+            let Some(format) = formats.iter().find(|f| f.matches(target)) else {
+                continue;
+            };
+            let Some(data) = self.backend.read(target) else { continue };
+            if data.len() > format.max_bytes {
+                println!("⚠️ 跳过 {}: {} bytes 超过上限 {}", target, data.len(), format.max_bytes);
+                continue;
+            }
+            events.push(ClipEvent::Custom { mime: target.clone(), data });
+        }
+        events
+    }
+
     pub fn update_latest(&self, data: &[u8]) -> bool {
         let hash = compute_hash(data);
         let now = Instant::now();
@@ -56,7 +225,54 @@ impl ClipboardHandler for SystemHook {
     fn on_clipboard_change(&mut self) -> CallbackResult {
         println!(">> ⚡ 底层事件触发 (Hook Triggered)");
 
-        // 1. 初始化读取器 (每次读取都建议新建实例以获取最新状态)
+        // 1. 暂停记录时不读取剪贴板
+        let mode = self.capture.lock().map(|mut capture| capture.mode()).unwrap_or(CaptureMode::Record);
+        if mode == CaptureMode::Skip {
+            println!("⏸️ 记录已暂停，忽略本次复制");
+            return CallbackResult::Next;
+        }
+
+        // 2. 前台应用命中隐私黑名单时直接丢弃，不读取剪贴板内容
+        let source = self.detector.active_app();
+        if self.is_suppressed(source.as_ref()) {
+            return CallbackResult::Next;
+        }
+
+        // 3. 密码管理器标记为机密的内容：只读取标记本身，按设置跳过或只暂存文本
+        //    无法列出格式时 (如未安装 xclip / wl-clipboard) 照常记录，但识别不到隐藏标记和 RTF 等格式
+        let targets = match self.backend.targets() {
+            Ok(targets) => targets,
+            Err(e) => {
+                if !self.targets_warned {
+                    self.targets_warned = true;
+                    eprintln!("⚠️ {}，无法识别密码管理器的隐藏标记和 RTF 等格式", e);
+                }
+                Vec::new()
+            }
+        };
+        if let Some(marker) = conceal::find_marker(&targets, |mime| self.backend.read(mime)) {
+            println!("🔒 检测到隐藏标记 {}，不保存本次复制", marker);
+            let policy = self.concealed_policy.lock().map(|policy| *policy).unwrap_or_default();
+            if policy == ConcealedPolicy::Ephemeral {
+                if let Ok(text) = Clipboard::new().and_then(|mut ctx| ctx.get_text()) {
+                    let _ = self.sender.send(ClipEvent::Ephemeral { text, source, session: false });
+                }
+            }
+            return CallbackResult::Next;
+        }
+
+        // 4. 隐身模式：只读取文本，保存在内存中
+        if mode == CaptureMode::Memory {
+            if let Ok(text) = Clipboard::new().and_then(|mut ctx| ctx.get_text()) {
+                if self.is_self_write(&text) {
+                    return CallbackResult::Next;
+                }
+                let _ = self.sender.send(ClipEvent::Ephemeral { text, source, session: true });
+            }
+            return CallbackResult::Next;
+        }
+
+        // 5. 初始化读取器 (每次读取都建议新建实例以获取最新状态)
         let mut ctx = match Clipboard::new() {
             Ok(ctx) => ctx,
             Err(e) => {
@@ -65,40 +281,67 @@ impl ClipboardHandler for SystemHook {
             }
         };
 
+        // 6. 读取所有可用表示，而不是只取第一个匹配的格式
+        let mut parts = Vec::new();
+        let mut fingerprint = Vec::new();
+
         if let Ok(file_list) = ctx.get().file_list() {
             let paths_str = file_list.iter()
                 .map(|p| p.to_string_lossy())
                 .collect::<Vec<_>>().join("\n");
-            if !self.update_latest(paths_str.as_bytes()) {
-                return CallbackResult::Next;
-            }
-            let _ = self.sender.send(ClipEvent::FileList(file_list));
+            fingerprint.extend_from_slice(paths_str.as_bytes());
+            parts.push(ClipEvent::FileList(file_list));
         }
-        else if let Ok(img) = ctx.get_image() {
+        if let Ok(img) = ctx.get_image() {
             let data = img.bytes.to_vec();
-            if !self.update_latest(&data) {
-                return CallbackResult::Next;
-            }
-            let _ = self.sender.send(ClipEvent::Image {
+            fingerprint.extend_from_slice(&data);
+            parts.push(ClipEvent::Image {
                 width: img.width,
                 height: img.height,
                 rgba_data: data,
             });
         }
-        else if let Ok(html) = ctx.get().html() {
-            if !self.update_latest(html.as_bytes()) {
-                return CallbackResult::Next;
+        if let Ok(html) = ctx.get().html() {
+            fingerprint.extend_from_slice(html.as_bytes());
+            parts.push(ClipEvent::Html(html));
+        }
+        if let Some(target) = RTF_TARGETS.iter().find(|t| targets.iter().any(|x| x == *t)) {
+            if let Some(rtf) = self.backend.read(target) {
+                let rtf = String::from_utf8_lossy(&rtf).to_string();
+                fingerprint.extend_from_slice(rtf.as_bytes());
+                parts.push(ClipEvent::Rtf(rtf));
             }
-            let _ = self.sender.send(ClipEvent::Html(html));
         }
-        else if let Ok(text) = ctx.get_text() {        // Text 作为兜底
-            if !self.update_latest(text.as_bytes()) {
-                return CallbackResult::Next;
+        if let Ok(text) = ctx.get_text() {
+            fingerprint.extend_from_slice(text.as_bytes());
+            parts.push(ClipEvent::Text(text));
+        }
+
+        let captured: Vec<String> = parts.iter()
+            .filter_map(|p| p.to_mime())
+            .chain(RTF_TARGETS.iter().copied())
+            .map(|m| m.to_string())
+            .collect();
+        for event in self.read_custom_formats(&targets, &captured) {
+            if let ClipEvent::Custom { data, .. } = &event {
+                fingerprint.extend_from_slice(data);
             }
-            let _ = self.sender.send(ClipEvent::Text(text));
-        } else {
+            parts.push(event);
+        }
+
+        if parts.is_empty() {
             eprintln!("未知类型");
+            return CallbackResult::Next;
         }
+        let self_write = parts.iter().any(|p| matches!(p, ClipEvent::Text(text) if self.is_self_write(text)));
+        if self_write {
+            println!("🔒 本程序写入的机密内容，不保存本次复制");
+            return CallbackResult::Next;
+        }
+        if !self.update_latest(&fingerprint) {
+            return CallbackResult::Next;
+        }
+        let _ = self.sender.send(ClipEvent::Bundle { parts, source });
 
         // 继续监听下一条消息
         CallbackResult::Next
@@ -114,4 +357,146 @@ impl ClipboardHandler for SystemHook {
 fn compute_hash(data: &[u8]) -> String {
     let hash = blake3::hash(data);
     hex::encode(hash.as_bytes())
-}
\ No newline at end of file
+}
+
+/// 文本内容的指纹，与 [`ClipboardWriter::fingerprint`] 一致
+pub fn text_fingerprint(text: &str) -> String {
+    compute_hash(text.as_bytes())
+}
+
+// 写回器：持有长期存在的 Clipboard 实例，
+// Linux 上剪贴板内容由持有者进程提供，实例释放后内容可能丢失
+pub struct ClipboardWriter {
+    ctx: Clipboard,
+    backend: Box<dyn MimeBackend>,
+}
+
+impl ClipboardWriter {
+    pub fn new() -> anyhow::Result<Self> {
+        Ok(Self { ctx: Clipboard::new()?, backend: mime::default_backend() })
+    }
+
+    /// 当前剪贴板内容的指纹 (文本优先，其次图片)，用于判断内容是否已被替换
+    pub fn fingerprint(&mut self) -> Option<String> {
+        if let Ok(text) = self.ctx.get_text() {
+            return Some(text_fingerprint(&text));
+        }
+        self.ctx.get_image().ok().map(|img| compute_hash(&img.bytes))
+    }
+
+    /// 清空系统剪贴板
+    pub fn clear(&mut self) -> anyhow::Result<()> {
+        self.ctx.clear()?;
+        Ok(())
+    }
+
+    /// 将记录恢复到系统剪贴板，尽可能同时提供所有表示
+    ///
+    /// 后端支持多格式时一次写入全部表示；否则 arboard 只能同时提供 HTML + 纯文本，
+    /// 文件列表、图片和私有格式只能单独提供，按主表示类型选择，RTF 退回纯文本，
+    /// 无法写入的私有格式退回文本表示
+    pub fn restore(&mut self, data: &ClipData, parts: &[ClipPart]) -> anyhow::Result<()> {
+        if self.backend.supports_multiple() {
+            match self.backend.write(&clip_formats(data, parts)) {
+                Ok(()) => return Ok(()),
+                Err(e) => eprintln!("⚠️ 多格式写入失败，改用 arboard: {}", e),
+            }
+        }
+
+        let part_text = |mime: &str| {
+            parts.iter().find(|p| p.mime == mime).and_then(|p| p.text.clone())
+        };
+
+        match data {
+            ClipData::Files(files) => {
+                self.ctx.set().file_list(files)?;
+            }
+            ClipData::Image(bytes) => {
+                let img = image::load_from_memory(bytes)?.to_rgba8();
+                self.ctx.set().image(ImageData {
+                    width: img.width() as usize,
+                    height: img.height() as usize,
+                    bytes: Cow::Owned(img.into_raw()),
+                })?;
+            }
+            ClipData::Html { text, html } => {
+                let alt = part_text(MIME_TEXT).unwrap_or_else(|| text.clone());
+                self.ctx.set().html(html.as_str(), Some(alt.as_str()))?;
+            }
+            ClipData::Rtf { text, .. } => {
+                // 单格式后端 (如 wl-copy) 写入 RTF 后剪贴板上只剩 text/rtf，
+                // 纯文本目标粘贴不到内容，因此这里只提供纯文本
+                self.ctx.set().text(text.as_str())?;
+            }
+            ClipData::Custom { mime, data } => {
+                // 后端不能写入 (如 macOS / Windows 上同步来的记录) 时退回记录附带的 HTML / 纯文本表示，
+                // 都没有时私有格式本身是文本则按纯文本粘贴
+                if let Err(e) = self.backend.write(&[(mime.clone(), data.clone())]) {
+                    eprintln!("⚠️ {}，改为写入文本表示", e);
+                    match (part_text(MIME_HTML), part_text(MIME_TEXT)) {
+                        (Some(html), alt) => self.ctx.set().html(html.as_str(), alt.as_deref())?,
+                        (None, Some(text)) => self.ctx.set().text(text.as_str())?,
+                        (None, None) => match std::str::from_utf8(data) {
+                            Ok(text) if !text.trim().is_empty() => self.ctx.set().text(text)?,
+                            _ => return Err(e),
+                        },
+                    }
+                }
+            }
+            ClipData::Text(text) | ClipData::Color(text) => {
+                match part_text(MIME_HTML) {
+                    Some(html) => self.ctx.set().html(html.as_str(), Some(text.as_str()))?,
+                    None => self.ctx.set().text(text.as_str())?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 将记录及其附加表示展开为 (MIME, 字节) 列表，主表示在前，同类型只保留第一份
+pub fn clip_formats(data: &ClipData, parts: &[ClipPart]) -> Vec<(String, Vec<u8>)> {
+    let mut formats: Vec<(String, Vec<u8>)> = Vec::new();
+    let mut push = |mime: &str, bytes: Vec<u8>| {
+        if !formats.iter().any(|(m, _)| m == mime) {
+            formats.push((mime.to_string(), bytes));
+        }
+    };
+
+    match data {
+        ClipData::Text(text) | ClipData::Color(text) => push(MIME_TEXT, text.as_bytes().to_vec()),
+        ClipData::Html { html, .. } => push(MIME_HTML, html.as_bytes().to_vec()),
+        ClipData::Rtf { rtf, .. } => push(MIME_RTF, rtf.as_bytes().to_vec()),
+        ClipData::Image(bytes) => push(MIME_PNG, bytes.clone()),
+        ClipData::Files(files) => push(MIME_FILES, file_uri_list(files.iter().map(|f| f.as_str())).into_bytes()),
+        ClipData::Custom { mime, data } => push(mime, data.clone()),
+    }
+
+    for part in parts {
+        match (&part.text, &part.data) {
+            (Some(text), _) if part.mime == MIME_FILES => {
+                push(MIME_FILES, file_uri_list(text.lines()).into_bytes())
+            }
+            (Some(text), _) => push(&part.mime, text.as_bytes().to_vec()),
+            (None, Some(bytes)) => push(&part.mime, bytes.clone()),
+            (None, None) => {}
+        }
+    }
+
+    // HTML / RTF 的纯文本回退
+    match data {
+        ClipData::Html { text, .. } | ClipData::Rtf { text, .. } => push(MIME_TEXT, text.as_bytes().to_vec()),
+        ClipData::Files(files) => push(MIME_TEXT, files.join("\n").into_bytes()),
+        _ => {}
+    }
+    formats
+}
+
+/// 本地路径转为 text/uri-list 格式
+fn file_uri_list<'a>(paths: impl Iterator<Item = &'a str>) -> String {
+    paths
+        .filter(|p| !p.is_empty())
+        .map(|p| if p.contains("://") { p.to_string() } else { format!("file://{}", p) })
+        .collect::<Vec<_>>()
+        .join("\r\n")
+}
@@ -0,0 +1,605 @@
+//! CSS 颜色解析 (CSS Color Level 4)
+//!
+//! 支持 #RGB / #RGBA / #RRGGBB / #RRGGBBAA、命名颜色、transparent，
+//! 以及 rgb() / rgba() / hsl() / hsla() / hwb() / lab() / lch() / oklab() / oklch()，
+//! 逗号分隔的旧语法和空格 + `/ alpha` 的新语法都可以。
+//! 分量超出 CSS 规定的范围时视为无效，避免把普通文本误判为颜色
+
+use serde::Serialize;
+
+/// sRGB 颜色，各分量范围 0.0 ~ 1.0
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rgba {
+    pub r: f64,
+    pub g: f64,
+    pub b: f64,
+    pub a: f64,
+}
+
+/// 同一颜色的几种常用写法
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ColorFormats {
+    pub hex: String,
+    pub rgb: String,
+    pub hsl: String,
+    pub oklch: String,
+}
+
+/// 解析 CSS 颜色，无效时返回 None
+pub fn parse(text: &str) -> Option<Rgba> {
+    let text = text.trim().to_ascii_lowercase();
+    if text.is_empty() || text.len() > 128 {
+        return None;
+    }
+    if let Some(hex) = text.strip_prefix('#') {
+        return parse_hex(hex);
+    }
+    if text == "transparent" {
+        return Some(Rgba { r: 0.0, g: 0.0, b: 0.0, a: 0.0 });
+    }
+    if let Some(rgb) = named(&text) {
+        return Some(Rgba::from_rgb8(rgb[0], rgb[1], rgb[2], 1.0));
+    }
+
+    let open = text.find('(')?;
+    if !text.ends_with(')') {
+        return None;
+    }
+    let name = text[..open].trim_end();
+    let args = parse_args(&text[open + 1..text.len() - 1])?;
+    let color = match name {
+        "rgb" | "rgba" => parse_rgb(&args)?,
+        "hsl" | "hsla" => parse_hsl(&args)?,
+        "hwb" if !args.legacy => parse_hwb(&args)?,
+        "lab" if !args.legacy => parse_lab(&args)?,
+        "lch" if !args.legacy => parse_lch(&args)?,
+        "oklab" if !args.legacy => parse_oklab(&args)?,
+        "oklch" if !args.legacy => parse_oklch(&args)?,
+        _ => return None,
+    };
+    Some(color.clamped())
+}
+
+/// 是否为有效的 CSS 颜色
+pub fn is_color(text: &str) -> bool {
+    parse(text).is_some()
+}
+
+impl Rgba {
+    pub fn from_rgb8(r: u8, g: u8, b: u8, a: f64) -> Self {
+        Self { r: r as f64 / 255.0, g: g as f64 / 255.0, b: b as f64 / 255.0, a }
+    }
+
+    pub fn to_rgba8(&self) -> [u8; 4] {
+        let c = |v: f64| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+        [c(self.r), c(self.g), c(self.b), c(self.a)]
+    }
+
+    /// 规范化存储格式：#rrggbbaa
+    pub fn to_hex8(&self) -> String {
+        let [r, g, b, a] = self.to_rgba8();
+        format!("#{:02x}{:02x}{:02x}{:02x}", r, g, b, a)
+    }
+
+    /// #rrggbb，不透明度小于 1 时为 #rrggbbaa
+    pub fn to_hex(&self) -> String {
+        let [r, g, b, a] = self.to_rgba8();
+        if a == 255 {
+            format!("#{:02x}{:02x}{:02x}", r, g, b)
+        } else {
+            format!("#{:02x}{:02x}{:02x}{:02x}", r, g, b, a)
+        }
+    }
+
+    pub fn to_css_rgb(&self) -> String {
+        let [r, g, b, _] = self.to_rgba8();
+        if self.a >= 1.0 {
+            format!("rgb({}, {}, {})", r, g, b)
+        } else {
+            format!("rgba({}, {}, {}, {})", r, g, b, fmt_num(self.a, 3))
+        }
+    }
+
+    pub fn to_css_hsl(&self) -> String {
+        let (h, s, l) = self.to_hsl();
+        let body = format!("{}, {}%, {}%", fmt_num(h, 1), fmt_num(s * 100.0, 1), fmt_num(l * 100.0, 1));
+        if self.a >= 1.0 {
+            format!("hsl({})", body)
+        } else {
+            format!("hsla({}, {})", body, fmt_num(self.a, 3))
+        }
+    }
+
+    pub fn to_css_oklch(&self) -> String {
+        let (l, c, h) = self.to_oklch();
+        let body = format!("{}% {} {}", fmt_num(l * 100.0, 2), fmt_num(c, 4), fmt_num(h, 2));
+        if self.a >= 1.0 {
+            format!("oklch({})", body)
+        } else {
+            format!("oklch({} / {})", body, fmt_num(self.a, 3))
+        }
+    }
+
+    pub fn formats(&self) -> ColorFormats {
+        ColorFormats {
+            hex: self.to_hex(),
+            rgb: self.to_css_rgb(),
+            hsl: self.to_css_hsl(),
+            oklch: self.to_css_oklch(),
+        }
+    }
+
+    /// 色相 (度)、饱和度、亮度 (0 ~ 1)
+    pub fn to_hsl(&self) -> (f64, f64, f64) {
+        let max = self.r.max(self.g).max(self.b);
+        let min = self.r.min(self.g).min(self.b);
+        let l = (max + min) / 2.0;
+        let d = max - min;
+        if d.abs() < 1e-9 {
+            return (0.0, 0.0, l);
+        }
+        let s = d / (1.0 - (2.0 * l - 1.0).abs());
+        let h = if max == self.r {
+            ((self.g - self.b) / d).rem_euclid(6.0)
+        } else if max == self.g {
+            (self.b - self.r) / d + 2.0
+        } else {
+            (self.r - self.g) / d + 4.0
+        };
+        (h * 60.0, s, l)
+    }
+
+    /// OKLCH：亮度 (0 ~ 1)、色度、色相 (度)
+    pub fn to_oklch(&self) -> (f64, f64, f64) {
+        let (l, a, b) = linear_srgb_to_oklab(to_linear(self.r), to_linear(self.g), to_linear(self.b));
+        let c = (a * a + b * b).sqrt();
+        let h = if c < 1e-4 { 0.0 } else { b.atan2(a).to_degrees().rem_euclid(360.0) };
+        (l, c, h)
+    }
+
+    fn clamped(self) -> Self {
+        Self {
+            r: self.r.clamp(0.0, 1.0),
+            g: self.g.clamp(0.0, 1.0),
+            b: self.b.clamp(0.0, 1.0),
+            a: self.a.clamp(0.0, 1.0),
+        }
+    }
+}
+
+fn fmt_num(value: f64, decimals: usize) -> String {
+    let s = format!("{:.*}", decimals, value);
+    let s = if s.contains('.') { s.trim_end_matches('0').trim_end_matches('.').to_string() } else { s };
+    if s == "-0" { "0".to_string() } else { s }
+}
+
+fn parse_hex(hex: &str) -> Option<Rgba> {
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let digit = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).ok().map(|v| v * 17);
+    let pair = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    let [r, g, b, a] = match hex.len() {
+        3 => [digit(0)?, digit(1)?, digit(2)?, 255],
+        4 => [digit(0)?, digit(1)?, digit(2)?, digit(3)?],
+        6 => [pair(0)?, pair(2)?, pair(4)?, 255],
+        8 => [pair(0)?, pair(2)?, pair(4)?, pair(6)?],
+        _ => return None,
+    };
+    Some(Rgba::from_rgb8(r, g, b, a as f64 / 255.0))
+}
+
+fn named(name: &str) -> Option<[u8; 3]> {
+    NAMED_COLORS
+        .binary_search_by(|(n, _)| (*n).cmp(name))
+        .ok()
+        .map(|i| NAMED_COLORS[i].1)
+}
+
+/// 函数参数中的一个分量
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Value {
+    Number(f64),
+    Percent(f64), // 0 ~ 100
+    Angle(f64),   // 已换算为度
+    None,         // CSS 的 none 关键字，按 0 处理
+}
+
+struct Args {
+    values: Vec<Value>,
+    alpha: Option<Value>,
+    legacy: bool, // 逗号分隔的旧语法
+}
+
+fn parse_args(inner: &str) -> Option<Args> {
+    let inner = inner.trim();
+    if inner.contains(',') {
+        // 旧语法：全部用逗号分隔，不允许出现 / 和 none
+        if inner.contains('/') {
+            return None;
+        }
+        let values = inner.split(',').map(|t| parse_value(t.trim())).collect::<Option<Vec<_>>>()?;
+        if values.contains(&Value::None) || !(3..=4).contains(&values.len()) {
+            return None;
+        }
+        let mut values = values;
+        let alpha = if values.len() == 4 { values.pop() } else { None };
+        return Some(Args { values, alpha, legacy: true });
+    }
+
+    let (main, alpha) = match inner.split_once('/') {
+        Some((main, alpha)) => (main, Some(parse_value(alpha.trim())?)),
+        None => (inner, None),
+    };
+    let values = main.split_whitespace().map(parse_value).collect::<Option<Vec<_>>>()?;
+    if values.len() != 3 {
+        return None;
+    }
+    Some(Args { values, alpha, legacy: false })
+}
+
+fn parse_value(token: &str) -> Option<Value> {
+    if token.is_empty() {
+        return None;
+    }
+    if token == "none" {
+        return Some(Value::None);
+    }
+    if let Some(n) = token.strip_suffix('%') {
+        return number(n).map(Value::Percent);
+    }
+    for (unit, to_deg) in [("deg", 1.0), ("grad", 0.9), ("rad", 180.0 / std::f64::consts::PI), ("turn", 360.0)] {
+        if let Some(n) = token.strip_suffix(unit) {
+            return number(n).map(|v| Value::Angle(v * to_deg));
+        }
+    }
+    number(token).map(Value::Number)
+}
+
+fn number(text: &str) -> Option<f64> {
+    // 只接受普通十进制数，排除 inf / nan 等
+    if text.is_empty() || !text.chars().all(|c| c.is_ascii_digit() || matches!(c, '.' | '-' | '+' | 'e')) {
+        return None;
+    }
+    text.parse::<f64>().ok().filter(|v| v.is_finite())
+}
+
+fn in_range(value: f64, min: f64, max: f64) -> Option<f64> {
+    (min..=max).contains(&value).then_some(value)
+}
+
+/// 透明度：0 ~ 1 或 0% ~ 100%
+fn alpha(args: &Args) -> Option<f64> {
+    match args.alpha {
+        None => Some(1.0),
+        Some(Value::None) => Some(0.0),
+        Some(Value::Number(a)) => in_range(a, 0.0, 1.0),
+        Some(Value::Percent(p)) => in_range(p, 0.0, 100.0).map(|p| p / 100.0),
+        Some(Value::Angle(_)) => None,
+    }
+}
+
+/// 色相：数字或角度，取模到 0 ~ 360
+fn hue(value: Value) -> Option<f64> {
+    match value {
+        Value::Number(h) | Value::Angle(h) => Some(h.rem_euclid(360.0)),
+        Value::None => Some(0.0),
+        Value::Percent(_) => None,
+    }
+}
+
+/// 百分比分量；新语法中也允许不带 % 的数字
+fn percent(value: Value, legacy: bool) -> Option<f64> {
+    match value {
+        Value::Percent(p) => in_range(p, 0.0, 100.0).map(|p| p / 100.0),
+        Value::Number(n) if !legacy => in_range(n, 0.0, 100.0).map(|n| n / 100.0),
+        Value::None => Some(0.0),
+        _ => None,
+    }
+}
+
+/// 数字或百分比分量，`full` 为 100% 对应的数值
+fn number_or_percent(value: Value, min: f64, max: f64, full: f64) -> Option<f64> {
+    match value {
+        Value::Number(n) => in_range(n, min, max),
+        Value::Percent(p) => in_range(p / 100.0 * full, min, max),
+        Value::None => Some(0.0),
+        Value::Angle(_) => None,
+    }
+}
+
+fn parse_rgb(args: &Args) -> Option<Rgba> {
+    let v = &args.values;
+    // 旧语法要求三个分量同为数字或同为百分比
+    if args.legacy {
+        let all_numbers = v.iter().all(|x| matches!(x, Value::Number(_)));
+        let all_percents = v.iter().all(|x| matches!(x, Value::Percent(_)));
+        if !all_numbers && !all_percents {
+            return None;
+        }
+    }
+    let channel = |x: Value| number_or_percent(x, 0.0, 255.0, 255.0).map(|c| c / 255.0);
+    Some(Rgba { r: channel(v[0])?, g: channel(v[1])?, b: channel(v[2])?, a: alpha(args)? })
+}
+
+fn parse_hsl(args: &Args) -> Option<Rgba> {
+    let v = &args.values;
+    let h = hue(v[0])?;
+    let s = percent(v[1], args.legacy)?;
+    let l = percent(v[2], args.legacy)?;
+    let (r, g, b) = hsl_to_rgb(h, s, l);
+    Some(Rgba { r, g, b, a: alpha(args)? })
+}
+
+fn parse_hwb(args: &Args) -> Option<Rgba> {
+    let v = &args.values;
+    let h = hue(v[0])?;
+    let white = percent(v[1], false)?;
+    let black = percent(v[2], false)?;
+    let (r, g, b) = if white + black >= 1.0 {
+        let gray = white / (white + black);
+        (gray, gray, gray)
+    } else {
+        let (r, g, b) = hsl_to_rgb(h, 1.0, 0.5);
+        let scale = 1.0 - white - black;
+        (r * scale + white, g * scale + white, b * scale + white)
+    };
+    Some(Rgba { r, g, b, a: alpha(args)? })
+}
+
+/// lab() 的 L 为 0 ~ 100，a / b 的参考范围是 ±125 (100% 对应 125)
+fn parse_lab(args: &Args) -> Option<Rgba> {
+    let v = &args.values;
+    let l = number_or_percent(v[0], 0.0, 100.0, 100.0)?;
+    let a = number_or_percent(v[1], -160.0, 160.0, 125.0)?;
+    let b = number_or_percent(v[2], -160.0, 160.0, 125.0)?;
+    let (r, g, bl) = lab_to_srgb(l, a, b);
+    Some(Rgba { r, g, b: bl, a: alpha(args)? })
+}
+
+fn parse_lch(args: &Args) -> Option<Rgba> {
+    let v = &args.values;
+    let l = number_or_percent(v[0], 0.0, 100.0, 100.0)?;
+    let c = number_or_percent(v[1], 0.0, 230.0, 150.0)?;
+    let h = hue(v[2])?.to_radians();
+    let (r, g, b) = lab_to_srgb(l, c * h.cos(), c * h.sin());
+    Some(Rgba { r, g, b, a: alpha(args)? })
+}
+
+/// oklab() 的 L 为 0 ~ 1，a / b 的参考范围是 ±0.4 (100% 对应 0.4)
+fn parse_oklab(args: &Args) -> Option<Rgba> {
+    let v = &args.values;
+    let l = number_or_percent(v[0], 0.0, 1.0, 1.0)?;
+    let a = number_or_percent(v[1], -0.5, 0.5, 0.4)?;
+    let b = number_or_percent(v[2], -0.5, 0.5, 0.4)?;
+    let (r, g, bl) = oklab_to_linear_srgb(l, a, b);
+    Some(Rgba { r: from_linear(r), g: from_linear(g), b: from_linear(bl), a: alpha(args)? })
+}
+
+fn parse_oklch(args: &Args) -> Option<Rgba> {
+    let v = &args.values;
+    let l = number_or_percent(v[0], 0.0, 1.0, 1.0)?;
+    let c = number_or_percent(v[1], 0.0, 0.5, 0.4)?;
+    let h = hue(v[2])?.to_radians();
+    let (r, g, b) = oklab_to_linear_srgb(l, c * h.cos(), c * h.sin());
+    Some(Rgba { r: from_linear(r), g: from_linear(g), b: from_linear(b), a: alpha(args)? })
+}
+
+fn hsl_to_rgb(h: f64, s: f64, l: f64) -> (f64, f64, f64) {
+    let f = |n: f64| {
+        let k = (n + h / 30.0) % 12.0;
+        let a = s * l.min(1.0 - l);
+        l - a * (k - 3.0).min(9.0 - k).clamp(-1.0, 1.0)
+    };
+    (f(0.0), f(8.0), f(4.0))
+}
+
+fn to_linear(c: f64) -> f64 {
+    if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+}
+
+fn from_linear(c: f64) -> f64 {
+    let c = c.clamp(0.0, 1.0);
+    if c <= 0.0031308 { c * 12.92 } else { 1.055 * c.powf(1.0 / 2.4) - 0.055 }
+}
+
+/// CIE Lab (D50) 转 sRGB
+fn lab_to_srgb(l: f64, a: f64, b: f64) -> (f64, f64, f64) {
+    const KAPPA: f64 = 24389.0 / 27.0;
+    const EPSILON: f64 = 216.0 / 24389.0;
+    const WHITE_D50: [f64; 3] = [0.3457 / 0.3585, 1.0, (1.0 - 0.3457 - 0.3585) / 0.3585];
+
+    let fy = (l + 16.0) / 116.0;
+    let fx = a / 500.0 + fy;
+    let fz = fy - b / 200.0;
+    let x = if fx.powi(3) > EPSILON { fx.powi(3) } else { (116.0 * fx - 16.0) / KAPPA };
+    let y = if l > KAPPA * EPSILON { fy.powi(3) } else { l / KAPPA };
+    let z = if fz.powi(3) > EPSILON { fz.powi(3) } else { (116.0 * fz - 16.0) / KAPPA };
+    let (x, y, z) = (x * WHITE_D50[0], y * WHITE_D50[1], z * WHITE_D50[2]);
+
+    // Bradford 色适应 D50 -> D65
+    let x65 = 0.955473421488075 * x - 0.02309845494876471 * y + 0.06325924320057072 * z;
+    let y65 = -0.0283697093338637 * x + 1.0099953980813041 * y + 0.021041441191917323 * z;
+    let z65 = 0.012314014864481998 * x - 0.020507649298898964 * y + 1.330365926242124 * z;
+
+    // XYZ (D65) -> 线性 sRGB
+    let r = 3.2409699419045226 * x65 - 1.537383177570094 * y65 - 0.4986107602930034 * z65;
+    let g = -0.9692436362808796 * x65 + 1.8759675015077202 * y65 + 0.04155505740717559 * z65;
+    let bl = 0.05563007969699366 * x65 - 0.20397695888897652 * y65 + 1.0569715142428786 * z65;
+    (from_linear(r), from_linear(g), from_linear(bl))
+}
+
+fn oklab_to_linear_srgb(l: f64, a: f64, b: f64) -> (f64, f64, f64) {
+    let l_ = l + 0.3963377774 * a + 0.2158037573 * b;
+    let m_ = l - 0.1055613458 * a - 0.0638541728 * b;
+    let s_ = l - 0.0894841775 * a - 1.2914855480 * b;
+    let (l3, m3, s3) = (l_.powi(3), m_.powi(3), s_.powi(3));
+    (
+        4.0767416621 * l3 - 3.3077115913 * m3 + 0.2309699292 * s3,
+        -1.2684380046 * l3 + 2.6097574011 * m3 - 0.3413193965 * s3,
+        -0.0041960863 * l3 - 0.7034186147 * m3 + 1.7076147010 * s3,
+    )
+}
+
+fn linear_srgb_to_oklab(r: f64, g: f64, b: f64) -> (f64, f64, f64) {
+    let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+    let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+    let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+    (
+        0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+        1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+        0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+    )
+}
+
+/// CSS 命名颜色，按名称排序以便二分查找
+const NAMED_COLORS: &[(&str, [u8; 3])] = &[
+    ("aliceblue", [240, 248, 255]),
+    ("antiquewhite", [250, 235, 215]),
+    ("aqua", [0, 255, 255]),
+    ("aquamarine", [127, 255, 212]),
+    ("azure", [240, 255, 255]),
+    ("beige", [245, 245, 220]),
+    ("bisque", [255, 228, 196]),
+    ("black", [0, 0, 0]),
+    ("blanchedalmond", [255, 235, 205]),
+    ("blue", [0, 0, 255]),
+    ("blueviolet", [138, 43, 226]),
+    ("brown", [165, 42, 42]),
+    ("burlywood", [222, 184, 135]),
+    ("cadetblue", [95, 158, 160]),
+    ("chartreuse", [127, 255, 0]),
+    ("chocolate", [210, 105, 30]),
+    ("coral", [255, 127, 80]),
+    ("cornflowerblue", [100, 149, 237]),
+    ("cornsilk", [255, 248, 220]),
+    ("crimson", [220, 20, 60]),
+    ("cyan", [0, 255, 255]),
+    ("darkblue", [0, 0, 139]),
+    ("darkcyan", [0, 139, 139]),
+    ("darkgoldenrod", [184, 134, 11]),
+    ("darkgray", [169, 169, 169]),
+    ("darkgreen", [0, 100, 0]),
+    ("darkgrey", [169, 169, 169]),
+    ("darkkhaki", [189, 183, 107]),
+    ("darkmagenta", [139, 0, 139]),
+    ("darkolivegreen", [85, 107, 47]),
+    ("darkorange", [255, 140, 0]),
+    ("darkorchid", [153, 50, 204]),
+    ("darkred", [139, 0, 0]),
+    ("darksalmon", [233, 150, 122]),
+    ("darkseagreen", [143, 188, 143]),
+    ("darkslateblue", [72, 61, 139]),
+    ("darkslategray", [47, 79, 79]),
+    ("darkslategrey", [47, 79, 79]),
+    ("darkturquoise", [0, 206, 209]),
+    ("darkviolet", [148, 0, 211]),
+    ("deeppink", [255, 20, 147]),
+    ("deepskyblue", [0, 191, 255]),
+    ("dimgray", [105, 105, 105]),
+    ("dimgrey", [105, 105, 105]),
+    ("dodgerblue", [30, 144, 255]),
+    ("firebrick", [178, 34, 34]),
+    ("floralwhite", [255, 250, 240]),
+    ("forestgreen", [34, 139, 34]),
+    ("fuchsia", [255, 0, 255]),
+    ("gainsboro", [220, 220, 220]),
+    ("ghostwhite", [248, 248, 255]),
+    ("gold", [255, 215, 0]),
+    ("goldenrod", [218, 165, 32]),
+    ("gray", [128, 128, 128]),
+    ("green", [0, 128, 0]),
+    ("greenyellow", [173, 255, 47]),
+    ("grey", [128, 128, 128]),
+    ("honeydew", [240, 255, 240]),
+    ("hotpink", [255, 105, 180]),
+    ("indianred", [205, 92, 92]),
+    ("indigo", [75, 0, 130]),
+    ("ivory", [255, 255, 240]),
+    ("khaki", [240, 230, 140]),
+    ("lavender", [230, 230, 250]),
+    ("lavenderblush", [255, 240, 245]),
+    ("lawngreen", [124, 252, 0]),
+    ("lemonchiffon", [255, 250, 205]),
+    ("lightblue", [173, 216, 230]),
+    ("lightcoral", [240, 128, 128]),
+    ("lightcyan", [224, 255, 255]),
+    ("lightgoldenrodyellow", [250, 250, 210]),
+    ("lightgray", [211, 211, 211]),
+    ("lightgreen", [144, 238, 144]),
+    ("lightgrey", [211, 211, 211]),
+    ("lightpink", [255, 182, 193]),
+    ("lightsalmon", [255, 160, 122]),
+    ("lightseagreen", [32, 178, 170]),
+    ("lightskyblue", [135, 206, 250]),
+    ("lightslategray", [119, 136, 153]),
+    ("lightslategrey", [119, 136, 153]),
+    ("lightsteelblue", [176, 196, 222]),
+    ("lightyellow", [255, 255, 224]),
+    ("lime", [0, 255, 0]),
+    ("limegreen", [50, 205, 50]),
+    ("linen", [250, 240, 230]),
+    ("magenta", [255, 0, 255]),
+    ("maroon", [128, 0, 0]),
+    ("mediumaquamarine", [102, 205, 170]),
+    ("mediumblue", [0, 0, 205]),
+    ("mediumorchid", [186, 85, 211]),
+    ("mediumpurple", [147, 112, 219]),
+    ("mediumseagreen", [60, 179, 113]),
+    ("mediumslateblue", [123, 104, 238]),
+    ("mediumspringgreen", [0, 250, 154]),
+    ("mediumturquoise", [72, 209, 204]),
+    ("mediumvioletred", [199, 21, 133]),
+    ("midnightblue", [25, 25, 112]),
+    ("mintcream", [245, 255, 250]),
+    ("mistyrose", [255, 228, 225]),
+    ("moccasin", [255, 228, 181]),
+    ("navajowhite", [255, 222, 173]),
+    ("navy", [0, 0, 128]),
+    ("oldlace", [253, 245, 230]),
+    ("olive", [128, 128, 0]),
+    ("olivedrab", [107, 142, 35]),
+    ("orange", [255, 165, 0]),
+    ("orangered", [255, 69, 0]),
+    ("orchid", [218, 112, 214]),
+    ("palegoldenrod", [238, 232, 170]),
+    ("palegreen", [152, 251, 152]),
+    ("paleturquoise", [175, 238, 238]),
+    ("palevioletred", [219, 112, 147]),
+    ("papayawhip", [255, 239, 213]),
+    ("peachpuff", [255, 218, 185]),
+    ("peru", [205, 133, 63]),
+    ("pink", [255, 192, 203]),
+    ("plum", [221, 160, 221]),
+    ("powderblue", [176, 224, 230]),
+    ("purple", [128, 0, 128]),
+    ("rebeccapurple", [102, 51, 153]),
+    ("red", [255, 0, 0]),
+    ("rosybrown", [188, 143, 143]),
+    ("royalblue", [65, 105, 225]),
+    ("saddlebrown", [139, 69, 19]),
+    ("salmon", [250, 128, 114]),
+    ("sandybrown", [244, 164, 96]),
+    ("seagreen", [46, 139, 87]),
+    ("seashell", [255, 245, 238]),
+    ("sienna", [160, 82, 45]),
+    ("silver", [192, 192, 192]),
+    ("skyblue", [135, 206, 235]),
+    ("slateblue", [106, 90, 205]),
+    ("slategray", [112, 128, 144]),
+    ("slategrey", [112, 128, 144]),
+    ("snow", [255, 250, 250]),
+    ("springgreen", [0, 255, 127]),
+    ("steelblue", [70, 130, 180]),
+    ("tan", [210, 180, 140]),
+    ("teal", [0, 128, 128]),
+    ("thistle", [216, 191, 216]),
+    ("tomato", [255, 99, 71]),
+    ("turquoise", [64, 224, 208]),
+    ("violet", [238, 130, 238]),
+    ("wheat", [245, 222, 179]),
+    ("white", [255, 255, 255]),
+    ("whitesmoke", [245, 245, 245]),
+    ("yellow", [255, 255, 0]),
+    ("yellowgreen", [154, 205, 50]),
+];
@@ -0,0 +1,128 @@
+//! 密码管理器的隐藏标记：识别剪贴板上的机密内容，并在内存中暂存会过期的临时记录
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+use crate::source_app::SourceApp;
+
+/// KDE 约定的密码管理器标记，值为 "secret"
+pub const KDE_PASSWORD_HINT: &str = "x-kde-passwordManagerHint";
+
+/// 剪贴板上表示"不要记录"的格式
+pub struct Marker {
+    pub mime: &'static str,
+    pub value: Option<&'static [u8]>, // None 表示只要存在该格式就视为机密
+}
+
+/// 已知的隐藏标记
+///
+/// 只有 Linux 的后端能列出剪贴板格式，因此只识别 Linux 上的约定；
+/// macOS (org.nspasteboard.*) 和 Windows (ExcludeClipboardContentFromMonitorProcessing 等)
+/// 的标记需要读取原生格式列表，目前不支持
+pub const MARKERS: &[Marker] = &[
+    // KDE Klipper 约定，KeePassXC 等密码管理器在 Linux 上使用
+    Marker { mime: KDE_PASSWORD_HINT, value: Some(b"secret") },
+];
+
+/// 在剪贴板提供的格式中查找隐藏标记，返回命中的格式名
+///
+/// 只读取标记本身，不读取剪贴板内容
+pub fn find_marker(targets: &[String], read: impl Fn(&str) -> Option<Vec<u8>>) -> Option<&'static str> {
+    MARKERS.iter()
+        .filter(|marker| targets.iter().any(|t| t == marker.mime))
+        .find(|marker| match marker.value {
+            None => true,
+            Some(expected) => read(marker.mime).is_some_and(|data| data.trim_ascii() == expected),
+        })
+        .map(|marker| marker.mime)
+}
+
+/// 剪贴板监听线程和界面共享的处理方式，修改设置后立即生效
+pub type SharedPolicy = Arc<Mutex<ConcealedPolicy>>;
+
+/// 带隐藏标记内容的处理方式
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ConcealedPolicy {
+    /// 完全不记录
+    #[default]
+    Skip,
+    /// 只在内存中保留一段时间，不写入数据库
+    Ephemeral,
+}
+
+/// 内存中的临时记录
+#[derive(Debug, Clone, Serialize)]
+pub struct EphemeralClip {
+    pub id: u64,
+    #[serde(skip_serializing)]
+    pub text: String,
+    pub preview: String, // 带隐藏标记或检测为敏感的内容只显示遮盖后的预览
+    pub source_app: Option<SourceApp>,
+    pub expires_at: Option<i64>, // 过期时间，与 created_at 一样为微秒时间戳；None 表示隐身模式下的记录，退出隐身时丢弃
+}
+
+/// 临时记录列表，只存在于内存中，过期或退出隐身模式后丢弃
+#[derive(Default)]
+pub struct EphemeralClips {
+    items: Vec<EphemeralClip>,
+    next_id: u64,
+}
+
+impl EphemeralClips {
+    /// 带隐藏标记的内容：遮盖预览，到 `expires_at` (微秒时间戳) 丢弃
+    pub fn push(&mut self, text: String, source_app: Option<SourceApp>, expires_at: i64) -> u64 {
+        let preview = mask(&text);
+        self.insert(text, preview, source_app, Some(expires_at))
+    }
+
+    /// 隐身模式下复制的内容：保留到退出隐身模式，敏感内容同样遮盖预览
+    pub fn push_session(&mut self, text: String, source_app: Option<SourceApp>) -> u64 {
+        let preview = match crate::secret::detect(&text) {
+            Some(kind) => crate::secret::mask(Some(kind.as_str()), &text),
+            None => text.chars().take(100).collect::<String>().replace('\n', " "),
+        };
+        self.insert(text, preview, source_app, None)
+    }
+
+    fn insert(&mut self, text: String, preview: String, source_app: Option<SourceApp>, expires_at: Option<i64>) -> u64 {
+        self.next_id += 1;
+        self.items.push(EphemeralClip { id: self.next_id, text, preview, source_app, expires_at });
+        self.next_id
+    }
+
+    /// 未过期的记录，最新的在前
+    pub fn list(&mut self) -> Vec<EphemeralClip> {
+        self.purge_expired();
+        self.items.iter().rev().cloned().collect()
+    }
+
+    /// 未过期记录的原文
+    pub fn get(&mut self, id: u64) -> Option<String> {
+        self.purge_expired();
+        self.items.iter().find(|item| item.id == id).map(|item| item.text.clone())
+    }
+
+    /// 丢弃过期的记录，返回它们的 ID
+    pub fn purge_expired(&mut self) -> Vec<u64> {
+        let now = Utc::now().timestamp_micros();
+        self.drain(|item| item.expires_at.is_some_and(|at| at <= now))
+    }
+
+    /// 丢弃隐身模式下的记录，返回它们的 ID
+    pub fn clear_session(&mut self) -> Vec<u64> {
+        self.drain(|item| item.expires_at.is_none())
+    }
+
+    fn drain(&mut self, remove: impl Fn(&EphemeralClip) -> bool) -> Vec<u64> {
+        let removed = self.items.iter().filter(|item| remove(item)).map(|item| item.id).collect();
+        self.items.retain(|item| !remove(item));
+        removed
+    }
+}
+
+/// 遮盖预览：只显示长度，不泄露任何字符
+fn mask(text: &str) -> String {
+    format!("•••••••• ({} 字符)", text.chars().count())
+}
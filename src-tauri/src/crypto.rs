@@ -0,0 +1,190 @@
+//! 静态加密：数据库由 SQLCipher 整库加密，图片、附加表示和外置文本逐个文件用 XChaCha20-Poly1305 加密
+//!
+//! 密钥由口令经 Argon2id 派生，或随机生成后保存在系统钥匙串中；数据目录下的 encryption.json 只记录密钥来源和盐
+
+use anyhow::{anyhow, Context, Result};
+use argon2::Argon2;
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use zeroize::Zeroizing;
+
+const CONFIG_FILE: &str = "encryption.json";
+
+/// 钥匙串中的条目
+const KEYRING_SERVICE: &str = "pastee";
+const KEYRING_USER: &str = "store-key";
+
+/// 加密文件的头部，用于区分迁移中断时尚未加密的文件
+pub const SEALED_MAGIC: &[u8; 6] = b"PSTEE1";
+
+const NONCE_LEN: usize = 24;
+const SALT_LEN: usize = 16;
+
+/// 存储使用的加密算法
+pub trait Cipher: Send + Sync {
+    /// 加密文件内容，输出带 [`SEALED_MAGIC`] 头部
+    fn seal(&self, plaintext: &[u8]) -> Result<Vec<u8>>;
+
+    /// 解密 [`Cipher::seal`] 的输出
+    fn open(&self, sealed: &[u8]) -> Result<Vec<u8>>;
+
+    /// SQLCipher 的原始密钥，形如 x'…'
+    fn sql_key(&self) -> String;
+}
+
+/// 文件内容是否已加密
+pub fn is_sealed(data: &[u8]) -> bool {
+    data.starts_with(SEALED_MAGIC)
+}
+
+/// 口令派生密钥用的随机盐
+pub fn random_salt() -> Result<[u8; SALT_LEN]> {
+    let mut salt = [0u8; SALT_LEN];
+    getrandom::fill(&mut salt).map_err(|e| anyhow!("无法生成随机数: {}", e))?;
+    Ok(salt)
+}
+
+/// 256 位存储密钥，释放时清零
+pub struct StoreKey(Zeroizing<[u8; 32]>);
+
+impl StoreKey {
+    /// 由口令派生 (Argon2id，默认参数)
+    pub fn derive(passphrase: &str, salt: &[u8]) -> Result<Self> {
+        let mut key = Zeroizing::new([0u8; 32]);
+        Argon2::default()
+            .hash_password_into(passphrase.as_bytes(), salt, &mut *key)
+            .map_err(|e| anyhow!("密钥派生失败: {}", e))?;
+        Ok(Self(key))
+    }
+
+    /// 随机生成，用于保存到系统钥匙串
+    pub fn generate() -> Result<Self> {
+        let mut key = Zeroizing::new([0u8; 32]);
+        getrandom::fill(&mut *key).map_err(|e| anyhow!("无法生成随机数: {}", e))?;
+        Ok(Self(key))
+    }
+
+    /// 从系统钥匙串读取
+    pub fn from_keyring() -> Result<Self> {
+        let encoded = Zeroizing::new(keyring_entry()?.get_password().context("无法从系统钥匙串读取密钥")?);
+        let bytes = Zeroizing::new(hex::decode(encoded.as_str()).context("钥匙串中的密钥格式错误")?);
+        let mut key = Zeroizing::new([0u8; 32]);
+        if bytes.len() != key.len() {
+            return Err(anyhow!("钥匙串中的密钥长度错误"));
+        }
+        key.copy_from_slice(&bytes);
+        Ok(Self(key))
+    }
+
+    /// 保存到系统钥匙串
+    pub fn save_to_keyring(&self) -> Result<()> {
+        let encoded = Zeroizing::new(hex::encode(self.0.as_slice()));
+        keyring_entry()?.set_password(&encoded).context("无法写入系统钥匙串")
+    }
+
+    fn cipher(&self) -> XChaCha20Poly1305 {
+        XChaCha20Poly1305::new(Key::from_slice(self.0.as_slice()))
+    }
+}
+
+impl Cipher for StoreKey {
+    fn seal(&self, plaintext: &[u8]) -> Result<Vec<u8>> {
+        let mut nonce = [0u8; NONCE_LEN];
+        getrandom::fill(&mut nonce).map_err(|e| anyhow!("无法生成随机数: {}", e))?;
+        let ciphertext = self.cipher()
+            .encrypt(XNonce::from_slice(&nonce), plaintext)
+            .map_err(|_| anyhow!("加密失败"))?;
+
+        let mut sealed = Vec::with_capacity(SEALED_MAGIC.len() + NONCE_LEN + ciphertext.len());
+        sealed.extend_from_slice(SEALED_MAGIC);
+        sealed.extend_from_slice(&nonce);
+        sealed.extend_from_slice(&ciphertext);
+        Ok(sealed)
+    }
+
+    fn open(&self, sealed: &[u8]) -> Result<Vec<u8>> {
+        if !is_sealed(sealed) || sealed.len() < SEALED_MAGIC.len() + NONCE_LEN {
+            return Err(anyhow!("不是加密文件"));
+        }
+        let (nonce, ciphertext) = sealed[SEALED_MAGIC.len()..].split_at(NONCE_LEN);
+        self.cipher()
+            .decrypt(XNonce::from_slice(nonce), ciphertext)
+            .map_err(|_| anyhow!("解密失败：密钥错误或文件已损坏"))
+    }
+
+    fn sql_key(&self) -> String {
+        format!("x'{}'", hex::encode(self.0.as_slice()))
+    }
+}
+
+fn keyring_entry() -> Result<keyring::Entry> {
+    keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER).map_err(|e| anyhow!("系统钥匙串不可用: {}", e))
+}
+
+/// 密钥来源
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum KeySource {
+    /// 解锁时输入口令
+    Passphrase,
+    /// 系统钥匙串，解锁不需要口令
+    Keyring,
+}
+
+/// 加密存储的元数据，保存在数据目录下的 encryption.json；文件不存在表示未加密
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncryptionConfig {
+    pub key_source: KeySource,
+    pub salt: Option<String>, // 口令模式下 Argon2 的盐 (hex)
+    pub complete: bool,       // 已有数据是否已全部加密；迁移中断时为 false，解锁后继续
+}
+
+impl EncryptionConfig {
+    pub fn load<P: AsRef<Path>>(data_dir: P) -> Option<Self> {
+        let json = fs::read_to_string(data_dir.as_ref().join(CONFIG_FILE)).ok()?;
+        serde_json::from_str(&json)
+            .map_err(|e| eprintln!("⚠️ 加密配置解析失败: {}", e))
+            .ok()
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, data_dir: P) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(data_dir.as_ref().join(CONFIG_FILE), json).context("Failed to write encryption config")?;
+        Ok(())
+    }
+
+    /// 为新的加密存储生成密钥：有口令时由口令派生，否则随机生成并保存到系统钥匙串
+    pub fn create(passphrase: Option<&str>) -> Result<(Self, StoreKey)> {
+        match passphrase {
+            Some(passphrase) => {
+                if passphrase.is_empty() {
+                    return Err(anyhow!("口令不能为空"));
+                }
+                let salt = random_salt()?;
+                let key = StoreKey::derive(passphrase, &salt)?;
+                let config = Self { key_source: KeySource::Passphrase, salt: Some(hex::encode(salt)), complete: false };
+                Ok((config, key))
+            }
+            None => {
+                let key = StoreKey::generate()?;
+                key.save_to_keyring()?;
+                Ok((Self { key_source: KeySource::Keyring, salt: None, complete: false }, key))
+            }
+        }
+    }
+
+    /// 取得已加密存储的密钥，口令模式下 `passphrase` 必填
+    pub fn key(&self, passphrase: Option<&str>) -> Result<StoreKey> {
+        match self.key_source {
+            KeySource::Passphrase => {
+                let passphrase = passphrase.ok_or_else(|| anyhow!("需要输入口令"))?;
+                let salt = hex::decode(self.salt.as_deref().unwrap_or_default()).context("加密配置中的盐格式错误")?;
+                StoreKey::derive(passphrase, &salt)
+            }
+            KeySource::Keyring => StoreKey::from_keyring(),
+        }
+    }
+}
@@ -0,0 +1,99 @@
+//! 识别以文本形式复制的图片：data URI (data:image/png;base64,...) 或裸 base64
+
+use base64::alphabet;
+use base64::engine::{general_purpose::GeneralPurpose, DecodePaddingMode, GeneralPurposeConfig};
+use base64::Engine as _;
+
+/// 裸 base64 至少这么长才尝试解码，避免把短的随机串当成图片
+const MIN_RAW_BASE64_LEN: usize = 64;
+
+/// 兼容有无 padding 的写法
+const PADDING_INDIFFERENT: GeneralPurposeConfig =
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent);
+const STANDARD: GeneralPurpose = GeneralPurpose::new(&alphabet::STANDARD, PADDING_INDIFFERENT);
+const URL_SAFE: GeneralPurpose = GeneralPurpose::new(&alphabet::URL_SAFE, PADDING_INDIFFERENT);
+
+/// 从文本中解码出的图片
+#[derive(Debug, Clone, PartialEq)]
+pub enum EncodedImage {
+    /// PNG / JPEG / GIF / WebP 等位图文件的字节
+    Raster(Vec<u8>),
+    /// SVG 源码
+    Svg(String),
+}
+
+/// 解码文本形式的图片，文本不是图片时返回 None
+///
+/// 位图只检查文件头，是否能完整解码由调用方决定
+pub fn decode_image(text: &str) -> Option<EncodedImage> {
+    let text = text.trim();
+    match text.get(..5) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("data:") => decode_data_uri(&text[5..]),
+        _ => decode_raw_base64(text).map(EncodedImage::Raster),
+    }
+}
+
+/// 解析 data URI 中 "data:" 之后的部分：[mime][;参数][;base64],数据
+fn decode_data_uri(rest: &str) -> Option<EncodedImage> {
+    let (header, payload) = rest.split_once(',')?;
+    let mut params = header.split(';').map(str::trim);
+    let mime = params.next()?.to_ascii_lowercase();
+    if !mime.starts_with("image/") {
+        return None;
+    }
+    let is_base64 = params.any(|p| p.eq_ignore_ascii_case("base64"));
+
+    let bytes = if is_base64 {
+        decode_base64(payload)?
+    } else {
+        percent_decode(payload)
+    };
+
+    if mime == "image/svg+xml" {
+        let markup = String::from_utf8(bytes).ok()?;
+        return crate::svg::is_svg(&markup).then_some(EncodedImage::Svg(markup));
+    }
+    image::guess_format(&bytes).ok()?;
+    Some(EncodedImage::Raster(bytes))
+}
+
+/// 整段文本都是 base64 且解码后是已知图片格式
+fn decode_raw_base64(text: &str) -> Option<Vec<u8>> {
+    if text.len() < MIN_RAW_BASE64_LEN {
+        return None;
+    }
+    let valid = text.bytes().all(|b| {
+        b.is_ascii_alphanumeric() || matches!(b, b'+' | b'/' | b'-' | b'_' | b'=') || b.is_ascii_whitespace()
+    });
+    if !valid {
+        return None;
+    }
+    let bytes = decode_base64(text)?;
+    image::guess_format(&bytes).ok()?;
+    Some(bytes)
+}
+
+fn decode_base64(payload: &str) -> Option<Vec<u8>> {
+    let compact: String = payload.chars().filter(|c| !c.is_ascii_whitespace()).collect();
+    STANDARD.decode(&compact).or_else(|_| URL_SAFE.decode(&compact)).ok()
+}
+
+/// data URI 非 base64 形式使用 URL 百分号编码
+fn percent_decode(payload: &str) -> Vec<u8> {
+    let bytes = payload.as_bytes();
+    let hex = |b: u8| (b as char).to_digit(16);
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            if let (Some(hi), Some(lo)) = (hex(bytes[i + 1]), hex(bytes[i + 2])) {
+                out.push((hi * 16 + lo) as u8);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    out
+}
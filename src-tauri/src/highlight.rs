@@ -0,0 +1,129 @@
+//! 语法高亮：使用 syntect 把代码切分为带颜色的片段，前端直接按片段渲染
+
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use std::sync::LazyLock;
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{Color, FontStyle, Style, ThemeSet};
+use syntect::parsing::{SyntaxReference, SyntaxSet};
+use syntect::util::LinesWithEndings;
+
+/// 默认主题 (浅色)
+pub const DEFAULT_THEME: &str = "InspiredGitHub";
+
+/// 最多高亮的行数，超出部分不返回
+const MAX_LINES: usize = 5000;
+
+static SYNTAXES: LazyLock<SyntaxSet> = LazyLock::new(SyntaxSet::load_defaults_newlines);
+static THEMES: LazyLock<ThemeSet> = LazyLock::new(ThemeSet::load_defaults);
+
+/// 一段连续同样式的文本
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct HighlightSpan {
+    pub text: String,
+    pub color: String, // #rrggbb 或 #rrggbbaa
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+}
+
+/// 高亮结果，每行一个片段数组 (不含换行符)
+#[derive(Debug, Clone, Serialize)]
+pub struct Highlighted {
+    pub language: String,
+    pub background: String,
+    pub lines: Vec<Vec<HighlightSpan>>,
+    pub truncated: bool,
+}
+
+/// 可用的主题名称
+pub fn themes() -> Vec<String> {
+    THEMES.themes.keys().cloned().collect()
+}
+
+/// 按语言标识高亮文本；`language` 为 None 或未知时按纯文本处理
+pub fn highlight(text: &str, language: Option<&str>, theme: Option<&str>) -> Result<Highlighted> {
+    let theme_name = theme.unwrap_or(DEFAULT_THEME);
+    let theme = THEMES.themes.get(theme_name).ok_or_else(|| anyhow!("未知主题: {}", theme_name))?;
+    let syntax = language
+        .and_then(syntax_for)
+        .unwrap_or_else(|| SYNTAXES.find_syntax_plain_text());
+
+    let mut highlighter = HighlightLines::new(syntax, theme);
+    let mut lines = Vec::new();
+    let mut truncated = false;
+    for (index, line) in LinesWithEndings::from(text).enumerate() {
+        if index >= MAX_LINES {
+            truncated = true;
+            break;
+        }
+        let ranges = highlighter.highlight_line(line, &SYNTAXES)?;
+        lines.push(to_spans(&ranges));
+    }
+
+    Ok(Highlighted {
+        language: language.unwrap_or("text").to_string(),
+        background: theme.settings.background.map(hex).unwrap_or_else(|| "#ffffff".to_string()),
+        lines,
+        truncated,
+    })
+}
+
+/// 语言标识映射到 syntect 的语法定义
+fn syntax_for(language: &str) -> Option<&'static SyntaxReference> {
+    let extension = match language {
+        "rust" => "rs",
+        "python" => "py",
+        // 内置语法没有 TypeScript，使用 JavaScript 近似
+        "javascript" | "typescript" => "js",
+        "go" => "go",
+        "java" => "java",
+        "c" => "c",
+        "cpp" => "cpp",
+        "csharp" => "cs",
+        "ruby" => "rb",
+        "php" => "php",
+        "shell" => "sh",
+        "sql" => "sql",
+        "json" => "json",
+        "html" => "html",
+        "xml" => "xml",
+        "css" => "css",
+        other => other,
+    };
+    SYNTAXES.find_syntax_by_extension(extension)
+}
+
+fn to_spans(ranges: &[(Style, &str)]) -> Vec<HighlightSpan> {
+    let mut spans: Vec<HighlightSpan> = Vec::new();
+    for (style, text) in ranges {
+        let text = text.trim_end_matches(['\n', '\r']);
+        if text.is_empty() {
+            continue;
+        }
+        let span = HighlightSpan {
+            text: text.to_string(),
+            color: hex(style.foreground),
+            bold: style.font_style.contains(FontStyle::BOLD),
+            italic: style.font_style.contains(FontStyle::ITALIC),
+            underline: style.font_style.contains(FontStyle::UNDERLINE),
+        };
+        // 合并样式相同的相邻片段
+        match spans.last_mut() {
+            Some(last) if last.color == span.color && last.bold == span.bold
+                && last.italic == span.italic && last.underline == span.underline => {
+                last.text.push_str(&span.text);
+            }
+            _ => spans.push(span),
+        }
+    }
+    spans
+}
+
+fn hex(color: Color) -> String {
+    if color.a == 0xFF {
+        format!("#{:02x}{:02x}{:02x}", color.r, color.g, color.b)
+    } else {
+        format!("#{:02x}{:02x}{:02x}{:02x}", color.r, color.g, color.b, color.a)
+    }
+}
@@ -0,0 +1,168 @@
+//! HTML 转纯文本：用于 HTML 剪贴板内容的预览和搜索
+//!
+//! 使用 HTML5 解析器处理实体、属性中的 `>`、注释等情况，
+//! 段落 / 列表项 / 表格行转为换行，单元格之间以制表符分隔
+
+use ego_tree::NodeRef;
+use scraper::{Html, Node};
+
+/// 内容不可见的元素，整棵子树跳过
+const SKIP_ELEMENTS: &[&str] = &[
+    "head", "script", "style", "template", "noscript", "title", "meta", "link", "iframe", "object",
+];
+
+/// 前后空一行的块级元素
+const PARAGRAPH_ELEMENTS: &[&str] = &[
+    "p", "h1", "h2", "h3", "h4", "h5", "h6", "blockquote", "pre", "table", "ul", "ol", "dl",
+    "figure", "hr",
+];
+
+/// 独占一行的块级元素
+const LINE_ELEMENTS: &[&str] = &[
+    "div", "li", "tr", "dt", "dd", "section", "article", "header", "footer", "nav", "aside",
+    "main", "address", "caption", "figcaption", "details", "summary", "form", "fieldset",
+    "thead", "tbody", "tfoot",
+];
+
+/// 提取 HTML 中的可见文本
+pub fn to_plain_text(html: &str) -> String {
+    let document = Html::parse_document(html);
+    let mut writer = TextWriter::default();
+    walk(document.tree.root(), &mut writer);
+    writer.finish()
+}
+
+fn walk(node: NodeRef<Node>, writer: &mut TextWriter) {
+    // 用显式栈遍历，避免深层嵌套导致栈溢出
+    enum Step<'a> {
+        Enter(NodeRef<'a, Node>),
+        Exit(&'a str),
+    }
+
+    let mut stack = vec![Step::Enter(node)];
+    while let Some(step) = stack.pop() {
+        match step {
+            Step::Enter(node) => match node.value() {
+                Node::Text(text) => writer.push_text(text),
+                Node::Element(element) => {
+                    let name = element.name();
+                    if SKIP_ELEMENTS.contains(&name) {
+                        continue;
+                    }
+                    writer.open(name);
+                    stack.push(Step::Exit(name));
+                    for child in node.children().rev() {
+                        stack.push(Step::Enter(child));
+                    }
+                }
+                Node::Document | Node::Fragment => {
+                    for child in node.children().rev() {
+                        stack.push(Step::Enter(child));
+                    }
+                }
+                _ => {}
+            },
+            Step::Exit(name) => writer.close(name),
+        }
+    }
+}
+
+#[derive(Default)]
+struct TextWriter {
+    out: String,
+    pending_space: bool,
+    pending_breaks: usize,
+    pre_depth: usize,
+}
+
+impl TextWriter {
+    fn open(&mut self, name: &str) {
+        match name {
+            "br" => {
+                self.flush_breaks();
+                self.out.push('\n');
+                self.pending_space = false;
+            }
+            "td" | "th" => {
+                if self.pending_breaks == 0 && !self.out.is_empty() && !self.out.ends_with('\n') {
+                    self.out.push('\t');
+                    self.pending_space = false;
+                }
+            }
+            "pre" => {
+                self.block(2);
+                self.pre_depth += 1;
+            }
+            _ => self.block_for(name),
+        }
+    }
+
+    fn close(&mut self, name: &str) {
+        if name == "pre" {
+            self.pre_depth = self.pre_depth.saturating_sub(1);
+            self.block(2);
+        } else {
+            self.block_for(name);
+        }
+    }
+
+    fn block_for(&mut self, name: &str) {
+        if PARAGRAPH_ELEMENTS.contains(&name) {
+            self.block(2);
+        } else if LINE_ELEMENTS.contains(&name) {
+            self.block(1);
+        }
+    }
+
+    /// 要求后续文本前至少有 `lines` 个换行
+    fn block(&mut self, lines: usize) {
+        self.pending_breaks = self.pending_breaks.max(lines);
+        self.pending_space = false;
+    }
+
+    fn flush_breaks(&mut self) {
+        if self.pending_breaks == 0 {
+            return;
+        }
+        if !self.out.is_empty() {
+            let existing = self.out.len() - self.out.trim_end_matches('\n').len();
+            for _ in existing..self.pending_breaks {
+                self.out.push('\n');
+            }
+        }
+        self.pending_breaks = 0;
+    }
+
+    fn push_text(&mut self, text: &str) {
+        if self.pre_depth > 0 {
+            if !text.is_empty() {
+                self.flush_breaks();
+                self.out.push_str(text);
+            }
+            return;
+        }
+        for c in text.chars() {
+            if c.is_whitespace() {
+                self.pending_space = true;
+                continue;
+            }
+            if self.pending_breaks > 0 {
+                self.flush_breaks();
+            } else if self.pending_space && !self.out.is_empty() && !self.out.ends_with(['\n', '\t']) {
+                self.out.push(' ');
+            }
+            self.pending_space = false;
+            self.out.push(c);
+        }
+    }
+
+    fn finish(self) -> String {
+        self.out
+            .lines()
+            .map(str::trim_end)
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_string()
+    }
+}
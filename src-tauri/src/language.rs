@@ -0,0 +1,217 @@
+//! 编程语言检测：按各语言特征打分，取得分最高且超过阈值的语言
+//!
+//! 返回的标识是稳定的小写名称，会写入数据库 `language` 列
+
+use crate::classify::{Detector, SqlDetector};
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// 支持检测的语言
+pub const LANGUAGES: &[&str] = &[
+    "rust", "python", "javascript", "typescript", "go", "java", "c", "cpp", "csharp", "ruby",
+    "php", "shell", "sql", "json", "html", "xml", "css",
+];
+
+/// 只检测前 32 KiB
+const MAX_DETECT_BYTES: usize = 32 * 1024;
+
+/// 最低得分，低于该值认为无法判断
+const MIN_SCORE: usize = 4;
+
+/// 同一特征最多计入的次数，避免某个常见模式刷分
+const MAX_HITS: usize = 3;
+
+struct Signature {
+    language: &'static str,
+    pattern: &'static str,
+    weight: usize,
+}
+
+const fn sig(language: &'static str, pattern: &'static str, weight: usize) -> Signature {
+    Signature { language, pattern, weight }
+}
+
+const SIGNATURES: &[Signature] = &[
+    // Rust
+    sig("rust", r"\bfn\s+\w+\s*[<(]", 3),
+    sig("rust", r"\blet\s+mut\b", 3),
+    sig("rust", r"\bimpl\b(\s*<.*>)?\s+\w+", 2),
+    sig("rust", r"\bpub(\(crate\))?\s+(fn|struct|enum|mod|use|trait|const)\b", 3),
+    sig("rust", r"\b(println|format|vec|macro_rules|assert_eq|panic)!", 3),
+    sig("rust", r"(?m)^\s*use\s+[\w:]+::", 3),
+    sig("rust", r"&str\b|&mut\s|\bOption<|\bResult<|\bVec<", 2),
+    sig("rust", r"(?m)^\s*#\[\w+", 2),
+    // Python
+    sig("python", r"(?m)^\s*def\s+\w+\s*\(.*\)\s*(->\s*[^:]+)?:\s*$", 4),
+    sig("python", r"(?m)^\s*class\s+\w+(\(.*\))?:\s*$", 4),
+    sig("python", r"(?m)^\s*(from\s+[\w.]+\s+)?import\s+[\w.]+(\s+as\s+\w+)?\s*$", 2),
+    sig("python", r"\bself\.\w+", 2),
+    sig("python", r"(?m)^\s*(elif|except|finally|with)\b.*:\s*$", 3),
+    sig("python", r"\b(None|True|False)\b", 1),
+    sig("python", r"if __name__ == .__main__.", 5),
+    sig("python", r"(?m)^\s*print\(", 1),
+    // JavaScript (TypeScript 共用)
+    sig("javascript", r"\bfunction\s*\w*\s*\(", 2),
+    sig("javascript", r"\b(const|let|var)\s+\w+\s*=", 2),
+    sig("javascript", r"=>", 1),
+    sig("javascript", r"\bconsole\.(log|error|warn)\(", 4),
+    sig("javascript", r"\b(document|window)\.\w+", 3),
+    sig("javascript", r"\brequire\(['\x22]", 3),
+    sig("javascript", r"\bmodule\.exports\b|\bexport\s+(default|const|function|class)\b", 3),
+    sig("javascript", r"===|!==", 3),
+    sig("javascript", r"(?m)^\s*import\s+.+\s+from\s+['\x22]", 3),
+    // TypeScript 专有
+    sig("typescript", r":\s*(string|number|boolean|any|void|unknown|never)\b", 3),
+    sig("typescript", r"\binterface\s+\w+\s*(extends\s+[\w, ]+)?\{", 2),
+    sig("typescript", r"(?m)^\s*(export\s+)?type\s+\w+\s*=", 3),
+    sig("typescript", r"\bas\s+(string|number|any|const|unknown)\b", 3),
+    sig("typescript", r"\b(public|private|protected|readonly)\s+\w+\s*:", 3),
+    // Go
+    sig("go", r"(?m)^package\s+\w+\s*$", 4),
+    sig("go", r"\bfunc\s+(\(\w+\s+\*?\w+\)\s*)?\w+\s*\(", 4),
+    sig("go", r":=", 2),
+    sig("go", r"\bfmt\.\w+\(", 4),
+    sig("go", r"\bgo\s+func\b|\bchan\s+\w+|\bdefer\s+\w+", 3),
+    sig("go", r"(?m)^import\s*\(", 3),
+    // Java
+    sig("java", r"\bpublic\s+(static\s+)?(final\s+)?(class|void|interface)\b", 3),
+    sig("java", r"\bSystem\.out\.print", 5),
+    sig("java", r"@Override\b", 4),
+    sig("java", r"(?m)^\s*import\s+java(x)?\.", 5),
+    sig("java", r"\bString\[\]\s+\w+", 3),
+    sig("java", r"\bprivate\s+(final\s+)?\w+(<[\w<>, ]*>)?\s+\w+\s*[;=]", 2),
+    // C
+    sig("c", r"#include\s*<\w+\.h>", 4),
+    sig("c", r"\bprintf\s*\(", 2),
+    sig("c", r"\bint\s+main\s*\(", 3),
+    sig("c", r"\b(malloc|calloc|free|sizeof)\s*\(", 2),
+    sig("c", r"(?m)^\s*#define\s+\w+", 2),
+    // C++
+    sig("cpp", r"#include\s*<(iostream|vector|string|map|memory|algorithm|unordered_map)>", 5),
+    sig("cpp", r"\bstd::\w+", 4),
+    sig("cpp", r"\bcout\s*<<|\bcin\s*>>", 4),
+    sig("cpp", r"\btemplate\s*<", 4),
+    sig("cpp", r"\bnamespace\s+\w+\s*\{|\busing\s+namespace\b", 3),
+    // C#
+    sig("csharp", r"(?m)^\s*using\s+System(\.\w+)*;", 5),
+    sig("csharp", r"\bConsole\.Write(Line)?\(", 5),
+    sig("csharp", r"\{\s*get;\s*(private\s+)?(set;)?\s*\}", 5),
+    sig("csharp", r"\bnamespace\s+[\w.]+", 2),
+    sig("csharp", r"\bpublic\s+(static\s+)?(async\s+)?(void|Task|string|int|bool)\s+[A-Z]\w*\s*\(", 2),
+    // Ruby
+    sig("ruby", r"(?m)^\s*def\s+\w+[?!]?(\(.*\))?\s*$", 3),
+    sig("ruby", r"(?m)^\s*end\s*$", 3),
+    sig("ruby", r"(?m)^\s*puts\s", 3),
+    sig("ruby", r"(?m)^\s*require(_relative)?\s+['\x22]", 3),
+    sig("ruby", r"\bdo\s*\|\w+(,\s*\w+)*\|", 4),
+    sig("ruby", r"(?m)^\s*attr_(accessor|reader|writer)\s", 4),
+    // PHP
+    sig("php", r"<\?php", 10),
+    sig("php", r"\$\w+\s*=[^=]", 2),
+    sig("php", r"\becho\s", 2),
+    sig("php", r"\bfunction\s+\w+\s*\(\s*\$", 4),
+    sig("php", r"\$this->", 4),
+    // Shell
+    sig("shell", r"(?m)^\s*(sudo|apt|apt-get|brew|npm|yarn|pnpm|cargo|git|docker|kubectl|cd|ls|export|curl|wget|pip|chmod|chown|mkdir|rm|cp|mv|source)\s", 2),
+    sig("shell", r"\|\s*(grep|awk|sed|xargs|sort|uniq|head|tail|wc|tee)\b", 3),
+    sig("shell", r"(?m)^\s*(if\s+\[|fi\s*$|then\s*$|done\s*$|esac\s*$|do\s*$)", 4),
+    sig("shell", r"\$\{\w+\}|\$\(\w+", 2),
+    sig("shell", r"(?m)^\s*echo\s+['\x22$]", 2),
+    // CSS
+    sig("css", r"(?m)^\s*[.#]?[\w-]+(\s*[,>+~]?\s*[.#:]?[\w-]+)*\s*\{\s*$", 1),
+    sig("css", r"(?m)^\s*[a-z-]+\s*:\s*[^;{}]+;\s*$", 2),
+    sig("css", r"@(media|import|keyframes|font-face)\b", 4),
+    sig("css", r"\d(px|rem|em|vh|vw)\b", 1),
+];
+
+static COMPILED: LazyLock<Vec<(&'static str, Regex, usize)>> = LazyLock::new(|| {
+    SIGNATURES
+        .iter()
+        .map(|s| (s.language, Regex::new(s.pattern).expect("invalid language signature"), s.weight))
+        .collect()
+});
+
+static SHEBANG_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^#!\s*\S*?(?:/env\s+)?\b(python3?|bash|sh|zsh|node|ruby|php)\b").unwrap()
+});
+
+/// 检测文本的编程语言，无法判断时返回 None
+pub fn detect(text: &str) -> Option<&'static str> {
+    let text = truncate(text.trim(), MAX_DETECT_BYTES);
+    if text.is_empty() {
+        return None;
+    }
+
+    // 结构化格式直接判断
+    if let Some(language) = detect_structured(text) {
+        return Some(language);
+    }
+    if let Some(caps) = SHEBANG_RE.captures(text) {
+        return Some(match &caps[1] {
+            "python" | "python3" => "python",
+            "node" => "javascript",
+            "ruby" => "ruby",
+            "php" => "php",
+            _ => "shell",
+        });
+    }
+
+    // 与 LANGUAGES 一一对应
+    let mut scores = vec![0usize; LANGUAGES.len()];
+    let index = |language: &str| LANGUAGES.iter().position(|l| *l == language);
+    for (language, regex, weight) in COMPILED.iter() {
+        let hits = regex.find_iter(text).take(MAX_HITS).count();
+        if let Some(i) = index(language) {
+            scores[i] += hits * weight;
+        }
+    }
+
+    let (js, ts, c, cpp) = (index("javascript"), index("typescript"), index("c"), index("cpp"));
+    if let (Some(js), Some(ts), Some(c), Some(cpp)) = (js, ts, c, cpp) {
+        // TypeScript 是 JavaScript 的超集，有专有特征时合并 JavaScript 的得分
+        if scores[ts] >= 3 {
+            scores[ts] += scores[js];
+        }
+        // C++ 通常也会命中 C 的特征
+        if scores[cpp] > 0 {
+            scores[cpp] += scores[c];
+        }
+    }
+
+    // 得分相同时按 LANGUAGES 中的顺序取第一个
+    let (best, score) = scores
+        .iter()
+        .enumerate()
+        .fold((0, 0), |best, (i, s)| if *s > best.1 { (i, *s) } else { best });
+    let language = LANGUAGES[best];
+    (score >= MIN_SCORE).then_some(language)
+}
+
+fn detect_structured(text: &str) -> Option<&'static str> {
+    let json_like = (text.starts_with('{') && text.ends_with('}')) || (text.starts_with('[') && text.ends_with(']'));
+    if json_like && serde_json::from_str::<serde_json::Value>(text).is_ok() {
+        return Some("json");
+    }
+    if text.starts_with('<') && text.ends_with('>') && !text.starts_with("<?php") {
+        let lower = text[..text.len().min(1024)].to_ascii_lowercase();
+        let html = ["<!doctype html", "<html", "<div", "<span", "<p>", "<p ", "<body", "<a ", "<table", "<ul", "<script"]
+            .iter()
+            .any(|tag| lower.contains(tag));
+        return Some(if html { "html" } else { "xml" });
+    }
+    if SqlDetector.detect(text) {
+        return Some("sql");
+    }
+    None
+}
+
+fn truncate(text: &str, max_bytes: usize) -> &str {
+    if text.len() <= max_bytes {
+        return text;
+    }
+    let mut end = max_bytes;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
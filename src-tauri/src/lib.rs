@@ -1,28 +1,66 @@
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
+pub mod barcode;
+pub mod capture;
+pub mod classify;
 pub mod clipboard;
+pub mod color;
+pub mod conceal;
+pub mod crypto;
+pub mod datauri;
+pub mod highlight;
+pub mod html;
+pub mod language;
+pub mod markdown;
+pub mod mime;
+pub mod ocr;
+pub mod palette;
 pub mod persist;
+pub mod placeholder;
+pub mod privacy;
+pub mod rtf;
+pub mod sanitize;
+pub mod secret;
 pub mod setting;
+pub mod source_app;
+pub mod svg;
+pub mod vault;
 
+use std::collections::VecDeque;
 use std::sync::{Mutex, Arc};
 use std::thread;
 
 use base64::{Engine as _, engine::general_purpose};
 use chrono::Utc;
 use clipboard::ClipEvent;
-use persist::{ClipItem, Storage};
+use persist::{ClipFilter, ClipItem, Storage};
 
 use tauri::{Manager, Emitter, AppHandle};
 
-use crate::persist::ClipData;
+use crate::capture::{CaptureState, SharedCapture};
+use crate::clipboard::{ClipboardWriter, SharedSelfWrite};
+use crate::conceal::{EphemeralClip, SharedPolicy};
+use crate::crypto::{Cipher, EncryptionConfig, KeySource};
+use crate::persist::{ClipData, ClipPart, TextChunk, MIME_SVG, MIME_TEXT};
+use crate::privacy::{PrivacyGuard, SharedGuard};
+use crate::setting::{Settings, SharedFormats};
+use crate::source_app::SourceApp;
+use crate::vault::VaultItem;
+
+/// get_clip_content 对超大文本返回的首页大小
+const TEXT_PAGE_BYTES: usize = 64 * 1024;
+/// 单次分段读取的上限，避免一次通过 IPC 传输过多内容
+const MAX_TEXT_RANGE_BYTES: usize = 1024 * 1024;
 
 #[tauri::command]
 fn get_recent_clips(
     state: tauri::State<AppState>, 
     limit: usize, 
-    offset: usize
+    offset: usize,
+    filter: Option<ClipFilter>,
 ) -> Result<Vec<ClipItem>, String> {
+    touch_activity(&state);
     let storage = state.storage.lock().map_err(|_| "Lock error")?;
-    storage.get_recent(limit, offset).map_err(|e| e.to_string())
+    storage.get_recent_filtered(limit, offset, &filter.unwrap_or_default()).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -40,10 +78,12 @@ fn clear_unpinned_clips(state: tauri::State<AppState>) -> Result<i64, String> {
 #[tauri::command]
 fn search_clips(
     state: tauri::State<AppState>, 
-    query: String
+    query: String,
+    filter: Option<ClipFilter>,
 ) -> Result<Vec<ClipItem>, String> {
+    touch_activity(&state);
     let storage = state.storage.lock().map_err(|_| "Lock error")?;
-    storage.search(&query).map_err(|e| e.to_string())
+    storage.search_filtered(&query, &filter.unwrap_or_default()).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -51,7 +91,21 @@ fn get_clip_content(
     state: tauri::State<AppState>,
     id: i64
 ) -> Result<serde_json::Value, String> {
+    touch_activity(&state);
     let storage = state.storage.lock().map_err(|_| "Lock error")?;
+
+    // 超大文本只返回第一页，其余通过 get_clip_text_range 分段读取
+    if let Some(size) = storage.external_text_size(id).map_err(|e| e.to_string())? {
+        let chunk = storage.read_text_range(id, 0, TEXT_PAGE_BYTES).map_err(|e| e.to_string())?;
+        return Ok(serde_json::json!({
+            "type": "text",
+            "data": chunk.text,
+            "size": size,
+            "next_offset": chunk.next_offset,
+            "chunked": true
+        }));
+    }
+
     let content = storage.get_content(id).map_err(|e| e.to_string())?;
     
     let json_value = match content {
@@ -75,11 +129,227 @@ fn get_clip_content(
             "type": "color",
             "data": color
         }),
+        ClipData::Rtf { text, rtf } => serde_json::json!({
+            "type": "rtf",
+            "text": text,
+            "html": rtf::to_html(&rtf)
+        }),
+        ClipData::Custom { mime, data } => serde_json::json!({
+            "type": "custom",
+            "mime": mime,
+            "size": data.len()
+        }),
     };
     
     Ok(json_value)
 }
 
+#[tauri::command]
+fn get_clip_text_range(
+    state: tauri::State<AppState>,
+    id: i64,
+    offset: usize,
+    length: usize
+) -> Result<TextChunk, String> {
+    let storage = state.storage.lock().map_err(|_| "Lock error")?;
+    storage.read_text_range(id, offset, length.min(MAX_TEXT_RANGE_BYTES)).map_err(|e| e.to_string())
+}
+
+/// 把颜色记录转换为 hex / rgb / hsl / oklch 几种写法
+#[tauri::command]
+fn convert_color(
+    state: tauri::State<AppState>,
+    id: i64,
+) -> Result<color::ColorFormats, String> {
+    let storage = state.storage.lock().map_err(|_| "Lock error")?;
+    let color = storage.get_color(id).map_err(|e| e.to_string())?
+        .ok_or("该记录不是颜色")?;
+    Ok(color.formats())
+}
+
+/// 获取图片记录的调色板
+#[tauri::command]
+fn get_palette(
+    state: tauri::State<AppState>,
+    id: i64,
+) -> Result<Vec<palette::Swatch>, String> {
+    let storage = state.storage.lock().map_err(|_| "Lock error")?;
+    storage.get_palette(id).map_err(|e| e.to_string())
+}
+
+/// 把调色板中的一个颜色保存为颜色记录
+#[tauri::command]
+fn promote_swatch(
+    state: tauri::State<AppState>,
+    id: i64,
+    index: usize,
+) -> Result<i64, String> {
+    let mut storage = state.storage.lock().map_err(|_| "Lock error")?;
+    storage.promote_swatch(id, index).map_err(|e| e.to_string())
+}
+
+/// 获取图片 OCR 识别出的文字
+#[tauri::command]
+fn get_ocr_text(
+    state: tauri::State<AppState>,
+    id: i64,
+) -> Result<Option<String>, String> {
+    let storage = state.storage.lock().map_err(|_| "Lock error")?;
+    storage.get_ocr_text(id).map_err(|e| e.to_string())
+}
+
+/// 把文本记录生成为二维码图片记录，返回新记录 ID
+#[tauri::command]
+fn generate_qr(
+    state: tauri::State<AppState>,
+    id: i64,
+) -> Result<i64, String> {
+    let mut storage = state.storage.lock().map_err(|_| "Lock error")?;
+    let (qr_id, _) = storage.generate_qr(id).map_err(|e| e.to_string())?;
+    Ok(qr_id)
+}
+
+/// 出现过的来源应用及其记录数，用于"按应用过滤"
+#[tauri::command]
+fn list_source_apps(state: tauri::State<AppState>) -> Result<Vec<(String, i64)>, String> {
+    let storage = state.storage.lock().map_err(|_| "Lock error")?;
+    storage.list_apps().map_err(|e| e.to_string())
+}
+
+/// 获取代码片段的语法高亮结果；未识别出语言时按纯文本返回
+#[tauri::command]
+fn get_highlighted(
+    state: tauri::State<AppState>,
+    id: i64,
+    theme: Option<String>,
+) -> Result<highlight::Highlighted, String> {
+    let (text, language) = {
+        let storage = state.storage.lock().map_err(|_| "Lock error")?;
+        let text = match storage.get_content(id).map_err(|e| e.to_string())? {
+            ClipData::Text(text) => text,
+            ClipData::Html { text, .. } => text,
+            _ => return Err("该记录不是文本".to_string()),
+        };
+        (text, storage.get_language(id).map_err(|e| e.to_string())?)
+    };
+    highlight::highlight(&text, language.as_deref(), theme.as_deref()).map_err(|e| e.to_string())
+}
+
+/// 手动指定记录的编程语言，传 None 清除
+#[tauri::command]
+fn set_clip_language(
+    state: tauri::State<AppState>,
+    id: i64,
+    language: Option<String>,
+) -> Result<(), String> {
+    let storage = state.storage.lock().map_err(|_| "Lock error")?;
+    storage.set_language(id, language.as_deref()).map_err(|e| e.to_string())
+}
+
+/// 获取净化后的 HTML 预览（移除脚本 / 事件属性，远程图片替换为占位图）
+#[tauri::command]
+fn get_clip_preview_html(
+    state: tauri::State<AppState>,
+    id: i64
+) -> Result<String, String> {
+    let storage = state.storage.lock().map_err(|_| "Lock error")?;
+    match storage.get_content(id).map_err(|e| e.to_string())? {
+        ClipData::Html { html, .. } => Ok(sanitize::clean_html(&html)),
+        ClipData::Rtf { rtf, .. } => Ok(sanitize::clean_html(&rtf::to_html(&rtf))),
+        _ => Err("该记录没有 HTML 预览".to_string()),
+    }
+}
+
+#[tauri::command]
+fn get_settings(state: tauri::State<AppState>) -> Result<Settings, String> {
+    let storage = state.storage.lock().map_err(|_| "Lock error")?;
+    Ok(storage.settings().clone())
+}
+
+#[tauri::command]
+fn update_settings(state: tauri::State<AppState>, settings: Settings) -> Result<(), String> {
+    PrivacyGuard::validate(&settings.privacy_blocklist).map_err(|e| e.to_string())?;
+    let mut storage = state.storage.lock().map_err(|_| "Lock error")?;
+    let rules = settings.privacy_blocklist.clone();
+    let formats = settings.custom_formats.clone();
+    let policy = settings.concealed_policy;
+    storage.update_settings(settings).map_err(|e| e.to_string())?;
+    *state.custom_formats.lock().map_err(|_| "Lock error")? = formats;
+    *state.concealed_policy.lock().map_err(|_| "Lock error")? = policy;
+    let mut privacy = state.privacy.lock().map_err(|_| "Lock error")?;
+    privacy.set_rules(&rules).map_err(|e| e.to_string())
+}
+
+/// 本次运行中各应用因隐私黑名单被忽略的复制次数
+#[tauri::command]
+fn get_suppressed_counts(state: tauri::State<AppState>) -> Result<Vec<(String, u64)>, String> {
+    let privacy = state.privacy.lock().map_err(|_| "Lock error")?;
+    Ok(privacy.suppressed_counts())
+}
+
+#[tauri::command]
+fn reset_suppressed_counts(state: tauri::State<AppState>) -> Result<(), String> {
+    let mut privacy = state.privacy.lock().map_err(|_| "Lock error")?;
+    privacy.reset_counts();
+    Ok(())
+}
+
+/// 存储是否加密、是否已锁定及密钥来源
+#[tauri::command]
+fn get_store_status(state: tauri::State<AppState>) -> Result<serde_json::Value, String> {
+    let storage = state.storage.lock().map_err(|_| "Lock error")?;
+    let key_source = EncryptionConfig::load(storage.data_dir())
+        .filter(|_| storage.is_encrypted())
+        .map(|config| config.key_source);
+    Ok(serde_json::json!({
+        "encrypted": storage.is_encrypted(),
+        "locked": storage.is_locked(),
+        "key_source": key_source
+    }))
+}
+
+/// 就地加密现有的明文存储；`passphrase` 为空时随机生成密钥并保存到系统钥匙串
+#[tauri::command]
+fn enable_encryption(app: AppHandle, state: tauri::State<AppState>, passphrase: Option<String>) -> Result<(), String> {
+    {
+        let mut storage = state.storage.lock().map_err(|_| "Lock error")?;
+        if storage.is_encrypted() {
+            return Err("存储已加密".to_string());
+        }
+        let (mut config, key) = EncryptionConfig::create(passphrase.as_deref()).map_err(|e| e.to_string())?;
+        // 先保存配置：加密中途退出时数据库可能已经替换，需要靠它解锁后继续
+        config.save(storage.data_dir()).map_err(|e| e.to_string())?;
+        storage.encrypt(Arc::new(key)).map_err(|e| e.to_string())?;
+        config.complete = true;
+        config.save(storage.data_dir()).map_err(|e| e.to_string())?;
+    }
+    touch_activity(&state);
+    emit_store_state(&app, false);
+    Ok(())
+}
+
+/// 解锁加密存储；钥匙串模式下不需要口令
+#[tauri::command]
+fn unlock_store(app: AppHandle, state: tauri::State<AppState>, passphrase: Option<String>) -> Result<(), String> {
+    {
+        let mut storage = state.storage.lock().map_err(|_| "Lock error")?;
+        unlock_storage(&mut storage, passphrase.as_deref()).map_err(|e| e.to_string())?;
+    }
+    touch_activity(&state);
+    emit_store_state(&app, false);
+    flush_pending_clips(&app);
+    Ok(())
+}
+
+#[tauri::command]
+fn lock_store(app: AppHandle, state: tauri::State<AppState>) -> Result<(), String> {
+    state.storage.lock().map_err(|_| "Lock error")?.lock_store().map_err(|e| e.to_string())?;
+    // 锁定存储时保险库一并锁定
+    emit_store_state(&app, true);
+    emit_vault_state(&app, None);
+    Ok(())
+}
+
 #[tauri::command]
 fn toggle_pin(
     state: tauri::State<AppState>,
@@ -89,6 +359,44 @@ fn toggle_pin(
     storage.toggle_pin(id).map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+fn get_capture_state(state: tauri::State<AppState>) -> Result<CaptureState, String> {
+    let mut capture = state.capture.lock().map_err(|_| "Lock error")?;
+    Ok(capture.state())
+}
+
+/// 暂停记录；`minutes` 为空时无限期暂停
+#[tauri::command]
+fn pause_capture(app: AppHandle, minutes: Option<u64>) -> Result<CaptureState, String> {
+    let duration = minutes
+        .map(|m| i64::try_from(m).ok().and_then(chrono::TimeDelta::try_minutes).ok_or("暂停时长超出范围"))
+        .transpose()?;
+    let mut paused = Ok(CaptureState::Active);
+    let current = update_capture(&app, |capture| { paused = capture.pause(duration); })?;
+    paused.map_err(|e| e.to_string())?;
+    Ok(current)
+}
+
+#[tauri::command]
+fn resume_capture(app: AppHandle) -> Result<CaptureState, String> {
+    update_capture(&app, |capture| { capture.resume(); })
+}
+
+/// 进入 / 退出隐身模式
+#[tauri::command]
+fn set_incognito(app: AppHandle, enabled: bool) -> Result<CaptureState, String> {
+    update_capture(&app, |capture| {
+        capture.set(if enabled { CaptureState::Incognito } else { CaptureState::Active });
+    })
+}
+
+/// 手动标记 / 取消敏感内容
+#[tauri::command]
+fn set_clip_sensitive(state: tauri::State<AppState>, id: i64, sensitive: bool) -> Result<(), String> {
+    let storage = state.storage.lock().map_err(|_| "Lock error")?;
+    storage.set_sensitive(id, sensitive).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 fn delete_clip(
     state: tauri::State<AppState>,
@@ -101,6 +409,167 @@ fn delete_clip(
     Ok(result)
 }
 
+#[tauri::command]
+fn copy_to_clipboard(
+    app: AppHandle,
+    state: tauri::State<AppState>,
+    id: i64,
+    format: Option<String>,
+) -> Result<(), String> {
+    touch_activity(&state);
+    let (content, parts) = {
+        let storage = state.storage.lock().map_err(|_| "Lock error")?;
+        match format.as_deref() {
+            // 粘贴为 Markdown：只写入纯文本
+            Some("markdown") => {
+                let markdown = storage.get_markdown(id).map_err(|e| e.to_string())?
+                    .ok_or("该记录没有 Markdown 表示")?;
+                (ClipData::Text(markdown), Vec::new())
+            }
+            // SVG 源码：纯文本 + image/svg+xml
+            Some("svg") => {
+                let markup = storage.get_svg(id).map_err(|e| e.to_string())?
+                    .ok_or("该记录不是 SVG")?;
+                (ClipData::Text(markup.clone()), vec![ClipPart::text(MIME_SVG, markup)])
+            }
+            // 只写入位图
+            Some("png") => match storage.get_content(id).map_err(|e| e.to_string())? {
+                image @ ClipData::Image(_) => (image, Vec::new()),
+                _ => return Err("该记录不是图片".to_string()),
+            },
+            Some(other) => return Err(format!("Unsupported format: {}", other)),
+            None => {
+                let content = storage.get_content(id).map_err(|e| e.to_string())?;
+                let parts = storage.get_parts(id).map_err(|e| e.to_string())?;
+                (content, parts)
+            }
+        }
+    };
+
+    {
+        let mut writer = state.writer.lock().map_err(|_| "Lock error")?;
+        if writer.is_none() {
+            *writer = Some(ClipboardWriter::new().map_err(|e| e.to_string())?);
+        }
+        writer.as_mut().unwrap().restore(&content, &parts).map_err(|e| e.to_string())?;
+    }
+    println!("📋 已恢复到剪贴板: ID {}", id);
+
+    // 一次性记录：宽限期后删除，届时剪贴板内容未被替换则一并清空
+    let expires_at = {
+        let storage = state.storage.lock().map_err(|_| "Lock error")?;
+        storage.mark_used(id).map_err(|e| e.to_string())?
+    };
+    if let Some(expires_at) = expires_at {
+        let fingerprint = state.writer.lock().map_err(|_| "Lock error")?
+            .as_mut()
+            .and_then(|writer| writer.fingerprint());
+        *state.one_time.lock().map_err(|_| "Lock error")? = fingerprint.map(|fingerprint| OneTimeClip { id, fingerprint });
+        let _ = app.emit("clipboard://clip-expiry", serde_json::json!({ "id": id, "expires_at": expires_at }));
+    }
+    Ok(())
+}
+
+/// 设置记录在多少分钟后自动删除；`minutes` 为空时取消
+#[tauri::command]
+fn set_clip_expiry(state: tauri::State<AppState>, id: i64, minutes: Option<u64>) -> Result<Option<i64>, String> {
+    let ttl = minutes
+        .map(|m| i64::try_from(m).ok().and_then(chrono::TimeDelta::try_minutes).ok_or("到期时间超出范围"))
+        .transpose()?;
+    let storage = state.storage.lock().map_err(|_| "Lock error")?;
+    storage.set_expiry(id, ttl).map_err(|e| e.to_string())
+}
+
+/// 标记 / 取消一次性记录 (粘贴回剪贴板后自动删除)
+#[tauri::command]
+fn set_clip_delete_after_use(state: tauri::State<AppState>, id: i64, enabled: bool) -> Result<(), String> {
+    let storage = state.storage.lock().map_err(|_| "Lock error")?;
+    storage.set_delete_after_use(id, enabled).map_err(|e| e.to_string())
+}
+
+/// 内存中未过期的临时记录 (预览已遮盖)
+#[tauri::command]
+fn get_ephemeral_clips(state: tauri::State<AppState>) -> Result<Vec<EphemeralClip>, String> {
+    let mut storage = state.storage.lock().map_err(|_| "Lock error")?;
+    Ok(storage.ephemeral_clips())
+}
+
+/// 把临时记录写回剪贴板，写入时带上隐藏标记以免再被记录
+#[tauri::command]
+fn copy_ephemeral_clip(state: tauri::State<AppState>, id: u64) -> Result<(), String> {
+    let text = {
+        let mut storage = state.storage.lock().map_err(|_| "Lock error")?;
+        storage.get_ephemeral(id).ok_or("临时记录已过期")?
+    };
+    restore_concealed(&state, text)?;
+    println!("📋 已恢复临时记录到剪贴板: ID {}", id);
+    Ok(())
+}
+
+/// 写入不应被记录的文本：带上隐藏标记，并先登记指纹，
+/// 后端无法同时写入标记时监听线程靠指纹跳过
+fn restore_concealed(state: &AppState, text: String) -> Result<(), String> {
+    *state.self_write.lock().map_err(|_| "Lock error")? = Some(clipboard::text_fingerprint(&text));
+    let hint = ClipPart::text(conceal::KDE_PASSWORD_HINT, "secret".to_string());
+
+    let mut writer = state.writer.lock().map_err(|_| "Lock error")?;
+    if writer.is_none() {
+        *writer = Some(ClipboardWriter::new().map_err(|e| e.to_string())?);
+    }
+    writer.as_mut().unwrap().restore(&ClipData::Text(text), &[hint]).map_err(|e| e.to_string())
+}
+
+/// 解锁保险库，返回到期时间 (微秒时间戳)；第一次调用时设置保险库口令
+#[tauri::command]
+fn vault_unlock(app: AppHandle, state: tauri::State<AppState>, passphrase: String) -> Result<i64, String> {
+    let expires_at = {
+        let mut storage = state.storage.lock().map_err(|_| "Lock error")?;
+        storage.vault_unlock(&passphrase).map_err(|e| e.to_string())?
+    };
+    emit_vault_state(&app, Some(expires_at));
+    Ok(expires_at)
+}
+
+#[tauri::command]
+fn vault_lock(app: AppHandle, state: tauri::State<AppState>) -> Result<(), String> {
+    state.storage.lock().map_err(|_| "Lock error")?.vault_lock();
+    emit_vault_state(&app, None);
+    Ok(())
+}
+
+/// 保险库解锁的到期时间，未解锁时为空
+#[tauri::command]
+fn get_vault_status(state: tauri::State<AppState>) -> Result<Option<i64>, String> {
+    let mut storage = state.storage.lock().map_err(|_| "Lock error")?;
+    Ok(storage.vault_expires_at())
+}
+
+/// 把文本记录移入保险库，需要先解锁
+#[tauri::command]
+fn move_to_vault(state: tauri::State<AppState>, id: i64, label: Option<String>) -> Result<(), String> {
+    let mut storage = state.storage.lock().map_err(|_| "Lock error")?;
+    storage.move_to_vault(id, label).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn list_vault(state: tauri::State<AppState>) -> Result<Vec<VaultItem>, String> {
+    let storage = state.storage.lock().map_err(|_| "Lock error")?;
+    storage.vault_items().map_err(|e| e.to_string())
+}
+
+/// 解密保险库中的记录并写入剪贴板，带隐藏标记，不会被记录到历史中
+#[tauri::command]
+fn copy_vault_clip(state: tauri::State<AppState>, id: i64) -> Result<(), String> {
+    touch_activity(&state);
+    let text = {
+        let mut storage = state.storage.lock().map_err(|_| "Lock error")?;
+        storage.read_vault(id).map_err(|e| e.to_string())?
+    };
+    restore_concealed(&state, text)?;
+    println!("📋 已恢复保险库记录到剪贴板: ID {}", id);
+    Ok(())
+}
+
 #[tauri::command]
 fn toggle_window(app: AppHandle) -> Result<(), String> {
     if let Some(window) = app.get_webview_window("main") {
@@ -146,6 +615,14 @@ fn get_image_url(
     thumbnail: bool,
 ) -> Result<String, String> {
     let storage = state.storage.lock().map_err(|_| "Lock error")?;
+
+    // 加密的图片文件前端无法直接读取，解密后以 data URL 返回；
+    // 原图可能有几十 MB，只解密缩略图，不把整张原图编码后经 IPC 传给前端
+    if storage.is_encrypted() {
+        let data = storage.image_data(id, true).map_err(|e| e.to_string())?;
+        return Ok(format!("data:image/webp;base64,{}", general_purpose::STANDARD.encode(data)));
+    }
+
     let (image_path, thumbnail_path) = storage
         .get_image_paths(id)
         .map_err(|e| e.to_string())?;
@@ -155,28 +632,93 @@ fn get_image_url(
     Ok(path)
 }
 
+/// 写回剪贴板、尚未到期的一次性记录
+struct OneTimeClip {
+    id: i64,
+    fingerprint: String, // 写入后剪贴板内容的指纹
+}
+
 struct AppState {
-    storage: Mutex<Storage>,
+    // 与剪贴板监听线程共享同一个连接，设置变更对两边同时生效
+    storage: Arc<Mutex<Storage>>,
     keep_window_open: Arc<Mutex<bool>>,
+    writer: Mutex<Option<ClipboardWriter>>,
+    // 与剪贴板监听线程共享的隐私黑名单
+    privacy: SharedGuard,
+    // 与剪贴板监听线程共享的记录状态
+    capture: SharedCapture,
+    // 与剪贴板监听线程共享的自定义格式白名单
+    custom_formats: SharedFormats,
+    // 与剪贴板监听线程共享的隐藏标记处理方式
+    concealed_policy: SharedPolicy,
+    // 写回剪贴板的机密文本指纹，监听线程据此跳过
+    self_write: SharedSelfWrite,
+    // 最近写回剪贴板的一次性记录
+    one_time: Mutex<Option<OneTimeClip>>,
+    // 最近一次用户操作的时间，用于加密存储的空闲自动锁定
+    last_activity: Mutex<std::time::Instant>,
+    // 存储锁定期间的复制，只保存在内存中，解锁后写入
+    pending_clips: Mutex<VecDeque<ClipEvent>>,
 }
 
 impl AppState {
-    fn new(data_dir: std::path::PathBuf) -> Result<Self, Box<dyn std::error::Error>> {
-        let storage = Storage::new(&data_dir)?;
+    fn new(
+        data_dir: std::path::PathBuf,
+        privacy: SharedGuard,
+        capture: SharedCapture,
+        custom_formats: SharedFormats,
+        concealed_policy: SharedPolicy,
+        self_write: SharedSelfWrite,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut storage = Storage::new(&data_dir)?;
+        // 密钥保存在系统钥匙串时启动后自动解锁
+        if storage.is_locked() {
+            let from_keyring = EncryptionConfig::load(&data_dir)
+                .is_some_and(|config| config.key_source == KeySource::Keyring);
+            if from_keyring {
+                if let Err(e) = unlock_storage(&mut storage, None) {
+                    eprintln!("⚠️ 无法用系统钥匙串解锁: {}", e);
+                }
+            }
+        }
+        match ocr::TesseractCli::detect(storage.settings().ocr_languages.clone()) {
+            Some(engine) => storage.set_ocr_engine(Arc::new(engine)),
+            None => println!("ℹ️ 未找到 tesseract，图片 OCR 不可用"),
+        }
+        match barcode::ZbarCli::detect() {
+            Some(decoder) => storage.set_code_decoder(Arc::new(decoder)),
+            None => println!("ℹ️ 未找到 zbarimg，图片二维码识别不可用"),
+        }
         Ok(AppState {
-            storage: Mutex::new(storage),
+            storage: Arc::new(Mutex::new(storage)),
             keep_window_open: Arc::new(Mutex::new(false)),
+            writer: Mutex::new(None),
+            privacy,
+            capture,
+            custom_formats,
+            concealed_policy,
+            self_write,
+            one_time: Mutex::new(None),
+            last_activity: Mutex::new(std::time::Instant::now()),
+            pending_clips: Mutex::new(VecDeque::new()),
         })
     }
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
-pub fn run(rx: crossbeam_channel::Receiver<clipboard::ClipEvent>) {
+pub fn run(
+    rx: crossbeam_channel::Receiver<clipboard::ClipEvent>,
+    privacy: SharedGuard,
+    capture: SharedCapture,
+    custom_formats: SharedFormats,
+    concealed_policy: SharedPolicy,
+    self_write: SharedSelfWrite,
+) {
     tauri::Builder::default()
         .setup(|app| {
             setup_tray(app)?;
             setup_global_shortcut(app)?;
-            setup_storage_and_clipboard(app, rx)?;
+            setup_storage_and_clipboard(app, rx, privacy, capture, custom_formats, concealed_policy, self_write)?;
             setup_window_events(app)?;
             Ok(())
         })
@@ -188,8 +730,42 @@ pub fn run(rx: crossbeam_channel::Receiver<clipboard::ClipEvent>) {
             clear_unpinned_clips,
             search_clips,
             get_clip_content,
+            get_clip_text_range,
+            get_clip_preview_html,
+            get_highlighted,
+            convert_color,
+            get_palette,
+            promote_swatch,
+            get_ocr_text,
+            generate_qr,
+            list_source_apps,
+            get_suppressed_counts,
+            reset_suppressed_counts,
+            get_store_status,
+            enable_encryption,
+            unlock_store,
+            lock_store,
+            set_clip_language,
+            get_settings,
+            update_settings,
             toggle_pin,
             delete_clip,
+            set_clip_sensitive,
+            set_clip_expiry,
+            set_clip_delete_after_use,
+            copy_to_clipboard,
+            get_ephemeral_clips,
+            get_capture_state,
+            pause_capture,
+            resume_capture,
+            set_incognito,
+            copy_ephemeral_clip,
+            vault_unlock,
+            vault_lock,
+            get_vault_status,
+            move_to_vault,
+            list_vault,
+            copy_vault_clip,
             toggle_window,
             set_keep_window_open,
             open_accessibility_settings,
@@ -217,133 +793,420 @@ pub fn handle_clipboard_event(
 ) {
     loop {
         match rx.recv() {
-            Ok(ClipEvent::Text(text)) => {
-                let trimmed_text = text.trim_start().to_string();
-                println!("✅ 捕获到文本: [ {} ]", trimmed_text);
-                
-                // 保存到数据库
-                if let Ok(mut store) = storage.lock() {
-                    if let Err(e) = store.add_text(trimmed_text.clone()) {
-                        eprintln!("❌ 保存文本失败: {}", e);
+            Ok(event) => {
+                // 锁定期间暂存在内存中，解锁后写入；临时记录只在内存中，不受影响
+                let locked = !matches!(event, ClipEvent::Ephemeral { .. })
+                    && storage.lock().map(|store| store.is_locked()).unwrap_or(false);
+                if locked {
+                    hold_while_locked(&app, event);
+                    continue;
+                }
+                flush_pending_clips(&app);
+                process_clip_event(event, Vec::new(), None, &app, &storage)
+            }
+            Err(_) => {}
+        }
+    }
+}
+
+/// 存储锁定期间暂存的复制上限，超出时丢弃最早的
+const PENDING_CLIPS_LIMIT: usize = 50;
+
+/// 存储锁定期间把复制暂存在内存中；超出上限时丢弃最早的一条并通知界面
+fn hold_while_locked(app: &AppHandle, event: ClipEvent) {
+    let state = app.state::<AppState>();
+    let Ok(mut pending) = state.pending_clips.lock() else { return };
+    pending.push_back(event);
+    println!("🔒 存储已锁定，本次复制暂存到解锁后写入 (共 {} 条)", pending.len());
+    if pending.len() > PENDING_CLIPS_LIMIT {
+        pending.pop_front();
+        drop(pending);
+        eprintln!("⚠️ 锁定期间的复制超过 {} 条，丢弃最早的一条", PENDING_CLIPS_LIMIT);
+        let _ = app.emit("store://clip-dropped", serde_json::json!({ "limit": PENDING_CLIPS_LIMIT }));
+    }
+}
+
+/// 解锁后写入锁定期间暂存的复制
+fn flush_pending_clips(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let pending: Vec<ClipEvent> = match state.pending_clips.lock() {
+        Ok(mut pending) => pending.drain(..).collect(),
+        Err(_) => return,
+    };
+    if pending.is_empty() {
+        return;
+    }
+    println!("🔓 写入锁定期间暂存的 {} 次复制", pending.len());
+    let storage = Arc::clone(&state.storage);
+    for event in pending {
+        process_clip_event(event, Vec::new(), None, app, &storage);
+    }
+}
+
+/// 保存一个剪贴板事件；`alternates` 为同一次复制中的其它表示，附加到主记录上
+fn process_clip_event(
+    event: ClipEvent,
+    alternates: Vec<ClipPart>,
+    source: Option<SourceApp>,
+    app: &tauri::AppHandle,
+    storage: &Arc<Mutex<Storage>>,
+) {
+    match event {
+        ClipEvent::Bundle { parts, source } => {
+            if let Some((primary, rest)) = clipboard::split_primary(parts) {
+                let alternates = rest.iter().filter_map(|p| p.to_part()).collect();
+                process_clip_event(primary, alternates, source, app, storage);
+            }
+        },
+        ClipEvent::Text(text) => {
+            // 日志和事件只带长度和记录 ID，不带内容本身 (可能是密码或密钥)
+            let len = text.len();
+            
+            // SVG 源码渲染为图片保存，渲染失败时按普通文本保存
+            if svg::is_svg(&text) {
+                if let Some(id) = save_svg(storage, &text, &alternates, source.as_ref()) {
+                    let _ = app.emit("clipboard://new-clip", serde_json::json!({
+                        "type": "image",
+                        "id": id
+                    }));
+                    return;
+                }
+            }
+
+            // 保存到数据库 (原样保存，不做 trim)
+            let mut saved_id = None;
+            if let Ok(mut store) = storage.lock() {
+                match store.add_text(text) {
+                    Ok(id) => {
+                        println!("✅ 捕获到文本: [ 记录 {}, {} bytes ]", id, len);
+                        saved_id = Some(id);
+                        finish_record(&mut store, id, &alternates, source.as_ref());
                     }
+                    Err(e) => eprintln!("❌ 保存文本失败: {}", e),
                 }
-                
-                // 推送事件到前端
-                let _ = app.emit("clipboard://new-clip", serde_json::json!({
-                    "type": "text",
-                    "preview": trimmed_text
-                }));
-            },
-            Ok(ClipEvent::Image { width, height, rgba_data }) => {
-                println!("✅ 捕获到图片: [ {}x{}, {} bytes ]", width, height, rgba_data.len());
-                
-                // 立即发送"处理中"事件给前端
-                let temp_id = chrono::Utc::now().timestamp_micros();
-                let _ = app.emit("clipboard://image-pending", serde_json::json!({
-                    "temp_id": temp_id,
-                    "type": "image"
-                }));
-                
-                // 异步处理图片保存和缩略图生成
-                let storage_clone = Arc::clone(&storage);
-                let app_clone = app.clone();
-                thread::spawn(move || {
-                    if let Ok(mut store) = storage_clone.lock() {
-                        match store.add_image(width, height, rgba_data) {
-                            Ok((id, thumbnail_data)) => {
-                                // 将缩略图数据编码为 base64 发送给前端
-                                let base64_thumbnail = general_purpose::STANDARD.encode(&thumbnail_data);
-                                let _ = app_clone.emit("clipboard://image-ready", serde_json::json!({
-                                    "temp_id": temp_id,
-                                    "id": id,
-                                    "type": "image",
-                                    "thumbnail": base64_thumbnail
-                                }));
-                            }
-                            Err(e) => {
-                                eprintln!("❌ 保存图片失败: {}", e);
-                                let _ = app_clone.emit("clipboard://image-error", serde_json::json!({
-                                    "temp_id": temp_id,
-                                    "error": e.to_string()
-                                }));
-                            }
+            }
+            
+            // 推送事件到前端，前端收到后重新拉取列表 (预览按敏感状态遮盖)
+            let _ = app.emit("clipboard://new-clip", serde_json::json!({
+                "type": "text",
+                "id": saved_id
+            }));
+        },
+        ClipEvent::Image { width, height, rgba_data } => {
+            println!("✅ 捕获到图片: [ {}x{}, {} bytes ]", width, height, rgba_data.len());
+            
+            // 立即发送"处理中"事件给前端，附带占位信息
+            let temp_id = chrono::Utc::now().timestamp_micros();
+            let placeholder = placeholder::compute(width as u32, height as u32, &rgba_data);
+            let _ = app.emit("clipboard://image-pending", serde_json::json!({
+                "temp_id": temp_id,
+                "type": "image",
+                "blurhash": placeholder.as_ref().map(|p| &p.blurhash),
+                "average_color": placeholder.as_ref().map(|p| &p.average_color)
+            }));
+            
+            // 异步处理图片保存和缩略图生成
+            let storage_clone = Arc::clone(storage);
+            let app_clone = app.clone();
+            thread::spawn(move || {
+                let mut saved_id = None;
+                if let Ok(mut store) = storage_clone.lock() {
+                    match store.add_image(width, height, rgba_data) {
+                        Ok((id, thumbnail_data)) => {
+                            saved_id = Some(id);
+                            finish_record(&mut store, id, &alternates, source.as_ref());
+                            // 将缩略图数据编码为 base64 发送给前端
+                            let base64_thumbnail = general_purpose::STANDARD.encode(&thumbnail_data);
+                            let _ = app_clone.emit("clipboard://image-ready", serde_json::json!({
+                                "temp_id": temp_id,
+                                "id": id,
+                                "type": "image",
+                                "thumbnail": base64_thumbnail
+                            }));
                         }
-                    }
-                });
-            },
-            Ok(ClipEvent::Html(html)) => {
-                println!("✅ 捕获到 HTML: [ {} bytes ]", html.len());
-                
-                // 从 HTML 中提取纯文本作为 preview
-                // 1. 移除 script 和 style 标签及其内容
-                let text_preview = html
-                    .replace(|c| c == '\n' || c == '\r', " ")
-                    .split('<')
-                    .enumerate()
-                    .filter_map(|(i, s)| {
-                        if i == 0 {
-                            Some(s.to_string()) // 第一段（标签前的文本）
-                        } else if let Some(pos) = s.find('>') {
-                            // 检查是否是 script 或 style 标签，跳过其内容
-                            let tag_name = s[..pos].split_whitespace().next().unwrap_or("");
-                            if tag_name.eq_ignore_ascii_case("script") || tag_name.eq_ignore_ascii_case("style") {
-                                None
-                            } else {
-                                Some(s[pos + 1..].to_string()) // 标签后的文本
-                            }
-                        } else {
-                            None
+                        Err(e) => {
+                            eprintln!("❌ 保存图片失败: {}", e);
+                            let _ = app_clone.emit("clipboard://image-error", serde_json::json!({
+                                "temp_id": temp_id,
+                                "error": e.to_string()
+                            }));
                         }
-                    })
-                    .collect::<Vec<_>>()
-                    .join(" ")
-                    .split_whitespace()
-                    .collect::<Vec<_>>()
-                    .join(" ");
-                
-                println!("📄 提取文本预览: [ {} ]", text_preview.chars().take(100).collect::<String>());
-                
-                // 保存到数据库
-                if let Ok(mut store) = storage.lock() {
-                    if let Err(e) = store.add_html(text_preview, html.clone()) {
-                        eprintln!("❌ 保存 HTML 失败: {}", e);
                     }
                 }
-                
-                let _ = app.emit("clipboard://new-clip", serde_json::json!({
-                    "type": "html",
-                    "preview": html.chars().take(100).collect::<String>()
-                }));
-            },
-            Ok(ClipEvent::FileList(files)) => {
-                println!("✅ 捕获到文件列表: [ {} files ]", files.len());
-                
-                // 转换 PathBuf 为 String
-                let file_paths: Vec<String> = files
-                    .iter()
-                    .map(|p| p.to_string_lossy().to_string())
-                    .collect();
-                
-                // 保存到数据库
-                if let Ok(mut store) = storage.lock() {
-                    if let Err(e) = store.add_files(file_paths) {
-                        eprintln!("❌ 保存文件列表失败: {}", e);
+
+                // 释放锁之后再识别二维码和文字，完成后通知前端刷新
+                if let Some(id) = saved_id {
+                    match barcode::process(&storage_clone, id) {
+                        Ok(derived) => for derived_id in derived {
+                            let _ = app_clone.emit("clipboard://new-clip", serde_json::json!({
+                                "type": "text",
+                                "id": derived_id,
+                                "source_id": id
+                            }));
+                        },
+                        Err(e) => eprintln!("❌ 二维码识别失败 (记录 {}): {}", id, e),
+                    }
+                    match ocr::process(&storage_clone, id) {
+                        Ok(()) => { let _ = app_clone.emit("clipboard://ocr-ready", serde_json::json!({ "id": id })); }
+                        Err(e) => eprintln!("❌ OCR 失败 (记录 {}): {}", id, e),
                     }
                 }
-                
-                let _ = app.emit("clipboard://new-clip", serde_json::json!({
-                    "type": "files",
-                    "preview": "Files"
-                }));
-            },
-            Ok(ClipEvent::Error(e)) => {
-                eprintln!("❌ 读取失败: {}", e);
-            },
-            Err(_) => {}
+            });
+        },
+        ClipEvent::Html(html) => {
+            println!("✅ 捕获到 HTML: [ {} bytes ]", html.len());
+            
+            // 从 HTML 中提取纯文本作为 preview
+            let text_preview = html::to_plain_text(&html);
+            
+            // 保存到数据库；大段内容不经日志和事件传给前端，前端按 ID 重新拉取
+            let mut saved_id = None;
+            if let Ok(mut store) = storage.lock() {
+                match store.add_html(text_preview, html) {
+                    Ok(id) => {
+                        saved_id = Some(id);
+                        finish_record(&mut store, id, &alternates, source.as_ref());
+                    }
+                    Err(e) => eprintln!("❌ 保存 HTML 失败: {}", e),
+                }
+            }
+            
+            let _ = app.emit("clipboard://new-clip", serde_json::json!({
+                "type": "html",
+                "id": saved_id
+            }));
+        },
+        ClipEvent::Rtf(rtf) => {
+            println!("✅ 捕获到 RTF: [ {} bytes ]", rtf.len());
+
+            let mut saved_id = None;
+            if let Ok(mut store) = storage.lock() {
+                match store.add_rtf(rtf) {
+                    Ok(id) => {
+                        saved_id = Some(id);
+                        finish_record(&mut store, id, &alternates, source.as_ref());
+                    }
+                    Err(e) => eprintln!("❌ 保存 RTF 失败: {}", e),
+                }
+            }
+
+            let _ = app.emit("clipboard://new-clip", serde_json::json!({
+                "type": "rtf",
+                "id": saved_id
+            }));
+        },
+        ClipEvent::Custom { mime, data } => {
+            println!("✅ 捕获到私有格式: [ {}, {} bytes ]", mime, data.len());
+
+            if mime == MIME_SVG {
+                let markup = String::from_utf8_lossy(&data);
+                if let Some(id) = save_svg(storage, &markup, &alternates, source.as_ref()) {
+                    let _ = app.emit("clipboard://new-clip", serde_json::json!({
+                        "type": "image",
+                        "id": id
+                    }));
+                    return;
+                }
+            }
+            let preview = format!("[{}] {} bytes", mime, data.len());
+
+            if let Ok(mut store) = storage.lock() {
+                match store.add_custom(mime, data) {
+                    Ok(id) => finish_record(&mut store, id, &alternates, source.as_ref()),
+                    Err(e) => eprintln!("❌ 保存私有格式失败: {}", e),
+                }
+            }
+
+            let _ = app.emit("clipboard://new-clip", serde_json::json!({
+                "type": "custom",
+                "preview": preview
+            }));
+        },
+        ClipEvent::FileList(files) => {
+            println!("✅ 捕获到文件列表: [ {} files ]", files.len());
+            
+            // 转换 PathBuf 为 String
+            let file_paths: Vec<String> = files
+                .iter()
+                .map(|p| p.to_string_lossy().to_string())
+                .collect();
+            
+            // 保存到数据库
+            if let Ok(mut store) = storage.lock() {
+                match store.add_files(file_paths) {
+                    Ok(id) => finish_record(&mut store, id, &alternates, source.as_ref()),
+                    Err(e) => eprintln!("❌ 保存文件列表失败: {}", e),
+                }
+            }
+            
+            let _ = app.emit("clipboard://new-clip", serde_json::json!({
+                "type": "files",
+                "preview": "Files"
+            }));
+        },
+        ClipEvent::Ephemeral { text, source, session } => {
+            println!("🔒 暂存到内存: [ {} 字符 ]", text.chars().count());
+            if let Ok(mut store) = storage.lock() {
+                let id = if session {
+                    Ok(store.add_session_clip(text, source))
+                } else {
+                    store.add_ephemeral(text, source)
+                };
+                match id {
+                    Ok(id) => { let _ = app.emit("clipboard://ephemeral", serde_json::json!({ "id": id })); }
+                    Err(e) => eprintln!("❌ 暂存失败: {}", e),
+                }
+            }
+        },
+        ClipEvent::Error(e) => {
+            eprintln!("❌ 读取失败: {}", e);
+        },
+    }
+}
+
+/// 渲染并保存 SVG，失败时返回 None 由调用方按原类型保存
+fn save_svg(
+    storage: &Arc<Mutex<Storage>>,
+    markup: &str,
+    alternates: &[ClipPart],
+    source: Option<&SourceApp>,
+) -> Option<i64> {
+    println!("✅ 识别为 SVG: [ {} bytes ]", markup.len());
+    let mut store = storage.lock().ok()?;
+    match store.add_svg(markup.to_string()) {
+        Ok((id, _)) => {
+            // 源码已作为附加表示保存，不再用剪贴板上的同类表示覆盖
+            let rest: Vec<ClipPart> = alternates.iter()
+                .filter(|p| p.mime != MIME_SVG && p.mime != MIME_TEXT)
+                .cloned()
+                .collect();
+            finish_record(&mut store, id, &rest, source);
+            Some(id)
+        }
+        Err(e) => {
+            eprintln!("⚠️ SVG 渲染失败，按原格式保存: {}", e);
+            None
+        }
+    }
+}
+
+/// 保存附加表示和来源应用
+fn finish_record(store: &mut Storage, id: i64, alternates: &[ClipPart], source: Option<&SourceApp>) {
+    if let Err(e) = store.add_parts(id, alternates) {
+        eprintln!("❌ 保存附加表示失败: {}", e);
+    }
+    if let Some(app) = source.filter(|_| id > 0) {
+        if let Err(e) = store.set_source_app(id, app) {
+            eprintln!("❌ 保存来源应用失败: {}", e);
+        }
+    }
+}
+
+/// 写回剪贴板的一次性记录到期时，若剪贴板内容仍是它则清空剪贴板
+fn clear_expired_one_time(app: &AppHandle, expired: &[i64]) {
+    let state = app.state::<AppState>();
+    let clip = match state.one_time.lock() {
+        Ok(mut one_time) if one_time.as_ref().is_some_and(|clip| expired.contains(&clip.id)) => one_time.take(),
+        _ => None,
+    };
+    let Some(clip) = clip else { return };
+    let Ok(mut writer) = state.writer.lock() else { return };
+    if let Some(writer) = writer.as_mut() {
+        if writer.fingerprint().as_deref() == Some(clip.fingerprint.as_str()) {
+            match writer.clear() {
+                Ok(()) => println!("🧹 一次性记录已到期，已清空剪贴板: ID {}", clip.id),
+                Err(e) => eprintln!("❌ 清空剪贴板失败: {}", e),
+            }
+        }
+    }
+}
+
+/// 修改记录状态并通知界面；退出隐身模式时丢弃隐身期间的内存记录
+fn update_capture(
+    app: &AppHandle,
+    apply: impl FnOnce(&mut capture::CaptureControl),
+) -> Result<CaptureState, String> {
+    let state = app.state::<AppState>();
+    let (previous, current) = {
+        let mut capture = state.capture.lock().map_err(|_| "Lock error")?;
+        let previous = capture.state();
+        apply(&mut capture);
+        (previous, capture.state())
+    };
+
+    if previous == CaptureState::Incognito && current != CaptureState::Incognito {
+        let cleared = state.storage.lock().map_err(|_| "Lock error")?.clear_session_clips();
+        if !cleared.is_empty() {
+            let _ = app.emit("clipboard://ephemeral-expired", serde_json::json!({ "ids": cleared }));
+        }
+    }
+    emit_capture_state(app, current);
+    Ok(current)
+}
+
+fn emit_capture_state(app: &AppHandle, state: CaptureState) {
+    let _ = app.emit("capture://state", state);
+}
+
+/// 解锁加密存储；上次就地加密中途退出时继续加密剩余的文件
+fn unlock_storage(storage: &mut Storage, passphrase: Option<&str>) -> anyhow::Result<()> {
+    let mut config = EncryptionConfig::load(storage.data_dir())
+        .ok_or_else(|| anyhow::anyhow!("缺少加密配置 encryption.json"))?;
+    let key: Arc<dyn Cipher> = Arc::new(config.key(passphrase)?);
+    storage.unlock_store(Arc::clone(&key))?;
+    if !config.complete {
+        storage.encrypt(key)?;
+        config.complete = true;
+        config.save(storage.data_dir())?;
+    }
+    Ok(())
+}
+
+/// 记录用户操作时间，用于空闲自动锁定
+fn touch_activity(state: &AppState) {
+    if let Ok(mut last_activity) = state.last_activity.lock() {
+        *last_activity = std::time::Instant::now();
+    }
+}
+
+/// 口令加密的存储空闲超过设置的时长后自动锁定
+///
+/// 密钥保存在系统钥匙串时不自动锁定：启动时已自动解锁，锁定只会让复制暂存在内存中
+fn auto_lock_if_idle(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let Ok(idle) = state.last_activity.lock().map(|last_activity| last_activity.elapsed()) else { return };
+    let Ok(mut storage) = state.storage.lock() else { return };
+    let minutes = storage.settings().auto_lock_minutes;
+    if minutes == 0 || storage.is_locked() || !storage.is_encrypted() || idle.as_secs() < minutes.saturating_mul(60) {
+        return;
+    }
+    let from_keyring = EncryptionConfig::load(storage.data_dir())
+        .is_none_or(|config| config.key_source == KeySource::Keyring);
+    if from_keyring {
+        return;
+    }
+    match storage.lock_store() {
+        Ok(()) => {
+            drop(storage);
+            println!("🔒 已空闲 {} 分钟，自动锁定存储", minutes);
+            emit_store_state(app, true);
+            emit_vault_state(app, None);
         }
+        Err(e) => eprintln!("❌ 自动锁定失败: {}", e),
     }
 }
 
+fn emit_store_state(app: &AppHandle, locked: bool) {
+    let _ = app.emit("store://state", serde_json::json!({ "locked": locked }));
+}
+
+fn emit_vault_state(app: &AppHandle, expires_at: Option<i64>) {
+    let _ = app.emit("vault://state", serde_json::json!({
+        "unlocked": expires_at.is_some(),
+        "expires_at": expires_at
+    }));
+}
+
 // ============================================================================
 // 辅助函数 - 初始化各个子系统
 // ============================================================================
@@ -355,13 +1218,21 @@ fn setup_tray(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
     use tauri::tray::MouseButton;
     use tauri::tray::MouseButtonState;
     use tauri::include_image;
-    use tauri::menu::{Menu, MenuItem};
+    use tauri::menu::{Menu, MenuItem, PredefinedMenuItem};
 
     // 创建托盘菜单
     let show_window = MenuItem::with_id(app, "show", "打开窗口", true, None::<&str>)?;
     let open_settings = MenuItem::with_id(app, "settings", "打开设置", true, None::<&str>)?;
+    let pause_15 = MenuItem::with_id(app, "pause_15", "暂停记录 15 分钟", true, None::<&str>)?;
+    let pause = MenuItem::with_id(app, "pause", "暂停记录", true, None::<&str>)?;
+    let incognito = MenuItem::with_id(app, "incognito", "隐身模式", true, None::<&str>)?;
+    let resume = MenuItem::with_id(app, "resume", "恢复记录", true, None::<&str>)?;
+    let separator = PredefinedMenuItem::separator(app)?;
+    let separator2 = PredefinedMenuItem::separator(app)?;
     let quit = MenuItem::with_id(app, "quit", "退出", true, None::<&str>)?;
-    let menu = Menu::with_items(app, &[&show_window, &open_settings, &quit])?;
+    let menu = Menu::with_items(app, &[
+        &show_window, &open_settings, &separator, &pause_15, &pause, &incognito, &resume, &separator2, &quit,
+    ])?;
 
     // 创建托盘图标
     let _tray = TrayIconBuilder::new()
@@ -384,6 +1255,18 @@ fn setup_tray(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
                             .spawn();
                     }
                 }
+                "pause_15" => {
+                    let _ = update_capture(app, |capture| { let _ = capture.pause(Some(chrono::Duration::minutes(15))); });
+                }
+                "pause" => {
+                    let _ = update_capture(app, |capture| { let _ = capture.pause(None); });
+                }
+                "incognito" => {
+                    let _ = update_capture(app, |capture| { capture.set(CaptureState::Incognito); });
+                }
+                "resume" => {
+                    let _ = update_capture(app, |capture| { capture.resume(); });
+                }
                 "quit" => {
                     app.exit(0);
                 }
@@ -416,7 +1299,7 @@ fn setup_tray(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
 
 /// 注册全局快捷键
 fn setup_global_shortcut(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
-    use tauri_plugin_global_shortcut::GlobalShortcutExt;
+    use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
     
     #[cfg(target_os = "macos")]
     let shortcut = "Cmd+Shift+V";
@@ -437,6 +1320,22 @@ fn setup_global_shortcut(app: &mut tauri::App) -> Result<(), Box<dyn std::error:
         #[cfg(target_os = "macos")]
         println!("macOS提示: 需要在系统设置 → 隐私与安全 → 辅助功能 中授予权限");
     }
+
+    // 暂停 / 恢复记录
+    #[cfg(target_os = "macos")]
+    let pause_shortcut = "Cmd+Alt+P";
+    #[cfg(not(target_os = "macos"))]
+    let pause_shortcut = "Ctrl+Alt+P";
+
+    let registered = app.global_shortcut().on_shortcut(pause_shortcut, move |app_handle, _shortcut, event| {
+        if event.state() == ShortcutState::Pressed {
+            let _ = update_capture(app_handle, |capture| { capture.toggle_pause(); });
+        }
+    });
+    match registered {
+        Ok(()) => println!("✅ 暂停快捷键已注册: {}", pause_shortcut),
+        Err(e) => println!("⚠️ 暂停快捷键注册失败: {} ({})", pause_shortcut, e),
+    }
     
     Ok(())
 }
@@ -445,22 +1344,66 @@ fn setup_global_shortcut(app: &mut tauri::App) -> Result<(), Box<dyn std::error:
 fn setup_storage_and_clipboard(
     app: &mut tauri::App,
     rx: crossbeam_channel::Receiver<clipboard::ClipEvent>,
+    privacy: SharedGuard,
+    capture: SharedCapture,
+    custom_formats: SharedFormats,
+    concealed_policy: SharedPolicy,
+    self_write: SharedSelfWrite,
 ) -> Result<(), Box<dyn std::error::Error>> {
     // 使用 $HOME/Documents/pastee 作为数据目录
-    let home = dirs::home_dir().ok_or("Failed to get home directory")?;
-    let data_dir = home.join("Documents").join("pastee");
+    let data_dir = setting::data_dir();
     
-    let app_state = AppState::new(data_dir.clone()).map_err(|e| e.to_string())?;
-    let shared_storage = Arc::new(Mutex::new(
-        Storage::new(&data_dir).map_err(|e| e.to_string())?
-    ));
+    let app_state = AppState::new(data_dir, privacy, capture, custom_formats, concealed_policy, self_write).map_err(|e| e.to_string())?;
+    let capture_clone = Arc::clone(&app_state.capture);
+    let storage_clone = Arc::clone(&app_state.storage);
     
     app.manage(app_state);
 
     // 获取 app handle 用于事件推送
     let app_handle = app.handle().clone();
-    let storage_clone = Arc::clone(&shared_storage);
     
+    // 后台补识别启用 OCR 之前保存的图片
+    let ocr_storage = Arc::clone(&storage_clone);
+    thread::spawn(move || {
+        match ocr::process_pending(&ocr_storage) {
+            Ok(0) => {}
+            Ok(n) => println!("🔤 已补识别 {} 张图片", n),
+            Err(e) => eprintln!("❌ 补识别图片失败: {}", e),
+        }
+    });
+
+    // 定期丢弃过期的临时记录和到期的敏感记录，保险库解锁到期后锁定
+    let expiry_storage = Arc::clone(&storage_clone);
+    let expiry_handle = app_handle.clone();
+    let expiry_capture = capture_clone;
+    thread::spawn(move || loop {
+        thread::sleep(std::time::Duration::from_secs(1));
+        // 定时暂停到期后自动恢复
+        let resumed = expiry_capture.lock().map(|mut capture| capture.refresh()).unwrap_or(false);
+        if resumed {
+            emit_capture_state(&expiry_handle, CaptureState::Active);
+        }
+        auto_lock_if_idle(&expiry_handle);
+        let (ephemeral, clips, vault_locked) = match expiry_storage.lock() {
+            Ok(mut store) => (store.purge_ephemeral(), store.purge_expired(), store.refresh_vault()),
+            Err(_) => continue,
+        };
+        if vault_locked {
+            emit_vault_state(&expiry_handle, None);
+        }
+        if !ephemeral.is_empty() {
+            let _ = expiry_handle.emit("clipboard://ephemeral-expired", serde_json::json!({ "ids": ephemeral }));
+        }
+        match clips {
+            Ok(ids) if !ids.is_empty() => {
+                clear_expired_one_time(&expiry_handle, &ids);
+                let _ = expiry_handle.emit("clipboard://clips-expired", serde_json::json!({ "ids": ids }));
+            }
+            Ok(_) => {}
+            Err(e) => eprintln!("❌ 删除到期记录失败: {}", e),
+        }
+    });
+
     thread::spawn(move || {
         handle_clipboard_event(rx, app_handle, storage_clone);
     });
@@ -475,15 +1418,24 @@ fn setup_window_events(app: &mut tauri::App) -> Result<(), Box<dyn std::error::E
         let window_clone = window.clone();
         let app_handle = app.handle().clone();
         window.on_window_event(move |event| {
-            if let tauri::WindowEvent::Focused(false) = event {
-                // 检查是否设置了保持窗口打开
-                if let Some(state) = app_handle.try_state::<AppState>() {
-                    if let Ok(keep_open) = state.keep_window_open.lock() {
-                        if !*keep_open {
-                            let _ = window_clone.hide();
+            match event {
+                // 打开窗口算作一次操作，推迟自动锁定
+                tauri::WindowEvent::Focused(true) => {
+                    if let Some(state) = app_handle.try_state::<AppState>() {
+                        touch_activity(&state);
+                    }
+                }
+                tauri::WindowEvent::Focused(false) => {
+                    // 检查是否设置了保持窗口打开
+                    if let Some(state) = app_handle.try_state::<AppState>() {
+                        if let Ok(keep_open) = state.keep_window_open.lock() {
+                            if !*keep_open {
+                                let _ = window_clone.hide();
+                            }
                         }
                     }
                 }
+                _ => {}
             }
         });
         
@@ -1,20 +1,45 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+use pastee_lib::capture::CaptureControl;
 use pastee_lib::clipboard::SystemHook;
+use pastee_lib::privacy::PrivacyGuard;
+use pastee_lib::setting::{self, Settings};
 use clipboard_master::Master;
+use std::sync::{Arc, Mutex};
 use std::thread;
 use crossbeam_channel;
 
 
 fn main() {
     let (tx, rx) = crossbeam_channel::bounded(128);
+    let settings = Settings::load(setting::data_dir());
+    // 隐私黑名单由监听线程和界面共享
+    let privacy = Arc::new(Mutex::new(PrivacyGuard::new(&settings.privacy_blocklist)));
+    let hook_privacy = Arc::clone(&privacy);
+    // 记录状态 (暂停 / 隐身) 同样共享
+    let capture = Arc::new(Mutex::new(CaptureControl::default()));
+    let hook_capture = Arc::clone(&capture);
+    // 自定义格式白名单同样共享，修改设置后立即生效
+    let custom_formats = Arc::new(Mutex::new(settings.custom_formats.clone()));
+    let hook_formats = Arc::clone(&custom_formats);
+    // 隐藏标记的处理方式同样共享
+    let concealed_policy = Arc::new(Mutex::new(settings.concealed_policy));
+    let hook_policy = Arc::clone(&concealed_policy);
+    // 本程序写入的机密文本指纹，监听线程据此跳过
+    let self_write = Arc::new(Mutex::new(None));
+    let hook_self_write = Arc::clone(&self_write);
 
-    thread::spawn(|| {
-        let handler = SystemHook::new(tx);
+    thread::spawn(move || {
+        let handler = SystemHook::new(tx)
+            .with_custom_formats(hook_formats)
+            .with_privacy(hook_privacy)
+            .with_capture(hook_capture)
+            .with_self_write(hook_self_write)
+            .with_concealed_policy(hook_policy);
         println!(">> 🎧 剪切板监听已启动...");
         let _ = Master::new(handler).unwrap().run();
     });
 
-    pastee_lib::run(rx)
+    pastee_lib::run(rx, privacy, capture, custom_formats, concealed_policy, self_write)
 }
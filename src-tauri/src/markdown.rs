@@ -0,0 +1,348 @@
+//! HTML 转 Markdown：用于“粘贴为 Markdown”和额外的可搜索表示
+//!
+//! 支持标题、段落、强调、行内代码、代码块、链接、图片、
+//! 有序 / 无序列表（可嵌套）、引用、分隔线和 GFM 表格
+
+use ego_tree::NodeRef;
+use scraper::{ElementRef, Html, Node};
+
+/// 内容不可见的元素，整棵子树跳过
+const SKIP_ELEMENTS: &[&str] = &["head", "script", "style", "template", "noscript", "title"];
+
+/// 超过该嵌套深度的子树按纯文本输出，防止栈溢出
+const MAX_DEPTH: usize = 256;
+
+/// HTML 转 Markdown
+pub fn from_html(html: &str) -> String {
+    let document = Html::parse_document(html);
+    let raw = Converter { depth: 0 }.children(document.tree.root(), false);
+    cleanup(&raw)
+}
+
+struct Converter {
+    depth: usize,
+}
+
+impl Converter {
+    fn children(&mut self, node: NodeRef<Node>, pre: bool) -> String {
+        let mut out = String::new();
+        for child in node.children() {
+            let piece = self.node(child, pre);
+            join_piece(&mut out, &piece);
+        }
+        out
+    }
+
+    fn node(&mut self, node: NodeRef<Node>, pre: bool) -> String {
+        match node.value() {
+            Node::Text(text) if pre => text.to_string(),
+            Node::Text(text) => escape(&collapse_whitespace(text)),
+            Node::Element(_) => {
+                let Some(element) = ElementRef::wrap(node) else { return String::new() };
+                if self.depth >= MAX_DEPTH {
+                    return escape(&collapse_whitespace(&raw_text(node)));
+                }
+                self.depth += 1;
+                let out = self.element(element);
+                self.depth -= 1;
+                out
+            }
+            Node::Document | Node::Fragment => self.children(node, pre),
+            _ => String::new(),
+        }
+    }
+
+    fn element(&mut self, element: ElementRef) -> String {
+        let node = *element;
+        let name = element.value().name();
+        if SKIP_ELEMENTS.contains(&name) {
+            return String::new();
+        }
+
+        match name {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                let level = name[1..].parse::<usize>().unwrap_or(1);
+                let text = single_line(&self.children(node, false));
+                if text.is_empty() {
+                    return String::new();
+                }
+                format!("\n\n{} {}\n\n", "#".repeat(level), text)
+            }
+            "p" => format!("\n\n{}\n\n", self.children(node, false).trim()),
+            "div" | "section" | "article" | "header" | "footer" | "main" | "nav" | "aside"
+            | "figure" | "figcaption" | "address" | "details" | "summary" | "dl" | "dt" | "dd" => {
+                format!("\n{}\n", self.children(node, false).trim())
+            }
+            "br" => "  \n".to_string(),
+            "hr" => "\n\n---\n\n".to_string(),
+            "strong" | "b" => wrap_inline(&self.children(node, false), "**"),
+            "em" | "i" => wrap_inline(&self.children(node, false), "*"),
+            "del" | "s" | "strike" => wrap_inline(&self.children(node, false), "~~"),
+            "code" | "kbd" | "samp" | "tt" => inline_code(&raw_text(node)),
+            "pre" => code_block(element),
+            "a" => {
+                let text = single_line(&self.children(node, false));
+                match element.value().attr("href").map(str::trim) {
+                    Some(href) if is_safe_url(href) => {
+                        if text.is_empty() || text == escape(href) {
+                            format!("<{}>", href)
+                        } else {
+                            format!("[{}]({})", text, escape_url(href))
+                        }
+                    }
+                    _ => text,
+                }
+            }
+            "img" => {
+                let alt = escape(&collapse_whitespace(element.value().attr("alt").unwrap_or("")));
+                match element.value().attr("src").map(str::trim) {
+                    Some(src) if !src.is_empty() => format!("![{}]({})", alt.trim(), escape_url(src)),
+                    _ => String::new(),
+                }
+            }
+            "blockquote" => {
+                let inner = cleanup(&self.children(node, false));
+                if inner.is_empty() {
+                    return String::new();
+                }
+                let quoted = inner
+                    .lines()
+                    .map(|line| if line.is_empty() { ">".to_string() } else { format!("> {}", line) })
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                format!("\n\n{}\n\n", quoted)
+            }
+            "ul" | "ol" => self.list(element),
+            "table" => self.table(element),
+            _ => self.children(node, false),
+        }
+    }
+
+    fn list(&mut self, list: ElementRef) -> String {
+        let ordered = list.value().name() == "ol";
+        let mut number = list.value().attr("start").and_then(|s| s.trim().parse::<i64>().ok()).unwrap_or(1);
+
+        let mut items = Vec::new();
+        for child in list.children() {
+            let Some(item) = ElementRef::wrap(child) else { continue };
+            let marker = if ordered {
+                let marker = format!("{}. ", number);
+                number += 1;
+                marker
+            } else {
+                "- ".to_string()
+            };
+
+            let content = if item.value().name() == "li" {
+                self.depth += 1;
+                let content = self.children(*item, false);
+                self.depth -= 1;
+                content
+            } else {
+                // 直接嵌套在列表中的子列表等
+                self.node(*item, false)
+            };
+            // 列表项内部保持紧凑，不留空行
+            let content = cleanup(&content)
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            let indent = " ".repeat(marker.len());
+            let mut lines = content.lines();
+            let mut text = format!("{}{}", marker, lines.next().unwrap_or("").trim_start());
+            for line in lines {
+                text.push('\n');
+                text.push_str(&indent);
+                text.push_str(line);
+            }
+            items.push(text.trim_end().to_string());
+        }
+        format!("\n\n{}\n\n", items.join("\n"))
+    }
+
+    fn table(&mut self, table: ElementRef) -> String {
+        let mut rows: Vec<Vec<String>> = Vec::new();
+        for row in table.descendants().filter_map(ElementRef::wrap) {
+            if row.value().name() != "tr" {
+                continue;
+            }
+            // 跳过嵌套表格中的行
+            let owner = row.ancestors().filter_map(ElementRef::wrap).find(|e| e.value().name() == "table");
+            if owner.map(|t| t.id()) != Some(table.id()) {
+                continue;
+            }
+            let cells = row
+                .children()
+                .filter_map(ElementRef::wrap)
+                .filter(|cell| matches!(cell.value().name(), "td" | "th"))
+                .map(|cell| single_line(&self.children(*cell, false)).replace('|', "\\|"))
+                .collect::<Vec<_>>();
+            rows.push(cells);
+        }
+
+        let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+        if columns == 0 {
+            return String::new();
+        }
+        for row in &mut rows {
+            row.resize(columns, String::new());
+        }
+
+        let format_row = |cells: &[String]| format!("| {} |", cells.join(" | "));
+        let mut lines = vec![format_row(&rows[0]), format_row(&vec!["---".to_string(); columns])];
+        lines.extend(rows[1..].iter().map(|row| format_row(row)));
+        format!("\n\n{}\n\n", lines.join("\n"))
+    }
+}
+
+/// 拼接时去掉行首 / 行尾多余的空格，缩进由父元素在之后统一添加
+fn join_piece(out: &mut String, piece: &str) {
+    if piece.is_empty() {
+        return;
+    }
+    let mut piece = piece;
+    if out.is_empty() || out.ends_with('\n') {
+        piece = piece.trim_start_matches(' ');
+    }
+    if piece.starts_with('\n') && !out.ends_with("  ") {
+        let trimmed = out.trim_end_matches(' ').len();
+        out.truncate(trimmed);
+    }
+    if piece.starts_with(' ') && out.ends_with(' ') {
+        piece = piece.trim_start_matches(' ');
+    }
+    out.push_str(piece);
+}
+
+/// 去掉空白行中的空格，合并连续空行（代码块内保持原样）
+fn cleanup(markdown: &str) -> String {
+    let mut out: Vec<&str> = Vec::new();
+    let mut in_fence = false;
+    for line in markdown.split('\n') {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        }
+        if !in_fence && line.trim().is_empty() {
+            if out.last().is_some_and(|l| l.is_empty()) || out.is_empty() {
+                continue;
+            }
+            out.push("");
+        } else {
+            out.push(line);
+        }
+    }
+    out.join("\n").trim_matches('\n').trim_end().to_string()
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut space = false;
+    for c in text.chars() {
+        if c.is_whitespace() {
+            space = true;
+        } else {
+            if space {
+                out.push(' ');
+                space = false;
+            }
+            out.push(c);
+        }
+    }
+    if space {
+        out.push(' ');
+    }
+    out
+}
+
+fn single_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// 转义会被误认为 Markdown 语法的字符
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '*' | '_' | '`' | '[' | ']') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn escape_url(url: &str) -> String {
+    url.replace(' ', "%20").replace('(', "%28").replace(')', "%29")
+}
+
+fn is_safe_url(url: &str) -> bool {
+    !url.is_empty() && !url.to_ascii_lowercase().starts_with("javascript:")
+}
+
+fn wrap_inline(inner: &str, mark: &str) -> String {
+    let trimmed = inner.trim();
+    if trimmed.is_empty() {
+        return inner.to_string();
+    }
+    // 保留原有的前后空格，避免和相邻单词粘连
+    let lead = if inner.starts_with(' ') { " " } else { "" };
+    let trail = if inner.ends_with(' ') { " " } else { "" };
+    format!("{}{}{}{}{}", lead, mark, trimmed, mark, trail)
+}
+
+fn inline_code(code: &str) -> String {
+    let code = code.replace('\n', " ");
+    if code.trim().is_empty() {
+        return String::new();
+    }
+    // 内容含反引号时使用更长的定界符
+    let longest = longest_run(&code, '`');
+    let fence = "`".repeat(longest + 1);
+    if longest > 0 {
+        format!("{} {} {}", fence, code, fence)
+    } else {
+        format!("{}{}{}", fence, code, fence)
+    }
+}
+
+fn code_block(pre: ElementRef) -> String {
+    let code = raw_text(*pre);
+    let code = code.strip_prefix('\n').unwrap_or(&code).trim_end();
+    let language = std::iter::once(pre)
+        .chain(pre.children().filter_map(ElementRef::wrap).filter(|e| e.value().name() == "code"))
+        .find_map(|e| e.value().classes().find_map(language_of))
+        .unwrap_or_default();
+    let fence = "`".repeat(longest_run(code, '`').max(2) + 1);
+    format!("\n\n{}{}\n{}\n{}\n\n", fence, language, code, fence)
+}
+
+fn language_of(class: &str) -> Option<String> {
+    class
+        .strip_prefix("language-")
+        .or_else(|| class.strip_prefix("lang-"))
+        .filter(|lang| !lang.is_empty())
+        .map(str::to_string)
+}
+
+fn longest_run(text: &str, target: char) -> usize {
+    let (mut longest, mut current) = (0, 0);
+    for c in text.chars() {
+        current = if c == target { current + 1 } else { 0 };
+        longest = longest.max(current);
+    }
+    longest
+}
+
+/// 子树的原始文本，`<br>` 视为换行
+fn raw_text(node: NodeRef<Node>) -> String {
+    let mut out = String::new();
+    for descendant in node.descendants() {
+        match descendant.value() {
+            Node::Text(text) => out.push_str(text),
+            Node::Element(element) if element.name() == "br" => out.push('\n'),
+            _ => {}
+        }
+    }
+    out
+}
@@ -0,0 +1,254 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// 任意 MIME 格式的剪贴板读写后端
+///
+/// arboard 只覆盖文本 / HTML / 图片 / 文件列表，
+/// RTF 等其它格式通过该 trait 读写
+pub trait MimeBackend: Send {
+    /// 当前剪贴板提供的所有格式；无法列出时返回错误 (如 wl-paste 未安装)
+    fn targets(&self) -> anyhow::Result<Vec<String>>;
+    /// 读取指定格式的原始字节
+    fn read(&self, mime: &str) -> Option<Vec<u8>>;
+    /// 写入剪贴板（会取代剪贴板上的现有内容），`formats` 按优先级排列
+    fn write(&self, formats: &[(String, Vec<u8>)]) -> anyhow::Result<()>;
+    /// 是否能同时提供多种格式；否则 `write` 只写入第一种
+    fn supports_multiple(&self) -> bool {
+        false
+    }
+}
+
+/// 根据当前平台选择默认后端
+pub fn default_backend() -> Box<dyn MimeBackend> {
+    #[cfg(target_os = "linux")]
+    {
+        if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+            return Box::new(CommandBackend::wayland());
+        }
+        Box::new(X11Backend)
+    }
+    #[cfg(not(target_os = "linux"))]
+    Box::new(NullBackend)
+}
+
+/// 通过外部命令 (wl-clipboard / xclip) 读写剪贴板
+///
+/// 这些工具一次只能提供一种格式，写入时剪贴板上只保留该格式
+pub struct CommandBackend {
+    list_cmd: Vec<&'static str>,
+    read_cmd: Vec<&'static str>,
+    write_cmd: Vec<&'static str>,
+}
+
+impl CommandBackend {
+    pub fn x11() -> Self {
+        Self {
+            list_cmd: vec!["xclip", "-selection", "clipboard", "-o", "-t", "TARGETS"],
+            read_cmd: vec!["xclip", "-selection", "clipboard", "-o", "-t"],
+            write_cmd: vec!["xclip", "-selection", "clipboard", "-i", "-t"],
+        }
+    }
+
+    pub fn wayland() -> Self {
+        Self {
+            list_cmd: vec!["wl-paste", "--list-types"],
+            read_cmd: vec!["wl-paste", "--no-newline", "--type"],
+            write_cmd: vec!["wl-copy", "--type"],
+        }
+    }
+
+    fn run(args: &[&str]) -> Option<Vec<u8>> {
+        let (program, rest) = args.split_first()?;
+        let output = Command::new(program)
+            .args(rest)
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+            .ok()?;
+        if output.status.success() { Some(output.stdout) } else { None }
+    }
+}
+
+impl MimeBackend for CommandBackend {
+    fn targets(&self) -> anyhow::Result<Vec<String>> {
+        let out = Self::run(&self.list_cmd)
+            .ok_or_else(|| anyhow::anyhow!("无法通过 {} 列出剪贴板格式", self.list_cmd.join(" ")))?;
+        Ok(String::from_utf8_lossy(&out)
+            .lines()
+            .map(|l| l.trim().to_string())
+            .filter(|l| !l.is_empty())
+            .collect())
+    }
+
+    fn read(&self, mime: &str) -> Option<Vec<u8>> {
+        let mut args = self.read_cmd.clone();
+        args.push(mime);
+        Self::run(&args).filter(|data| !data.is_empty())
+    }
+
+    fn write(&self, formats: &[(String, Vec<u8>)]) -> anyhow::Result<()> {
+        let (mime, data) = formats.first()
+            .ok_or_else(|| anyhow::anyhow!("没有可写入的格式"))?;
+        let (program, rest) = self.write_cmd.split_first()
+            .ok_or_else(|| anyhow::anyhow!("写入命令为空"))?;
+        let mut child = Command::new(program)
+            .args(rest)
+            .arg(mime)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()?;
+        child.stdin.take()
+            .ok_or_else(|| anyhow::anyhow!("无法打开 {} 的标准输入", program))?
+            .write_all(data)?;
+        // xclip / wl-copy 会在后台继续提供内容，这里只等待前台进程退出
+        let status = child.wait()?;
+        if !status.success() {
+            return Err(anyhow::anyhow!("{} 退出码: {}", program, status));
+        }
+        Ok(())
+    }
+}
+
+/// 不支持额外格式的平台 (macOS / Windows)
+///
+/// 无法列出原生格式，因此这些平台上识别不到密码管理器的隐藏标记
+pub struct NullBackend;
+
+impl MimeBackend for NullBackend {
+    fn targets(&self) -> anyhow::Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    fn read(&self, _mime: &str) -> Option<Vec<u8>> {
+        None
+    }
+
+    fn write(&self, formats: &[(String, Vec<u8>)]) -> anyhow::Result<()> {
+        let mime = formats.first().map(|(m, _)| m.as_str()).unwrap_or_default();
+        Err(anyhow::anyhow!("当前平台不支持写入 {}", mime))
+    }
+}
+
+/// X11：读取沿用 xclip，写入时自己持有 CLIPBOARD 选区，可同时提供任意多种格式
+#[cfg(target_os = "linux")]
+pub struct X11Backend;
+
+#[cfg(target_os = "linux")]
+impl MimeBackend for X11Backend {
+    fn targets(&self) -> anyhow::Result<Vec<String>> {
+        CommandBackend::x11().targets()
+    }
+
+    fn read(&self, mime: &str) -> Option<Vec<u8>> {
+        CommandBackend::x11().read(mime)
+    }
+
+    fn write(&self, formats: &[(String, Vec<u8>)]) -> anyhow::Result<()> {
+        x11_owner::serve(formats.to_vec())
+    }
+
+    fn supports_multiple(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod x11_owner {
+    use x11rb::connection::Connection;
+    use x11rb::protocol::xproto::{
+        Atom, AtomEnum, ConnectionExt, CreateWindowAux, EventMask, PropMode,
+        SelectionNotifyEvent, SelectionRequestEvent, WindowClass, SELECTION_NOTIFY_EVENT,
+    };
+    use x11rb::protocol::Event;
+    use x11rb::wrapper::ConnectionExt as _;
+    use x11rb::{COPY_DEPTH_FROM_PARENT, COPY_FROM_PARENT, CURRENT_TIME};
+
+    /// 纯文本在 X11 上的等价目标名
+    const TEXT_ALIASES: &[&str] = &["UTF8_STRING", "STRING", "TEXT", "text/plain;charset=utf-8"];
+
+    fn intern(conn: &impl Connection, name: &str) -> anyhow::Result<Atom> {
+        Ok(conn.intern_atom(false, name.as_bytes())?.reply()?.atom)
+    }
+
+    /// 获取 CLIPBOARD 所有权，并在后台线程中响应请求，直到被其它程序取代
+    ///
+    /// 与 arboard 一样不实现 INCR，超过 X 服务器单次请求上限的内容会转换失败
+    pub fn serve(formats: Vec<(String, Vec<u8>)>) -> anyhow::Result<()> {
+        let (conn, screen_num) = x11rb::connect(None)?;
+        let root = conn.setup().roots[screen_num].root;
+        let win = conn.generate_id()?;
+        conn.create_window(
+            COPY_DEPTH_FROM_PARENT, win, root, 0, 0, 1, 1, 0,
+            WindowClass::INPUT_OUTPUT, COPY_FROM_PARENT, &CreateWindowAux::new(),
+        )?;
+
+        let clipboard = intern(&conn, "CLIPBOARD")?;
+        let targets = intern(&conn, "TARGETS")?;
+        let mut served: Vec<(Atom, Vec<u8>)> = Vec::new();
+        for (mime, data) in &formats {
+            served.push((intern(&conn, mime)?, data.clone()));
+            if mime == "text/plain" {
+                for alias in TEXT_ALIASES {
+                    served.push((intern(&conn, alias)?, data.clone()));
+                }
+            }
+        }
+
+        conn.set_selection_owner(win, clipboard, CURRENT_TIME)?;
+        conn.flush()?;
+        if conn.get_selection_owner(clipboard)?.reply()?.owner != win {
+            return Err(anyhow::anyhow!("无法获取剪贴板所有权"));
+        }
+
+        std::thread::spawn(move || {
+            while let Ok(event) = conn.wait_for_event() {
+                match event {
+                    Event::SelectionRequest(req) => {
+                        if let Err(e) = respond(&conn, &req, targets, &served) {
+                            eprintln!("⚠️ 响应剪贴板请求失败: {}", e);
+                        }
+                    }
+                    Event::SelectionClear(_) => break,
+                    _ => {}
+                }
+            }
+        });
+        Ok(())
+    }
+
+    fn respond(
+        conn: &impl Connection,
+        req: &SelectionRequestEvent,
+        targets: Atom,
+        served: &[(Atom, Vec<u8>)],
+    ) -> anyhow::Result<()> {
+        // 旧式客户端可能不指定 property
+        let property = if req.property == Atom::from(AtomEnum::NONE) { req.target } else { req.property };
+
+        let success = if req.target == targets {
+            let mut atoms: Vec<Atom> = served.iter().map(|(atom, _)| *atom).collect();
+            atoms.push(targets);
+            conn.change_property32(PropMode::REPLACE, req.requestor, property, AtomEnum::ATOM, &atoms)?;
+            true
+        } else if let Some((_, data)) = served.iter().find(|(atom, _)| *atom == req.target) {
+            conn.change_property8(PropMode::REPLACE, req.requestor, property, req.target, data)?;
+            true
+        } else {
+            false
+        };
+
+        let notify = SelectionNotifyEvent {
+            response_type: SELECTION_NOTIFY_EVENT,
+            sequence: 0,
+            time: req.time,
+            requestor: req.requestor,
+            selection: req.selection,
+            target: req.target,
+            property: if success { property } else { AtomEnum::NONE.into() },
+        };
+        conn.send_event(false, req.requestor, EventMask::NO_EVENT, notify)?;
+        conn.flush()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,116 @@
+//! 图片文字识别 (OCR)：识别引擎可替换，默认调用本机安装的 tesseract，不访问网络
+
+use anyhow::{anyhow, Context, Result};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+
+use crate::persist::Storage;
+
+/// OCR 引擎
+pub trait OcrEngine: Send + Sync {
+    /// 引擎名称，用于日志
+    fn name(&self) -> &'static str;
+    /// 识别图片文件中的文字，没有文字时返回空字符串
+    fn recognize(&self, image: &Path) -> Result<String>;
+}
+
+/// 通过命令行调用 tesseract
+pub struct TesseractCli {
+    program: PathBuf,
+    languages: Option<String>, // 如 "eng+chi_sim"，None 使用 tesseract 默认语言
+}
+
+impl TesseractCli {
+    pub fn new(languages: Option<String>) -> Self {
+        Self { program: PathBuf::from("tesseract"), languages }
+    }
+
+    /// 指定可执行文件路径
+    pub fn with_program<P: Into<PathBuf>>(mut self, program: P) -> Self {
+        self.program = program.into();
+        self
+    }
+
+    /// tesseract 已安装时返回引擎
+    pub fn detect(languages: Option<String>) -> Option<Self> {
+        let engine = Self::new(languages);
+        let available = Command::new(&engine.program)
+            .arg("--version")
+            .output()
+            .map(|out| out.status.success())
+            .unwrap_or(false);
+        available.then_some(engine)
+    }
+}
+
+impl OcrEngine for TesseractCli {
+    fn name(&self) -> &'static str {
+        "tesseract"
+    }
+
+    fn recognize(&self, image: &Path) -> Result<String> {
+        let mut cmd = Command::new(&self.program);
+        cmd.arg(image).arg("stdout");
+        if let Some(languages) = &self.languages {
+            cmd.arg("-l").arg(languages);
+        }
+        let output = cmd.output().context("Failed to run tesseract")?;
+        if !output.status.success() {
+            return Err(anyhow!(
+                "tesseract 退出码 {}: {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(normalize(&String::from_utf8_lossy(&output.stdout)))
+    }
+}
+
+/// 去掉行尾空白和空行，换页符视为换行
+pub fn normalize(text: &str) -> String {
+    text.split(['\n', '\u{c}'])
+        .map(str::trim_end)
+        .filter(|line| !line.trim().is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 识别一条图片记录并写回数据库
+///
+/// 只在读取路径和写入结果时持锁，识别过程不阻塞剪贴板监听；未设置引擎时直接返回
+pub fn process(storage: &Mutex<Storage>, id: i64) -> Result<()> {
+    let (engine, image) = {
+        let store = storage.lock().map_err(|_| anyhow!("Lock error"))?;
+        match store.ocr_engine() {
+            Some(engine) => (engine, store.image_file(id)?),
+            None => return Ok(()),
+        }
+    };
+
+    let text = engine.recognize(&image)?;
+    println!("🔤 {} 识别记录 {}: {} 个字符", engine.name(), id, text.chars().count());
+
+    let store = storage.lock().map_err(|_| anyhow!("Lock error"))?;
+    store.set_ocr_text(id, &text)
+}
+
+/// 补识别尚未处理的图片 (例如启用 OCR 之前保存的记录)，返回成功识别的数量
+pub fn process_pending(storage: &Mutex<Storage>) -> Result<usize> {
+    let pending = {
+        let store = storage.lock().map_err(|_| anyhow!("Lock error"))?;
+        if store.ocr_engine().is_none() {
+            return Ok(0);
+        }
+        store.pending_ocr()?
+    };
+
+    let mut done = 0;
+    for id in pending {
+        match process(storage, id) {
+            Ok(()) => done += 1,
+            Err(e) => eprintln!("❌ OCR 失败 (记录 {}): {}", id, e),
+        }
+    }
+    Ok(done)
+}
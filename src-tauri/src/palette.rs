@@ -0,0 +1,135 @@
+//! 调色板提取：对图片做中位切分 (median-cut) 量化，得到主色
+
+use image::{imageops::FilterType, DynamicImage};
+use serde::{Deserialize, Serialize};
+
+use crate::color::Rgba;
+
+/// 默认提取的颜色数量
+pub const DEFAULT_COLORS: usize = 6;
+
+/// 参与统计前先缩小到该边长以内，避免大图逐像素计算 (最近邻采样，不会混合出新颜色)
+const SAMPLE_SIZE: u32 = 128;
+
+/// alpha 低于该值的像素视为背景，不参与统计
+const MIN_ALPHA: u8 = 128;
+
+/// 占比低于该值的颜色 (通常是抗锯齿边缘) 不返回
+const MIN_RATIO: f64 = 0.01;
+
+/// 两个颜色在各通道的差都不超过该值时视为同一种颜色并合并
+const MERGE_DISTANCE: u8 = 12;
+
+/// 调色板中的一个颜色
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Swatch {
+    pub color: String, // #rrggbb
+    pub ratio: f64,    // 占不透明像素的比例 (0-1)
+}
+
+impl Swatch {
+    pub fn rgba(&self) -> Option<Rgba> {
+        crate::color::parse(&self.color)
+    }
+}
+
+/// 一个颜色盒子：RGB 空间中的一组像素
+struct ColorBox {
+    pixels: Vec<[u8; 3]>,
+}
+
+impl ColorBox {
+    /// 返回跨度最大的通道及其跨度
+    fn widest_channel(&self) -> (usize, u8) {
+        (0..3)
+            .map(|c| {
+                let (min, max) = self.pixels.iter().fold((u8::MAX, u8::MIN), |(lo, hi), p| {
+                    (lo.min(p[c]), hi.max(p[c]))
+                });
+                (c, max.saturating_sub(min))
+            })
+            .max_by_key(|&(_, range)| range)
+            .unwrap_or((0, 0))
+    }
+
+    /// 沿跨度最大的通道在中位数处切成两半
+    fn split(mut self) -> (ColorBox, ColorBox) {
+        let (channel, _) = self.widest_channel();
+        self.pixels.sort_unstable_by_key(|p| p[channel]);
+        let upper = self.pixels.split_off(self.pixels.len() / 2);
+        (self, ColorBox { pixels: upper })
+    }
+
+    fn average(&self) -> [u8; 3] {
+        let mut sum = [0u64; 3];
+        for p in &self.pixels {
+            for (total, &value) in sum.iter_mut().zip(p) {
+                *total += value as u64;
+            }
+        }
+        let n = self.pixels.len().max(1) as u64;
+        [(sum[0] / n) as u8, (sum[1] / n) as u8, (sum[2] / n) as u8]
+    }
+}
+
+/// 提取图片的主色，按占比从高到低排列
+///
+/// 全透明的图片返回空数组
+pub fn extract(img: &DynamicImage, max_colors: usize) -> Vec<Swatch> {
+    let sample = if img.width() > SAMPLE_SIZE || img.height() > SAMPLE_SIZE {
+        img.resize(SAMPLE_SIZE, SAMPLE_SIZE, FilterType::Nearest)
+    } else {
+        img.clone()
+    };
+    let pixels: Vec<[u8; 3]> = sample
+        .to_rgba8()
+        .pixels()
+        .filter(|p| p[3] >= MIN_ALPHA)
+        .map(|p| [p[0], p[1], p[2]])
+        .collect();
+    if pixels.is_empty() || max_colors == 0 {
+        return Vec::new();
+    }
+    let total = pixels.len() as f64;
+
+    // 每次切分跨度最大的盒子，直到数量足够或无法再分
+    let mut boxes = vec![ColorBox { pixels }];
+    while boxes.len() < max_colors {
+        let Some(index) = boxes
+            .iter()
+            .enumerate()
+            .filter(|(_, b)| b.pixels.len() > 1 && b.widest_channel().1 > 0)
+            .max_by_key(|(_, b)| (b.widest_channel().1 as usize) * b.pixels.len())
+            .map(|(i, _)| i)
+        else {
+            break;
+        };
+        let (lower, upper) = boxes.swap_remove(index).split();
+        boxes.push(lower);
+        boxes.push(upper);
+    }
+
+    // 合并相近的颜色，保留像素更多的那个
+    let mut merged: Vec<([u8; 3], usize)> = Vec::new();
+    let mut averaged: Vec<([u8; 3], usize)> = boxes.iter().map(|b| (b.average(), b.pixels.len())).collect();
+    averaged.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+    for (color, count) in averaged {
+        let similar = merged.iter_mut().find(|(c, _)| {
+            (0..3).all(|i| c[i].abs_diff(color[i]) <= MERGE_DISTANCE)
+        });
+        match similar {
+            Some((_, n)) => *n += count,
+            None => merged.push((color, count)),
+        }
+    }
+    merged.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+
+    merged
+        .into_iter()
+        .filter(|&(_, count)| count as f64 / total >= MIN_RATIO)
+        .map(|([r, g, b], count)| Swatch {
+            color: Rgba::from_rgb8(r, g, b, 1.0).to_hex(),
+            ratio: count as f64 / total,
+        })
+        .collect()
+}
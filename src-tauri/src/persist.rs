@@ -1,12 +1,31 @@
 use anyhow::{Context, Result};
-use chrono::Utc;
+use chrono::{TimeDelta, Utc};
 use rusqlite::{params, Connection, OptionalExtension, Transaction};
 use rusqlite_migration::{Migrations, M};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use image::GenericImageView;
 
+use crate::barcode::CodeDecoder;
+use crate::classify::{Classifier, Detector};
+use crate::conceal::{EphemeralClip, EphemeralClips};
+use crate::crypto::{self, Cipher, StoreKey};
+use crate::color::Rgba;
+use crate::ocr::OcrEngine;
+use crate::palette::Swatch;
+use crate::secret::SecretKind;
+use crate::setting::Settings;
+use crate::source_app::SourceApp;
+use crate::vault::{VaultItem, VaultSession, VERIFIER_PLAINTEXT};
+
+/// 外置文本在 content_text 中保留的前缀长度 (字节)，用于搜索和预览
+pub const TEXT_PREFIX_BYTES: usize = 64 * 1024;
+
+/// 未加密 SQLite 数据库的文件头
+const SQLITE_HEADER: &[u8; 16] = b"SQLite format 3\0";
+
 
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -16,6 +35,8 @@ pub enum ClipType {
     Html,
     Files,
     Color,
+    Rtf,
+    Custom,
 }
 impl ToString for ClipType {
     fn to_string(&self) -> String {
@@ -25,6 +46,8 @@ impl ToString for ClipType {
             ClipType::Image => "image".to_string(),
             ClipType::Files => "files".to_string(),
             ClipType::Color => "color".to_string(),
+            ClipType::Rtf => "rtf".to_string(),
+            ClipType::Custom => "custom".to_string(),
         }
     }
 }
@@ -36,6 +59,8 @@ impl From<String> for ClipType {
             "image" => ClipType::Image,
             "files" => ClipType::Files,
             "color" => ClipType::Color,
+            "rtf" => ClipType::Rtf,
+            "custom" => ClipType::Custom,
             _ => ClipType::Text,
         }
     }
@@ -50,6 +75,15 @@ pub struct ClipItem {
     pub created_at: i64,
     pub is_pinned: bool,
     pub tags: Vec<String>,  // 标签数组：["color", "favorite"], ["image", "work"] 等
+    pub language: Option<String>, // 代码片段的编程语言
+    pub blurhash: Option<String>, // 图片占位：BlurHash
+    pub average_color: Option<String>, // 图片占位：平均色
+    pub source_id: Option<i64>, // 派生记录的来源 (如二维码内容来自哪张图片)
+    pub source_app: Option<SourceApp>, // 复制时的前台应用
+    pub is_sensitive: bool, // 敏感内容：预览已遮盖，不参与搜索
+    pub sensitive_kind: Option<String>, // 敏感内容类型，如 "aws_key"
+    pub expires_at: Option<i64>, // 到期自动删除的时间 (微秒时间戳)
+    pub delete_after_use: bool, // 一次性记录：粘贴回剪贴板后自动删除
 }
 
 
@@ -60,40 +94,459 @@ pub enum ClipData {
     Image(Vec<u8>),
     Files(Vec<String>), // 文件路径列表
     Color(String),      // 颜色值（保存原始格式）
+    Rtf { text: String, rtf: String }, // RTF 源码 + 提取出的纯文本
+    Custom { mime: String, data: Vec<u8> }, // 应用私有格式，原样保存
+}
+
+/// 文本分段读取的结果
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TextChunk {
+    pub text: String,
+    pub offset: usize,      // 本段起始字节偏移 (已对齐到字符边界)
+    pub next_offset: usize, // 下一段的起始偏移
+    pub total_size: usize,  // 完整文本的字节数
+    pub eof: bool,
+}
+
+/// 附加表示的 MIME 类型
+pub const MIME_TEXT: &str = "text/plain";
+pub const MIME_HTML: &str = "text/html";
+pub const MIME_PNG: &str = "image/png";
+pub const MIME_FILES: &str = "text/uri-list"; // 内容为换行分隔的本地路径
+pub const MIME_RTF: &str = "text/rtf";
+pub const MIME_MARKDOWN: &str = "text/markdown"; // 由 HTML 转换得到
+pub const MIME_SVG: &str = "image/svg+xml";
+
+/// 记录的一种附加表示（同一次复制事件中的其它格式）
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ClipPart {
+    pub mime: String,
+    pub text: Option<String>,   // 文本类表示
+    pub data: Option<Vec<u8>>,  // 二进制表示
+}
+
+impl ClipPart {
+    pub fn text(mime: &str, text: String) -> Self {
+        Self { mime: mime.to_string(), text: Some(text), data: None }
+    }
+
+    pub fn binary(mime: &str, data: Vec<u8>) -> Self {
+        Self { mime: mime.to_string(), text: None, data: Some(data) }
+    }
+}
+
+/// 列表 / 搜索的过滤条件，未设置的字段不参与过滤
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClipFilter {
+    pub tag: Option<String>, // 只返回带有该标签的记录
+    pub source_id: Option<i64>, // 只返回由该记录派生的记录
+    pub app: Option<String>, // 只返回来自该应用的记录 (应用名，不区分大小写)
+}
+
+impl ClipFilter {
+    pub fn tag(tag: &str) -> Self {
+        Self { tag: Some(tag.to_string()), ..Self::default() }
+    }
+
+    pub fn derived_from(source_id: i64) -> Self {
+        Self { source_id: Some(source_id), ..Self::default() }
+    }
+
+    pub fn app(app: &str) -> Self {
+        Self { app: Some(app.to_string()), ..Self::default() }
+    }
+
+    /// 生成 SQL 条件和对应的参数 (使用匿名 ? 占位符)
+    fn to_sql(&self) -> (String, Vec<rusqlite::types::Value>) {
+        // 保险库中的记录只通过 vault_items 列出
+        let mut clauses = vec!["records.in_vault = 0".to_string()];
+        let mut values = Vec::new();
+        if let Some(tag) = &self.tag {
+            clauses.push("EXISTS (SELECT 1 FROM json_each(records.tag) WHERE value = ?)".to_string());
+            values.push(rusqlite::types::Value::Text(tag.clone()));
+        }
+        if let Some(source_id) = self.source_id {
+            clauses.push("records.source_id = ?".to_string());
+            values.push(rusqlite::types::Value::Integer(source_id));
+        }
+        if let Some(app) = &self.app {
+            clauses.push("records.app_context = ? COLLATE NOCASE".to_string());
+            values.push(rusqlite::types::Value::Text(app.clone()));
+        }
+        (clauses.join(" AND "), values)
+    }
+}
+
+/// 图片原图的路径；加密存储时为解密后的临时文件，离开作用域时删除
+pub struct ImageFile {
+    path: PathBuf,
+    temporary: bool,
+}
+
+impl std::ops::Deref for ImageFile {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl AsRef<Path> for ImageFile {
+    fn as_ref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for ImageFile {
+    fn drop(&mut self) {
+        if self.temporary {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
 }
 
 pub struct Storage {
+    // 锁定期间是一个空的内存数据库
     conn: Connection,
+    data_dir: PathBuf,
+    db_path: PathBuf,
     image_dir: PathBuf,
+    blob_dir: PathBuf,
+    text_dir: PathBuf,
+    scratch_dir: PathBuf, // 加密存储时解密给外部工具读取的临时文件
+    settings: Settings,
+    classifier: Classifier,
+    ocr: Option<Arc<dyn OcrEngine>>,
+    decoder: Option<Arc<dyn CodeDecoder>>,
+    ephemeral: EphemeralClips,
+    cipher: Option<Arc<dyn Cipher>>,
+    locked: bool,
+    vault: VaultSession,
 }
 
 impl Storage {
     pub fn new<P: AsRef<Path>>(data_dir: P) -> Result<Self> {
         let data_dir = data_dir.as_ref().to_path_buf();
         let image_dir = data_dir.join("images");
+        let blob_dir = data_dir.join("blobs");
+        let text_dir = data_dir.join("texts");
+        let scratch_dir = data_dir.join("tmp");
         let db_path = data_dir.join("clippy.db");
 
         if !image_dir.exists() {
             fs::create_dir_all(&image_dir).context("Failed to create image dir")?;
         }
+        if !blob_dir.exists() {
+            fs::create_dir_all(&blob_dir).context("Failed to create blob dir")?;
+        }
+        if !text_dir.exists() {
+            fs::create_dir_all(&text_dir).context("Failed to create text dir")?;
+        }
+        Self::reset_scratch_dir(&scratch_dir)?;
+
+        // 加密数据库在解锁前不打开
+        let locked = Self::is_encrypted_db(&db_path);
+        let conn = if locked {
+            println!("🔒 数据库已加密，等待解锁");
+            Self::open_placeholder()?
+        } else {
+            Self::open_connection(&db_path, None)?
+        };
 
-        let mut conn = Connection::open(&db_path).context("Failed to open DB")?;
+        let settings = Settings::load(&data_dir);
+
+        Ok(Self {
+            conn, data_dir, db_path, image_dir, blob_dir, text_dir, scratch_dir, settings,
+            classifier: Classifier::default(), ocr: None, decoder: None, ephemeral: EphemeralClips::default(),
+            cipher: None, locked, vault: VaultSession::default(),
+        })
+    }
+
+    /// 打开数据库并迁移到最新版本；加密数据库需要提供密钥
+    fn open_connection(db_path: &Path, cipher: Option<&dyn Cipher>) -> Result<Connection> {
+        let mut conn = Connection::open(db_path).context("Failed to open DB")?;
+
+        if let Some(cipher) = cipher {
+            // 设置密钥必须是打开后的第一条语句
+            conn.execute_batch(&format!("PRAGMA key = \"{}\";", cipher.sql_key()))?;
+            // 密钥错误时第一次读取就会失败 (file is not a database)
+            conn.query_row("SELECT count(*) FROM sqlite_master", [], |row| row.get::<_, i64>(0))
+                .map_err(|_| anyhow::anyhow!("口令或密钥错误，无法解密数据库"))?;
+        }
         
         // 性能调优
         conn.pragma_update(None, "journal_mode", "WAL")?;
         conn.pragma_update(None, "synchronous", "NORMAL")?;
+        // record_parts 依赖外键级联删除
+        conn.pragma_update(None, "foreign_keys", "ON")?;
+        // 删除和清空的内容 (如移入保险库的明文) 用零覆盖，不残留在空闲页中
+        conn.pragma_update(None, "secure_delete", "ON")?;
 
         Self::migrate(&mut conn)?;
+        Ok(conn)
+    }
+
+    /// 清空并重建临时文件目录 (只有当前用户可访问)，删除上次异常退出时残留的解密文件
+    fn reset_scratch_dir(dir: &Path) -> Result<()> {
+        if dir.exists() {
+            fs::remove_dir_all(dir).context("Failed to clean scratch dir")?;
+        }
+        let mut builder = fs::DirBuilder::new();
+        #[cfg(unix)]
+        std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+        builder.create(dir).context("Failed to create scratch dir")?;
+        Ok(())
+    }
+
+    /// 锁定期间使用的空内存数据库，查询都返回空结果
+    fn open_placeholder() -> Result<Connection> {
+        let mut conn = Connection::open_in_memory()?;
+        conn.pragma_update(None, "foreign_keys", "ON")?;
+        Self::migrate(&mut conn)?;
+        Ok(conn)
+    }
+
+    /// 文件头不是明文 SQLite 格式即视为已加密
+    fn is_encrypted_db(db_path: &Path) -> bool {
+        use std::io::Read;
+
+        let mut header = [0u8; 16];
+        match fs::File::open(db_path) {
+            Ok(mut file) => file.read_exact(&mut header).is_ok() && &header != SQLITE_HEADER,
+            Err(_) => false,
+        }
+    }
+
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    /// 注册自定义内容检测器，之后保存的文本会带上它的标签
+    pub fn register_detector(&mut self, detector: Box<dyn Detector>) {
+        self.classifier.register(detector);
+    }
+
+    /// 设置 OCR 引擎，之后保存的图片会在后台识别文字
+    pub fn set_ocr_engine(&mut self, engine: Arc<dyn OcrEngine>) {
+        self.ocr = Some(engine);
+    }
+
+    /// 当前可用的 OCR 引擎，设置中关闭 OCR 时返回 None
+    pub fn ocr_engine(&self) -> Option<Arc<dyn OcrEngine>> {
+        self.ocr.clone().filter(|_| self.settings.ocr_enabled)
+    }
+
+    /// 设置二维码 / 条码识别器
+    pub fn set_code_decoder(&mut self, decoder: Arc<dyn CodeDecoder>) {
+        self.decoder = Some(decoder);
+    }
+
+    /// 当前可用的识别器，设置中关闭识别时返回 None
+    pub fn code_decoder(&self) -> Option<Arc<dyn CodeDecoder>> {
+        self.decoder.clone().filter(|_| self.settings.barcode_enabled)
+    }
+
+    /// 在内存中暂存带隐藏标记的文本，按设置的时长过期，不写入数据库
+    pub fn add_ephemeral(&mut self, text: String, source_app: Option<SourceApp>) -> Result<u64> {
+        let expires_at = Self::expires_after_secs(self.settings.ephemeral_ttl_secs)?;
+        Ok(self.ephemeral.push(text, source_app, expires_at))
+    }
+
+    /// 在内存中保存隐身模式下复制的文本，退出隐身模式时丢弃
+    pub fn add_session_clip(&mut self, text: String, source_app: Option<SourceApp>) -> u64 {
+        self.ephemeral.push_session(text, source_app)
+    }
+
+    /// 丢弃隐身模式下的记录，返回它们的 ID
+    pub fn clear_session_clips(&mut self) -> Vec<u64> {
+        self.ephemeral.clear_session()
+    }
+
+    pub fn ephemeral_clips(&mut self) -> Vec<EphemeralClip> {
+        self.ephemeral.list()
+    }
+
+    pub fn get_ephemeral(&mut self, id: u64) -> Option<String> {
+        self.ephemeral.get(id)
+    }
+
+    /// 丢弃过期的临时记录，返回它们的 ID
+    pub fn purge_ephemeral(&mut self) -> Vec<u64> {
+        self.ephemeral.purge_expired()
+    }
+
+    /// 更新设置并写入 settings.json
+    pub fn update_settings(&mut self, settings: Settings) -> Result<()> {
+        settings.save(&self.data_dir)?;
+        self.settings = settings;
+        Ok(())
+    }
+
+    pub fn data_dir(&self) -> &Path {
+        &self.data_dir
+    }
+
+    /// 是否启用了静态加密 (无论是否已解锁)
+    pub fn is_encrypted(&self) -> bool {
+        self.locked || self.cipher.is_some()
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    /// 用密钥打开加密数据库；密钥错误时保持锁定
+    pub fn unlock_store(&mut self, cipher: Arc<dyn Cipher>) -> Result<()> {
+        if !self.locked {
+            return Ok(());
+        }
+        self.conn = Self::open_connection(&self.db_path, Some(cipher.as_ref()))?;
+        self.cipher = Some(cipher);
+        self.locked = false;
+        // 保险库密钥只对应真实数据库中的口令
+        self.vault.lock();
+        println!("🔓 存储已解锁");
+        Ok(())
+    }
+
+    /// 关闭加密数据库并丢弃密钥，之后的查询返回空结果，新内容不会写入
+    pub fn lock_store(&mut self) -> Result<()> {
+        if self.cipher.is_none() {
+            return Err(anyhow::anyhow!("存储未加密或已锁定"));
+        }
+        self.conn = Self::open_placeholder()?;
+        self.cipher = None;
+        self.locked = true;
+        self.vault.lock();
+        println!("🔒 存储已锁定");
+        Ok(())
+    }
 
-        Ok(Self { conn, image_dir })
+    /// 就地加密明文存储：用 sqlcipher_export 导出加密副本替换数据库，再逐个加密图片、blob 和外置文本
+    ///
+    /// 已加密的部分会跳过，迁移中断后解锁再次调用即可继续。旧的明文数据可能仍残留在磁盘空闲块中
+    pub fn encrypt(&mut self, cipher: Arc<dyn Cipher>) -> Result<()> {
+        if self.locked {
+            return Err(anyhow::anyhow!("存储已锁定"));
+        }
+        if self.cipher.is_none() {
+            self.export_encrypted(cipher.as_ref())?;
+            self.cipher = Some(cipher);
+        }
+
+        let mut sealed = 0;
+        for dir in [&self.image_dir, &self.blob_dir, &self.text_dir] {
+            sealed += self.seal_files(dir)?;
+        }
+        println!("🔐 存储已加密，加密了 {} 个文件", sealed);
+        Ok(())
+    }
+
+    fn export_encrypted(&mut self, cipher: &dyn Cipher) -> Result<()> {
+        let encrypted_path = self.db_path.with_extension("db.encrypting");
+        let _ = fs::remove_file(&encrypted_path);
+
+        // sqlcipher_export 不复制 user_version，迁移版本需要手动带过去
+        let version: i64 = self.conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+        self.conn.execute_batch(&format!(
+            "ATTACH DATABASE '{}' AS encrypted KEY \"{}\";
+             SELECT sqlcipher_export('encrypted');
+             PRAGMA encrypted.user_version = {};
+             DETACH DATABASE encrypted;",
+            encrypted_path.to_string_lossy().replace('\'', "''"),
+            cipher.sql_key(),
+            version,
+        )).context("Failed to export encrypted database")?;
+
+        // 关闭明文连接后替换文件，残留的 WAL 也是明文
+        self.conn = Self::open_placeholder()?;
+        fs::rename(&encrypted_path, &self.db_path).context("Failed to replace database")?;
+        for suffix in ["db-wal", "db-shm"] {
+            let _ = fs::remove_file(self.db_path.with_extension(suffix));
+        }
+        self.conn = Self::open_connection(&self.db_path, Some(cipher))?;
+        Ok(())
+    }
+
+    /// 加密目录下尚未加密的文件，返回加密的数量
+    fn seal_files(&self, dir: &Path) -> Result<usize> {
+        let mut sealed = 0;
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                sealed += self.seal_files(&path)?;
+                continue;
+            }
+            let data = fs::read(&path)?;
+            if crypto::is_sealed(&data) {
+                continue;
+            }
+            // 先写临时文件再替换，中断时不会留下半截文件
+            let tmp_path = path.with_extension("sealing");
+            self.write_file(&tmp_path, &data)?;
+            fs::rename(&tmp_path, &path)?;
+            sealed += 1;
+        }
+        Ok(sealed)
+    }
+
+    /// 写入数据目录下的文件，加密存储时先加密
+    fn write_file(&self, path: &Path, data: &[u8]) -> Result<()> {
+        if self.locked {
+            return Err(anyhow::anyhow!("存储已锁定"));
+        }
+        match &self.cipher {
+            Some(cipher) => fs::write(path, cipher.seal(data)?)?,
+            None => fs::write(path, data)?,
+        }
+        Ok(())
+    }
+
+    /// 读取数据目录下的文件；迁移中断时留下的明文文件原样返回
+    fn read_file(&self, path: &Path) -> Result<Vec<u8>> {
+        let data = fs::read(path)?;
+        if !crypto::is_sealed(&data) {
+            return Ok(data);
+        }
+        let cipher = self.cipher.as_ref().ok_or_else(|| anyhow::anyhow!("存储已锁定"))?;
+        cipher.open(&data)
     }
 
     fn migrate(conn: &mut Connection) -> Result<()> {
         // SQL 迁移脚本从外部文件 migrations/*.sql 静态加载
         let schema_sql = include_str!("../migrations/001_schema_init.sql");
+        let parts_sql = include_str!("../migrations/002_record_parts.sql");
+        let blobs_sql = include_str!("../migrations/003_part_blobs.sql");
+        let external_text_sql = include_str!("../migrations/004_external_text.sql");
+        let language_sql = include_str!("../migrations/005_code_language.sql");
+        let color_sql = include_str!("../migrations/006_color_value.sql");
+        let palette_sql = include_str!("../migrations/007_image_palette.sql");
+        let placeholder_sql = include_str!("../migrations/008_image_placeholder.sql");
+        let ocr_sql = include_str!("../migrations/009_ocr_text.sql");
+        let derived_sql = include_str!("../migrations/010_derived_clips.sql");
+        let source_app_sql = include_str!("../migrations/011_source_app.sql");
+        let sensitive_sql = include_str!("../migrations/012_sensitive_clips.sql");
+        let expiry_sql = include_str!("../migrations/013_clip_expiry.sql");
+        let vault_sql = include_str!("../migrations/014_vault.sql");
+        let expiry_source_sql = include_str!("../migrations/015_expiry_source.sql");
         
         let migrations = Migrations::new(vec![
             M::up(schema_sql),
+            M::up(parts_sql),
+            M::up(blobs_sql),
+            M::up(external_text_sql),
+            M::up(language_sql),
+            M::up(color_sql),
+            M::up(palette_sql),
+            M::up(placeholder_sql),
+            M::up(ocr_sql),
+            M::up(derived_sql),
+            M::up(source_app_sql),
+            M::up(sensitive_sql),
+            M::up(expiry_sql),
+            M::up(vault_sql),
+            M::up(expiry_source_sql),
         ]);
         migrations.to_latest(conn)?;
         Ok(())
@@ -102,30 +555,124 @@ impl Storage {
 
     /// 1. 存纯文本
     pub fn add_text(&mut self, text: String) -> Result<i64> {
-        let text = text.trim().to_string();
-        if text.is_empty() { return Ok(0); }
-        let hash = Self::compute_hash(text.as_bytes());
+        // 原文按原样保存，去重和颜色识别使用规范化后的键
+        if text.trim().is_empty() { return Ok(0); }
+        // data URI / base64 形式的图片按图片保存
+        if let Some(id) = self.add_encoded_image(&text)? { return Ok(id); }
+        let key = self.settings.text_normalization.apply(&text).into_owned();
+        let hash = Self::compute_hash(key.as_bytes());
 
-        // 检测是否为颜色值，设置 tags 数组
-        let (clip_type, tags) = if Self::is_color(&text) {
+        // 检测是否为颜色值，设置 tags 数组；普通文本再附加智能标签
+        let color = crate::color::parse(&key);
+        let mut secret = None;
+        let (clip_type, tags) = if color.is_some() {
             (ClipType::Color, vec!["color".to_string()])
         } else {
-            (ClipType::Text, vec!["text".to_string()])
+            let mut tags = vec!["text".to_string()];
+            // 超大文本只检测前缀
+            let prefix = Self::truncate_at_char_boundary(&key, TEXT_PREFIX_BYTES);
+            tags.extend(self.classifier.classify(prefix));
+            secret = self.detect_secret(prefix);
+            (ClipType::Text, tags)
         };
+        let expires_at = Self::expires_after_secs(self.settings.sensitive_ttl_secs)?;
+
+        // 超大文本压缩外置，数据库只保留前缀
+        let external = if text.len() > self.settings.large_text_threshold {
+            Some(self.write_external_text(&Self::compute_hash(text.as_bytes()), &text)?)
+        } else {
+            None
+        };
+        let stored_text = match external {
+            Some(_) => Self::truncate_at_char_boundary(&text, TEXT_PREFIX_BYTES),
+            None => &text,
+        };
+        let language = Self::detect_language(&tags, &key);
+        let old_path = self.find_id_by_hash(&hash)?
+            .map(|id| self.external_text_path(id))
+            .transpose()?
+            .flatten();
 
         let tx = self.conn.transaction()?;
         let id = Self::upsert_record(&tx, clip_type, &hash, &tags, |sql, params| {
              tx.execute(sql, params)
-        }, Some(&text), None, None, None)?;
+        }, Some(stored_text), None, None, None)?;
+        // 规范化后相同的文本只保留一条记录，内容以最近一次复制的原文为准
+        tx.execute(
+            "UPDATE records SET content_text = ?1, content_path = ?2, content_size = ?3, language = ?4,
+             color_rgba = ?5
+             WHERE id = ?6",
+            params![
+                stored_text,
+                external,
+                external.as_ref().map(|_| text.len() as i64),
+                language,
+                color.map(|c| c.to_hex8()),
+                id
+            ],
+        )?;
+        Self::update_sensitivity(&tx, id, secret, expires_at)?;
         tx.commit()?;
+
+        if let Some(old) = old_path.filter(|old| Some(old) != external.as_ref()) {
+            self.remove_text_files(&[old]);
+        }
         Ok(id)
     }
 
+    /// 检测文本中的密钥和卡号 (超大文本只检测前缀)，设置中关闭检测时返回 None
+    fn detect_secret(&self, text: &str) -> Option<SecretKind> {
+        if !self.settings.secret_detection {
+            return None;
+        }
+        crate::secret::detect(Self::truncate_at_char_boundary(text, TEXT_PREFIX_BYTES))
+    }
+
+    /// 按检测结果更新敏感标记：以用户手动指定为准，敏感记录每次复制都重新计算到期时间
+    ///
+    /// 用户设置的到期时间 (expiry_auto = 0) 保持不变，不再敏感时只清除自动设置的到期时间
+    fn update_sensitivity(conn: &Connection, id: i64, secret: Option<SecretKind>, expires_at: i64) -> Result<()> {
+        conn.execute(
+            "UPDATE records SET sensitive_kind = COALESCE(?1, sensitive_kind),
+             is_sensitive = COALESCE(sensitive_override, ?2),
+             expires_at = CASE WHEN expiry_auto = 0 AND expires_at IS NOT NULL THEN expires_at
+                               WHEN COALESCE(sensitive_override, ?2) = 1 THEN ?3
+                               ELSE NULL END,
+             expiry_auto = CASE WHEN expiry_auto = 0 AND expires_at IS NOT NULL THEN 0
+                                ELSE COALESCE(sensitive_override, ?2) END
+             WHERE id = ?4",
+            params![secret.map(|kind| kind.as_str()), secret.is_some(), expires_at, id],
+        )?;
+        Ok(())
+    }
+
+    /// 1.1 文本能解码为图片时保存为图片记录，原文作为纯文本附加表示保留
+    fn add_encoded_image(&mut self, text: &str) -> Result<Option<i64>> {
+        use crate::datauri::EncodedImage;
+
+        let id = match crate::datauri::decode_image(text) {
+            Some(EncodedImage::Raster(bytes)) => {
+                let Ok(img) = image::load_from_memory(&bytes) else { return Ok(None) };
+                let img = img.to_rgba8();
+                let (width, height) = img.dimensions();
+                self.add_image(width as usize, height as usize, img.into_raw())?.0
+            }
+            Some(EncodedImage::Svg(markup)) => match self.add_svg(markup) {
+                Ok((id, _)) => id,
+                Err(_) => return Ok(None),
+            },
+            None => return Ok(None),
+        };
+        println!("🖼️ 文本解码为图片，记录 ID: {}", id);
+        self.add_parts(id, &[ClipPart::text(MIME_TEXT, text.to_string())])?;
+        Ok(Some(id))
+    }
+
     /// 2. 存 HTML (同时存纯文本用于搜索)
     pub fn add_html(&mut self, text_preview: String, html_content: String) -> Result<i64> {
         // 检测 text_preview 是否为颜色值，如果是则保存为 Color 类型
         let text_trimmed = text_preview.trim();
-        if Self::is_color(text_trimmed) {
+        if crate::color::is_color(text_trimmed) {
             // 直接保存为颜色
             return self.add_text(text_trimmed.to_string());
         }
@@ -133,11 +680,62 @@ impl Storage {
         // HTML 的指纹计算：建议用 html 内容算，或者 text+html 混合算
         let hash = Self::compute_hash(html_content.as_bytes());
         
+        let mut tags = vec!["html".to_string()];
+        tags.extend(self.classifier.classify(&text_preview));
+        let language = Self::detect_language(&tags, &text_preview);
+        let secret = self.detect_secret(&text_preview);
+        let expires_at = Self::expires_after_secs(self.settings.sensitive_ttl_secs)?;
+
         let tx = self.conn.transaction()?;
-        let id = Self::upsert_record(&tx, ClipType::Html, &hash, &vec!["html".to_string()], |sql, params| {
+        let id = Self::upsert_record(&tx, ClipType::Html, &hash, &tags, |sql, params| {
              tx.execute(sql, params)
         }, Some(&text_preview), Some(&html_content), None, None)?;
+        tx.execute("UPDATE records SET language = ?1 WHERE id = ?2", params![language, id])?;
+        Self::update_sensitivity(&tx, id, secret, expires_at)?;
         tx.commit()?;
+
+        // Markdown 版本用于“粘贴为 Markdown”和搜索
+        let markdown = crate::markdown::from_html(&html_content);
+        if !markdown.is_empty() {
+            self.add_parts(id, &[ClipPart::text(MIME_MARKDOWN, markdown)])?;
+        }
+        Ok(id)
+    }
+
+    /// 2.1 存 RTF (提取纯文本用于搜索，RTF 源码作为附加表示保存)
+    pub fn add_rtf(&mut self, rtf: String) -> Result<i64> {
+        let text = crate::rtf::to_plain_text(&rtf);
+        let hash = Self::compute_hash(rtf.as_bytes());
+        let secret = self.detect_secret(&text);
+        let expires_at = Self::expires_after_secs(self.settings.sensitive_ttl_secs)?;
+
+        let tx = self.conn.transaction()?;
+        let id = Self::upsert_record(&tx, ClipType::Rtf, &hash, &["rtf".to_string()], |sql, params| {
+             tx.execute(sql, params)
+        }, Some(&text), None, None, None)?;
+        Self::update_sensitivity(&tx, id, secret, expires_at)?;
+        tx.commit()?;
+
+        self.add_parts(id, &[ClipPart::text(MIME_RTF, rtf)])?;
+        Ok(id)
+    }
+
+    /// 2.2 存应用私有格式 (原始字节保存在 blobs 目录)
+    pub fn add_custom(&mut self, mime: String, data: Vec<u8>) -> Result<i64> {
+        if data.is_empty() { return Ok(0); }
+
+        let mut fingerprint = mime.as_bytes().to_vec();
+        fingerprint.extend_from_slice(&data);
+        let hash = Self::compute_hash(&fingerprint);
+        let description = format!("[{}] {} bytes", mime, data.len());
+
+        let tx = self.conn.transaction()?;
+        let id = Self::upsert_record(&tx, ClipType::Custom, &hash, &["custom".to_string()], |sql, params| {
+             tx.execute(sql, params)
+        }, Some(&description), None, None, None)?;
+        tx.commit()?;
+
+        self.add_parts(id, &[ClipPart::binary(&mime, data)])?;
         Ok(id)
     }
 
@@ -165,17 +763,28 @@ impl Storage {
 
     /// 获取列表
     pub fn get_recent(&self, limit: usize, offset: usize) -> Result<Vec<ClipItem>> {
-        println!("🔍 查询最近记录: limit={}, offset={}", limit, offset);
+        self.get_recent_filtered(limit, offset, &ClipFilter::default())
+    }
+
+    /// 按条件获取列表
+    pub fn get_recent_filtered(&self, limit: usize, offset: usize, filter: &ClipFilter) -> Result<Vec<ClipItem>> {
+        println!("🔍 查询最近记录: limit={}, offset={}, filter={:?}", limit, offset, filter);
         
-        let mut stmt = self.conn.prepare(
+        let (where_clause, mut values) = filter.to_sql();
+        values.push(rusqlite::types::Value::Integer(limit as i64));
+        values.push(rusqlite::types::Value::Integer(offset as i64));
+        let mut stmt = self.conn.prepare(&format!(
             "SELECT id, type, content_text, content_file_paths, created_at, is_pinned, tag,
-             image_format, width, height
+             image_format, width, height, language, blurhash, average_color, source_id,
+             app_context, app_title, app_path, is_sensitive, sensitive_kind, expires_at, delete_after_use
              FROM records 
+             WHERE {}
              ORDER BY is_pinned DESC, created_at DESC 
-             LIMIT ?1 OFFSET ?2"
-        )?;
+             LIMIT ? OFFSET ?",
+            where_clause
+        ))?;
 
-        let rows = stmt.query_map(params![limit, offset], |row| {
+        let rows = stmt.query_map(rusqlite::params_from_iter(values), |row| {
             let id: i64 = row.get(0)?;
             let type_str: String = row.get(1)?;
             let text: Option<String> = row.get(2)?;
@@ -186,6 +795,15 @@ impl Storage {
             let image_format: Option<String> = row.get(7)?;
             let width: Option<i64> = row.get(8)?;
             let height: Option<i64> = row.get(9)?;
+            let language: Option<String> = row.get(10)?;
+            let blurhash: Option<String> = row.get(11)?;
+            let average_color: Option<String> = row.get(12)?;
+            let source_id: Option<i64> = row.get(13)?;
+            let source_app = Self::source_app_from_columns(row.get(14)?, row.get(15)?, row.get(16)?);
+            let is_sensitive: bool = row.get(17)?;
+            let sensitive_kind: Option<String> = row.get(18)?;
+            let expires_at: Option<i64> = row.get(19)?;
+            let delete_after_use: bool = row.get(20)?;
 
             let content_type = ClipType::from(type_str);
             
@@ -196,13 +814,14 @@ impl Storage {
                 vec!["text".to_string()]
             };
             
-            // 生成 UI 预览文字
+            // 生成 UI 预览文字，敏感内容只显示类型
             let preview = match content_type {
-                ClipType::Text | ClipType::Html => {
+                _ if is_sensitive => crate::secret::mask(sensitive_kind.as_deref(), &text.unwrap_or_default()),
+                ClipType::Text | ClipType::Html | ClipType::Rtf => {
                     text.unwrap_or_default().chars().take(100).collect::<String>().replace('\n', " ")
                 },
-                ClipType::Color => {
-                    // 颜色直接显示值
+                ClipType::Color | ClipType::Custom => {
+                    // 颜色直接显示值，私有格式显示类型和大小
                     text.unwrap_or_default()
                 },
                 ClipType::Image => {
@@ -234,6 +853,15 @@ impl Storage {
                 created_at,
                 is_pinned,
                 tags,
+                language,
+                blurhash,
+                average_color,
+                source_id,
+                source_app,
+                is_sensitive,
+                sensitive_kind,
+                expires_at,
+                delete_after_use,
             })
         })?;
 
@@ -246,7 +874,7 @@ impl Storage {
     /// 获取总记录数
     pub fn get_total_count(&self) -> Result<i64> {
         let count: i64 = self.conn.query_row(
-            "SELECT COUNT(*) FROM records",
+            "SELECT COUNT(*) FROM records WHERE in_vault = 0",
             [],
             |row| row.get(0)
         )?;
@@ -254,18 +882,42 @@ impl Storage {
         Ok(count)
     }
 
-    /// 搜索 (所有类型都通过 content_text 搜索)
+    /// 搜索 (所有类型都通过 content_text 搜索，查询词与标签完全一致时也会命中)
     pub fn search(&self, query: &str) -> Result<Vec<ClipItem>> {
+        self.search_filtered(query, &ClipFilter::default())
+    }
+
+    /// 按条件搜索，敏感记录不参与搜索
+    pub fn search_filtered(&self, query: &str, filter: &ClipFilter) -> Result<Vec<ClipItem>> {
         // 使用 LIKE 查询支持中文和模糊匹配
         let like_query = format!("%{}%", query.replace('%', "\\%").replace('_', "\\_"));
-        let mut stmt = self.conn.prepare(
-            "SELECT id, type, content_text, content_file_paths, created_at, is_pinned, tag 
+        let tag_query = query.trim().to_lowercase();
+        let (where_clause, filter_values) = filter.to_sql();
+        let mut values = vec![
+            rusqlite::types::Value::Text(like_query.clone()),
+            rusqlite::types::Value::Text(like_query.clone()),
+            rusqlite::types::Value::Text(like_query),
+            rusqlite::types::Value::Text(tag_query),
+        ];
+        values.extend(filter_values);
+
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT id, type, content_text, content_file_paths, created_at, is_pinned, tag, language,
+             blurhash, average_color, source_id, app_context, app_title, app_path, expires_at, delete_after_use
              FROM records 
-             WHERE content_text LIKE ?1 ESCAPE '\\'
-             ORDER BY created_at DESC LIMIT 50"
-        )?;
+             WHERE is_sensitive = 0
+               AND (content_text LIKE ? ESCAPE '\\'
+                OR ocr_text LIKE ? ESCAPE '\\'
+                OR id IN (SELECT record_id FROM record_parts
+                          WHERE mime IN ('text/plain', 'text/markdown')
+                            AND content_text LIKE ? ESCAPE '\\')
+                OR EXISTS (SELECT 1 FROM json_each(records.tag) WHERE value = ?))
+               AND {}
+             ORDER BY created_at DESC LIMIT 50",
+            where_clause
+        ))?;
         
-        let rows = stmt.query_map(params![like_query], |row| {
+        let rows = stmt.query_map(rusqlite::params_from_iter(values), |row| {
              // 复制上面的 row mapping 逻辑
              let id: i64 = row.get(0)?;
              let type_str: String = row.get(1)?;
@@ -274,6 +926,13 @@ impl Storage {
              let created_at: i64 = row.get(4)?;
              let is_pinned: bool = row.get(5)?;
              let tags_json: Option<String> = row.get(6)?;
+             let language: Option<String> = row.get(7)?;
+             let blurhash: Option<String> = row.get(8)?;
+             let average_color: Option<String> = row.get(9)?;
+             let source_id: Option<i64> = row.get(10)?;
+             let source_app = Self::source_app_from_columns(row.get(11)?, row.get(12)?, row.get(13)?);
+             let expires_at: Option<i64> = row.get(14)?;
+             let delete_after_use: bool = row.get(15)?;
              let content_type = ClipType::from(type_str);
              
              // 解析 tags JSON 数组
@@ -284,12 +943,15 @@ impl Storage {
              };
              
              let preview = match content_type {
-                ClipType::Text | ClipType::Html => text.unwrap_or_default().chars().take(50).collect(),
-                ClipType::Color => text.unwrap_or_default(),
+                ClipType::Text | ClipType::Html | ClipType::Rtf => text.unwrap_or_default().chars().take(50).collect(),
+                ClipType::Color | ClipType::Custom => text.unwrap_or_default(),
                 ClipType::Image => "[图片]".to_string(),
                 ClipType::Files => "[文件]".to_string(),
             };
-            Ok(ClipItem { id, content_type, preview, created_at, is_pinned, tags })
+            Ok(ClipItem {
+                id, content_type, preview, created_at, is_pinned, tags, language, blurhash, average_color, source_id, source_app,
+                is_sensitive: false, sensitive_kind: None, expires_at, delete_after_use,
+            })
         })?;
 
         let mut items = Vec::new();
@@ -301,7 +963,7 @@ impl Storage {
     pub fn get_content(&self, id: i64) -> Result<ClipData> {
         let mut stmt = self.conn.prepare(
             "SELECT type, content_text, content_html, content_image_path, content_file_paths,
-             image_path, thumbnail_path
+             image_path, thumbnail_path, in_vault
              FROM records WHERE id = ?1"
         )?;
         
@@ -313,25 +975,46 @@ impl Storage {
             let file_paths: Option<String> = row.get(4)?;
             let image_path: Option<String> = row.get(5)?;
             let _thumbnail_path: Option<String> = row.get(6)?;
+            let in_vault: bool = row.get(7)?;
             
-            Ok((type_str, text, html, img_path_old, file_paths, image_path))
+            Ok((type_str, text, html, img_path_old, file_paths, image_path, in_vault))
         })?;
 
-        let (t_str, text, html, img_path_old, file_paths, image_path) = item;
+        let (t_str, text, html, img_path_old, file_paths, image_path, in_vault) = item;
+        if in_vault {
+            return Err(anyhow::anyhow!("记录 {} 在保险库中，请解锁保险库后读取", id));
+        }
 
         match ClipType::from(t_str) {
-            ClipType::Text => Ok(ClipData::Text(text.unwrap_or_default())),
+            ClipType::Text => match self.external_text_path(id)? {
+                Some(path) => Ok(ClipData::Text(self.read_external_text(&path)?)),
+                None => Ok(ClipData::Text(text.unwrap_or_default())),
+            },
             ClipType::Color => Ok(ClipData::Color(text.unwrap_or_default())),
             ClipType::Html => Ok(ClipData::Html {
                 text: text.unwrap_or_default(),
                 html: html.unwrap_or_default(),
             }),
+            ClipType::Rtf => {
+                let rtf = self.get_parts(id)?
+                    .into_iter()
+                    .find(|p| p.mime == MIME_RTF)
+                    .and_then(|p| p.text)
+                    .unwrap_or_default();
+                Ok(ClipData::Rtf { text: text.unwrap_or_default(), rtf })
+            },
+            ClipType::Custom => {
+                let part = self.get_parts(id)?
+                    .into_iter()
+                    .find(|p| p.data.is_some())
+                    .ok_or_else(|| anyhow::anyhow!("Custom data not found"))?;
+                Ok(ClipData::Custom { mime: part.mime, data: part.data.unwrap_or_default() })
+            },
             ClipType::Image => {
                 // 优先使用新字段 image_path，兼容旧数据
                 let path = image_path.or(img_path_old)
                     .ok_or_else(|| anyhow::anyhow!("Image path not found"))?;
-                let full_path = self.image_dir.join(path);
-                let bytes = fs::read(full_path)?;
+                let bytes = self.read_file(&self.image_dir.join(path))?;
                 Ok(ClipData::Image(bytes))
             },
             ClipType::Files => {
@@ -345,6 +1028,337 @@ impl Storage {
         }
     }
 
+    /// 为记录附加其它表示（同 MIME 类型覆盖旧值）
+    pub fn add_parts(&mut self, record_id: i64, parts: &[ClipPart]) -> Result<()> {
+        if record_id <= 0 || parts.is_empty() { return Ok(()); }
+
+        // 二进制内容先写入磁盘，数据库只保存相对路径
+        let mut blob_paths = Vec::with_capacity(parts.len());
+        for part in parts {
+            let path = match &part.data {
+                Some(data) => Some(self.write_blob(data)?),
+                None => None,
+            };
+            blob_paths.push(path);
+        }
+
+        let tx = self.conn.transaction()?;
+        for (part, blob_path) in parts.iter().zip(&blob_paths) {
+            tx.execute(
+                "INSERT INTO record_parts (record_id, mime, content_text, content_blob, blob_path)
+                 VALUES (?1, ?2, ?3, NULL, ?4)
+                 ON CONFLICT(record_id, mime) DO UPDATE SET
+                    content_text = excluded.content_text,
+                    content_blob = NULL,
+                    blob_path = excluded.blob_path",
+                params![record_id, part.mime, part.text, blob_path],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// 按内容哈希写入 blob 文件，相同内容只保存一份
+    fn write_blob(&self, data: &[u8]) -> Result<String> {
+        let hash = Self::compute_hash(data);
+        let year_month = chrono::Local::now().format("%Y%m").to_string();
+        let relative_path = format!("{}/{}.bin", year_month, hash);
+
+        // 同样的内容可能在之前的月份保存过
+        let existing: Option<String> = self.conn.query_row(
+            "SELECT blob_path FROM record_parts WHERE blob_path LIKE ?1 LIMIT 1",
+            params![format!("%/{}.bin", hash)],
+            |row| row.get(0),
+        ).optional()?;
+        if let Some(path) = existing {
+            if self.blob_dir.join(&path).exists() {
+                return Ok(path);
+            }
+        }
+
+        fs::create_dir_all(self.blob_dir.join(&year_month))?;
+        self.write_file(&self.blob_dir.join(&relative_path), data).context("Failed to write blob")?;
+        Ok(relative_path)
+    }
+
+    /// 收集记录引用的 blob 文件（删除记录前调用）
+    fn blob_paths_of(&self, where_clause: &str, args: &[&dyn rusqlite::ToSql]) -> Result<Vec<String>> {
+        let sql = format!(
+            "SELECT DISTINCT blob_path FROM record_parts
+             WHERE blob_path IS NOT NULL AND record_id IN (SELECT id FROM records WHERE {})",
+            where_clause
+        );
+        let mut stmt = self.conn.prepare(&sql)?;
+        let rows = stmt.query_map(args, |row| row.get::<_, String>(0))?;
+        let mut paths = Vec::new();
+        for row in rows { paths.push(row?); }
+        Ok(paths)
+    }
+
+    /// 收集记录的外置文本文件（删除记录前调用）
+    fn text_paths_of(&self, where_clause: &str, args: &[&dyn rusqlite::ToSql]) -> Result<Vec<String>> {
+        let sql = format!(
+            "SELECT content_path FROM records WHERE content_path IS NOT NULL AND {}",
+            where_clause
+        );
+        let mut stmt = self.conn.prepare(&sql)?;
+        let rows = stmt.query_map(args, |row| row.get::<_, String>(0))?;
+        let mut paths = Vec::new();
+        for row in rows { paths.push(row?); }
+        Ok(paths)
+    }
+
+    /// 收集记录的原图和缩略图路径（删除记录前调用）
+    fn image_files_of(&self, id: i64) -> Result<Vec<String>> {
+        let (image, thumbnail): (Option<String>, Option<String>) = self.conn.query_row(
+            "SELECT image_path, thumbnail_path FROM records WHERE id = ?1",
+            params![id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        Ok(image.into_iter().chain(thumbnail).collect())
+    }
+
+    /// 删除不再被任何记录引用的图片文件
+    fn remove_orphan_images(&self, paths: &[String]) -> Result<()> {
+        for path in paths {
+            let still_used: bool = self.conn.query_row(
+                "SELECT EXISTS(SELECT 1 FROM records WHERE image_path = ?1 OR thumbnail_path = ?1)",
+                params![path],
+                |row| row.get(0),
+            )?;
+            if !still_used {
+                let _ = fs::remove_file(self.image_dir.join(path));
+            }
+        }
+        Ok(())
+    }
+
+    /// 外置文本与记录一一对应 (按哈希去重)，随记录删除
+    fn remove_text_files(&self, paths: &[String]) {
+        for path in paths {
+            let _ = fs::remove_file(self.text_dir.join(path));
+        }
+    }
+
+    /// 删除不再被任何记录引用的 blob 文件
+    fn remove_orphan_blobs(&self, paths: &[String]) -> Result<()> {
+        for path in paths {
+            let still_used: bool = self.conn.query_row(
+                "SELECT EXISTS(SELECT 1 FROM record_parts WHERE blob_path = ?1)",
+                params![path],
+                |row| row.get(0),
+            )?;
+            if !still_used {
+                let _ = fs::remove_file(self.blob_dir.join(path));
+            }
+        }
+        Ok(())
+    }
+
+    /// 记录复制时的来源应用
+    pub fn set_source_app(&self, id: i64, app: &SourceApp) -> Result<()> {
+        self.conn.execute(
+            "UPDATE records SET app_context = ?1, app_title = ?2, app_path = ?3 WHERE id = ?4",
+            params![app.name, app.title, app.exe_path, id],
+        )?;
+        Ok(())
+    }
+
+    /// 出现过的来源应用及其记录数，按记录数降序
+    pub fn list_apps(&self) -> Result<Vec<(String, i64)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT app_context, COUNT(*) AS n FROM records
+             WHERE app_context IS NOT NULL
+             GROUP BY app_context COLLATE NOCASE
+             ORDER BY n DESC, app_context"
+        )?;
+        let apps = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<Vec<(String, i64)>>>()?;
+        Ok(apps)
+    }
+
+    fn source_app_from_columns(name: Option<String>, title: Option<String>, exe_path: Option<String>) -> Option<SourceApp> {
+        let app = SourceApp { name, title, exe_path };
+        (!app.is_empty()).then_some(app)
+    }
+
+    /// 获取颜色记录的颜色值，非颜色记录返回 None
+    pub fn get_color(&self, id: i64) -> Result<Option<Rgba>> {
+        let (type_str, rgba, text): (String, Option<String>, Option<String>) = self.conn.query_row(
+            "SELECT type, color_rgba, content_text FROM records WHERE id = ?1",
+            params![id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )?;
+        if ClipType::from(type_str) != ClipType::Color {
+            return Ok(None);
+        }
+        // 优先使用保存时解析好的值，迁移前的旧记录没有时再解析原文
+        Ok(rgba.or(text).as_deref().and_then(crate::color::parse))
+    }
+
+    /// 获取记录的编程语言 (非代码为 None)
+    pub fn get_language(&self, id: i64) -> Result<Option<String>> {
+        let language: Option<String> = self.conn.query_row(
+            "SELECT language FROM records WHERE id = ?1",
+            params![id],
+            |row| row.get(0),
+        )?;
+        Ok(language)
+    }
+
+    /// 手动指定 / 清除记录的编程语言
+    pub fn set_language(&self, id: i64, language: Option<&str>) -> Result<()> {
+        self.conn.execute("UPDATE records SET language = ?1 WHERE id = ?2", params![language, id])?;
+        Ok(())
+    }
+
+    /// 获取记录的 Markdown 表示；旧记录没有保存时从 HTML 现场转换
+    pub fn get_markdown(&self, id: i64) -> Result<Option<String>> {
+        if let Some(part) = self.get_parts(id)?.into_iter().find(|p| p.mime == MIME_MARKDOWN) {
+            return Ok(part.text);
+        }
+        match self.get_content(id)? {
+            ClipData::Html { html, .. } => Ok(Some(crate::markdown::from_html(&html))),
+            _ => Ok(None),
+        }
+    }
+
+    /// 获取记录的所有附加表示（用于恢复到剪贴板）
+    pub fn get_parts(&self, record_id: i64) -> Result<Vec<ClipPart>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT mime, content_text, content_blob, blob_path FROM record_parts
+             WHERE record_id = ?1 ORDER BY id"
+        )?;
+        let rows = stmt.query_map(params![record_id], |row| {
+            let part = ClipPart {
+                mime: row.get(0)?,
+                text: row.get(1)?,
+                data: row.get(2)?,
+            };
+            let blob_path: Option<String> = row.get(3)?;
+            Ok((part, blob_path))
+        })?;
+
+        let mut parts = Vec::new();
+        for row in rows {
+            let (mut part, blob_path) = row?;
+            if let Some(path) = blob_path {
+                part.data = Some(self.read_file(&self.blob_dir.join(path)).context("Failed to read blob")?);
+            }
+            parts.push(part);
+        }
+        Ok(parts)
+    }
+
+    /// 外置文本的完整字节数；未外置返回 None
+    pub fn external_text_size(&self, id: i64) -> Result<Option<usize>> {
+        let size: Option<i64> = self.conn.query_row(
+            "SELECT content_size FROM records WHERE id = ?1 AND content_path IS NOT NULL",
+            params![id],
+            |row| row.get(0),
+        ).optional()?.flatten();
+        Ok(size.map(|s| s as usize))
+    }
+
+    /// 按字节区间读取文本，外置文本以流式解压，不会整体载入内存
+    ///
+    /// 区间两端会对齐到 UTF-8 字符边界
+    pub fn read_text_range(&self, id: i64, offset: usize, len: usize) -> Result<TextChunk> {
+        use std::io::Read;
+
+        let (bytes, total_size) = match self.external_text_path(id)? {
+            Some(path) => {
+                let total_size = self.external_text_size(id)?.unwrap_or(0);
+                let (offset, len) = (offset.min(total_size), len.min(total_size));
+                let mut decoder = zstd::stream::read::Decoder::new(self.open_text_file(&path)?)?;
+                std::io::copy(&mut (&mut decoder).take(offset as u64), &mut std::io::sink())?;
+                // 首尾各多读 3 字节，保证对齐后的字符完整
+                let mut buf = Vec::with_capacity(len.saturating_add(6));
+                decoder.take(len.saturating_add(6) as u64).read_to_end(&mut buf)?;
+                (buf, total_size)
+            }
+            None => {
+                let text: Option<String> = self.conn.query_row(
+                    "SELECT content_text FROM records WHERE id = ?1",
+                    params![id],
+                    |row| row.get(0),
+                )?;
+                let text = text.unwrap_or_default().into_bytes();
+                let total_size = text.len();
+                let start = offset.min(total_size);
+                let end = start.saturating_add(len).saturating_add(6).min(total_size);
+                (text[start..end].to_vec(), total_size)
+            }
+        };
+
+        // 偏移量来自前端，先限制在文本范围内
+        let offset = offset.min(total_size);
+        // 起点落在多字节字符中间时跳过续字节
+        let skip = bytes.iter().take_while(|b| (**b & 0xC0) == 0x80).count();
+        let mut end = skip.saturating_add(len).min(bytes.len()).max(skip);
+        while end < bytes.len() && (bytes[end] & 0xC0) == 0x80 {
+            end += 1;
+        }
+        let text = String::from_utf8_lossy(&bytes[skip..end]).to_string();
+        let start = offset.saturating_add(skip).min(total_size);
+        let next_offset = offset.saturating_add(end).min(total_size);
+
+        Ok(TextChunk {
+            text,
+            offset: start,
+            next_offset,
+            total_size,
+            eof: next_offset >= total_size,
+        })
+    }
+
+    fn external_text_path(&self, id: i64) -> Result<Option<String>> {
+        let path: Option<String> = self.conn.query_row(
+            "SELECT content_path FROM records WHERE id = ?1",
+            params![id],
+            |row| row.get(0),
+        ).optional()?.flatten();
+        Ok(path)
+    }
+
+    /// 压缩写入外置文本，同一哈希只写一次
+    fn write_external_text(&self, hash: &str, text: &str) -> Result<String> {
+        let year_month = chrono::Local::now().format("%Y%m").to_string();
+        let relative_path = format!("{}/{}.txt.zst", year_month, hash);
+        let full_path = self.text_dir.join(&relative_path);
+        if !full_path.exists() {
+            fs::create_dir_all(self.text_dir.join(&year_month))?;
+            let compressed = zstd::encode_all(text.as_bytes(), 3)?;
+            self.write_file(&full_path, &compressed).context("Failed to write text file")?;
+        }
+        Ok(relative_path)
+    }
+
+    fn read_external_text(&self, path: &str) -> Result<String> {
+        let bytes = zstd::decode_all(self.open_text_file(path)?)?;
+        Ok(String::from_utf8(bytes)?)
+    }
+
+    /// 打开压缩后的外置文本；加密存储时需要先整体读入解密
+    fn open_text_file(&self, path: &str) -> Result<Box<dyn std::io::Read>> {
+        let full_path = self.text_dir.join(path);
+        if self.cipher.is_some() {
+            return Ok(Box::new(std::io::Cursor::new(self.read_file(&full_path)?)));
+        }
+        Ok(Box::new(fs::File::open(full_path).context("Failed to open text file")?))
+    }
+
+    fn truncate_at_char_boundary(text: &str, max_bytes: usize) -> &str {
+        if text.len() <= max_bytes {
+            return text;
+        }
+        let mut end = max_bytes;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        &text[..end]
+    }
+
     /// 获取图片的缩略图路径（用于前端展示）
     pub fn get_image_paths(&self, id: i64) -> Result<(String, String)> {
         self.conn.query_row(
@@ -428,68 +1442,37 @@ impl Storage {
         Ok(())
     }
 
+    /// 只对分类为代码 / 结构化文本的内容检测编程语言
+    fn detect_language(tags: &[String], text: &str) -> Option<&'static str> {
+        let is_code = tags.iter().any(|t| matches!(t.as_str(), "code" | "json" | "xml" | "sql"));
+        if is_code { crate::language::detect(text) } else { None }
+    }
+
     fn compute_hash(data: &[u8]) -> String {
         let hash = blake3::hash(data);
         hex::encode(hash.as_bytes())
     }
 
-    /// 检测字符串是否为颜色值
-    /// 支持格式：
-    /// - HEX: #RGB, #RRGGBB, #RRGGBBAA
-    /// - RGB: rgb(r, g, b)
-    /// - RGBA: rgba(r, g, b, a)
-    /// - HSL: hsl(h, s%, l%)
-    /// - HSLA: hsla(h, s%, l%, a)
-    fn is_color(text: &str) -> bool {
-        let text = text.trim();
-        
-        // HEX 格式: #RGB, #RRGGBB, #RRGGBBAA
-        if text.starts_with('#') {
-            let hex_part = &text[1..];
-            let len = hex_part.len();
-            // 验证长度和字符
-            if (len == 3 || len == 6 || len == 8) && hex_part.chars().all(|c| c.is_ascii_hexdigit()) {
-                return true;
-            }
-        }
-        
-        // RGB/RGBA 格式
-        let lower = text.to_lowercase();
-        if lower.starts_with("rgb(") || lower.starts_with("rgba(") {
-            if let (Some(start), Some(end)) = (lower.find('('), lower.rfind(')')) {
-                let content = &lower[start+1..end];
-                let parts: Vec<&str> = content.split(',').map(|s| s.trim()).collect();
-                // rgb 需要 3 个参数，rgba 需要 4 个参数
-                if (lower.starts_with("rgb(") && parts.len() == 3) || 
-                   (lower.starts_with("rgba(") && parts.len() == 4) {
-                    return true;
-                }
-            }
-        }
-        
-        // HSL/HSLA 格式
-        if lower.starts_with("hsl(") || lower.starts_with("hsla(") {
-            if let (Some(start), Some(end)) = (lower.find('('), lower.rfind(')')) {
-                let content = &lower[start+1..end];
-                let parts: Vec<&str> = content.split(',').map(|s| s.trim()).collect();
-                // hsl 需要 3 个参数，hsla 需要 4 个参数
-                if (lower.starts_with("hsl(") && parts.len() == 3) || 
-                   (lower.starts_with("hsla(") && parts.len() == 4) {
-                    return true;
-                }
-            }
-        }
-        
-        false
+    /// 从现在起 `secs` 秒后的微秒时间戳；时长超出范围时返回错误而不是溢出
+    fn expires_after_secs(secs: u64) -> Result<i64> {
+        i64::try_from(secs).ok()
+            .and_then(TimeDelta::try_seconds)
+            .and_then(|ttl| Utc::now().checked_add_signed(ttl))
+            .map(|at| at.timestamp_micros())
+            .ok_or_else(|| anyhow::anyhow!("时长超出范围: {} 秒", secs))
     }
 
     /// 切换记录的置顶状态
     pub fn toggle_pin(&self, id: i64) -> Result<bool> {
-        let new_state: bool = self.conn.query_row(
-            "SELECT is_pinned FROM records WHERE id = ?1",
+        let (new_state, in_vault): (bool, bool) = self.conn.query_row(
+            "SELECT is_pinned, in_vault FROM records WHERE id = ?1",
             params![id],
-            |row| row.get(0),
+            |row| Ok((row.get(0)?, row.get(1)?)),
         )?;
+        // 保险库记录始终置顶，以免被清空
+        if in_vault {
+            return Err(anyhow::anyhow!("保险库中的记录不能取消置顶"));
+        }
         
         self.conn.execute(
             "UPDATE records SET is_pinned = ?1 WHERE id = ?2",
@@ -501,16 +1484,217 @@ impl Storage {
 
     /// 删除指定记录
     pub fn delete_record(&self, id: i64) -> Result<()> {
+        let blobs = self.blob_paths_of("id = ?1", params![id])?;
+        let texts = self.text_paths_of("id = ?1", params![id])?;
         self.conn.execute(
             "DELETE FROM records WHERE id = ?1",
             params![id],
         )?;
+        self.remove_orphan_blobs(&blobs)?;
+        self.remove_text_files(&texts);
+        Ok(())
+    }
+
+    /// 手动标记或取消敏感，之后重复复制同样的内容也保持该选择
+    ///
+    /// 标记为敏感时从现在起重新计算到期时间，取消时只清除敏感标记带来的到期时间
+    pub fn set_sensitive(&self, id: i64, sensitive: bool) -> Result<()> {
+        let expires_at = Self::expires_after_secs(self.settings.sensitive_ttl_secs)?;
+        let updated = self.conn.execute(
+            "UPDATE records SET sensitive_override = ?1, is_sensitive = ?1,
+             sensitive_kind = CASE WHEN ?1 = 1 THEN COALESCE(sensitive_kind, ?2) ELSE sensitive_kind END,
+             expires_at = CASE WHEN ?1 = 1 THEN ?3 WHEN expiry_auto = 1 THEN NULL ELSE expires_at END,
+             expiry_auto = ?1
+             WHERE id = ?4",
+            params![sensitive, crate::secret::SecretKind::Manual.as_str(), expires_at, id],
+        )?;
+        if updated == 0 {
+            return Err(anyhow::anyhow!("记录不存在: {}", id));
+        }
+        Ok(())
+    }
+
+    /// 设置记录在多久后自动删除；`ttl` 为 None 时取消，返回新的到期时间
+    pub fn set_expiry(&self, id: i64, ttl: Option<TimeDelta>) -> Result<Option<i64>> {
+        let expires_at = ttl
+            .map(|ttl| {
+                Utc::now()
+                    .checked_add_signed(ttl)
+                    .map(|at| at.timestamp_micros())
+                    .ok_or_else(|| anyhow::anyhow!("到期时间超出范围"))
+            })
+            .transpose()?;
+        let updated = self.conn.execute(
+            "UPDATE records SET expires_at = ?1, expiry_auto = 0 WHERE id = ?2",
+            params![expires_at, id],
+        )?;
+        if updated == 0 {
+            return Err(anyhow::anyhow!("记录不存在: {}", id));
+        }
+        Ok(expires_at)
+    }
+
+    /// 标记 / 取消一次性记录
+    pub fn set_delete_after_use(&self, id: i64, enabled: bool) -> Result<()> {
+        let updated = self.conn.execute(
+            "UPDATE records SET delete_after_use = ?1 WHERE id = ?2",
+            params![enabled, id],
+        )?;
+        if updated == 0 {
+            return Err(anyhow::anyhow!("记录不存在: {}", id));
+        }
         Ok(())
     }
 
+    /// 记录已被粘贴回剪贴板：一次性记录在宽限期后到期，返回到期时间
+    pub fn mark_used(&self, id: i64) -> Result<Option<i64>> {
+        let grace = Self::expires_after_secs(self.settings.one_time_grace_secs)?;
+        self.conn.query_row(
+            "UPDATE records SET expires_at = MIN(COALESCE(expires_at, ?1), ?1),
+             expiry_auto = CASE WHEN COALESCE(expires_at, ?1) >= ?1 THEN 0 ELSE expiry_auto END
+             WHERE id = ?2 AND delete_after_use = 1
+             RETURNING expires_at",
+            params![grace, id],
+            |row| row.get(0),
+        ).optional().map_err(Into::into)
+    }
+
+    /// 删除已到期且未置顶的记录及其图片文件，返回被删除的 ID
+    pub fn purge_expired(&self) -> Result<Vec<i64>> {
+        let ids: Vec<i64> = self.conn
+            .prepare("SELECT id FROM records WHERE expires_at <= ?1 AND is_pinned = 0 AND in_vault = 0")?
+            .query_map(params![Utc::now().timestamp_micros()], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+        for &id in &ids {
+            let images = self.image_files_of(id)?;
+            self.delete_record(id)?;
+            self.remove_orphan_images(&images)?;
+        }
+        if !ids.is_empty() {
+            println!("⏰ 已删除 {} 条到期记录", ids.len());
+        }
+        Ok(ids)
+    }
+
+    /// 解锁保险库，返回到期时间；第一次解锁时设置口令
+    pub fn vault_unlock(&mut self, passphrase: &str) -> Result<i64> {
+        // 锁定期间的占位数据库没有保险库口令，不能在其中设置
+        if self.locked {
+            return Err(anyhow::anyhow!("存储已锁定，请先解锁"));
+        }
+        if passphrase.is_empty() {
+            return Err(anyhow::anyhow!("口令不能为空"));
+        }
+        let ttl = i64::try_from(self.settings.vault_unlock_secs).ok()
+            .and_then(TimeDelta::try_seconds)
+            .ok_or_else(|| anyhow::anyhow!("保险库解锁时长超出范围"))?;
+        let meta: Option<(Vec<u8>, Vec<u8>)> = self.conn.query_row(
+            "SELECT salt, verifier FROM vault_meta WHERE id = 1",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        ).optional()?;
+
+        let key = match meta {
+            Some((salt, verifier)) => {
+                let key = StoreKey::derive(passphrase, &salt)?;
+                if key.open(&verifier).ok().as_deref() != Some(VERIFIER_PLAINTEXT) {
+                    return Err(anyhow::anyhow!("保险库口令错误"));
+                }
+                key
+            }
+            None => {
+                let salt = crypto::random_salt()?;
+                let key = StoreKey::derive(passphrase, &salt)?;
+                self.conn.execute(
+                    "INSERT INTO vault_meta (id, salt, verifier) VALUES (1, ?1, ?2)",
+                    params![&salt[..], key.seal(VERIFIER_PLAINTEXT)?],
+                )?;
+                println!("🔐 已设置保险库口令");
+                key
+            }
+        };
+        self.vault.unlock(key, ttl)
+    }
+
+    /// 锁定保险库，之前处于解锁状态时返回 true
+    pub fn vault_lock(&mut self) -> bool {
+        self.vault.lock()
+    }
+
+    /// 保险库解锁到期时锁定，状态发生变化时返回 true
+    pub fn refresh_vault(&mut self) -> bool {
+        self.vault.refresh()
+    }
+
+    /// 保险库解锁的到期时间，未解锁时返回 None
+    pub fn vault_expires_at(&mut self) -> Option<i64> {
+        self.vault.expires_at()
+    }
+
+    /// 把文本类记录移入保险库：内容用保险库密钥加密，明文、附加表示和外置文本都被清除，记录置顶保留
+    pub fn move_to_vault(&mut self, id: i64, label: Option<String>) -> Result<()> {
+        let text = match self.get_content(id)? {
+            ClipData::Text(text) | ClipData::Color(text) => text,
+            ClipData::Html { text, .. } | ClipData::Rtf { text, .. } => text,
+            _ => return Err(anyhow::anyhow!("只能把文本类记录移入保险库")),
+        };
+        let payload = self.vault.key()
+            .ok_or_else(|| anyhow::anyhow!("保险库未解锁"))?
+            .seal(text.as_bytes())?;
+
+        let blobs = self.blob_paths_of("id = ?1", params![id])?;
+        let texts = self.text_paths_of("id = ?1", params![id])?;
+        let tx = self.conn.transaction()?;
+        tx.execute("DELETE FROM record_parts WHERE record_id = ?1", params![id])?;
+        // 标记为敏感：全文索引触发器随之删除旧内容；哈希改为与内容无关的值，之后再复制同样内容会成为普通记录
+        tx.execute(
+            "UPDATE records SET in_vault = 1, vault_payload = ?1, vault_label = ?2,
+             content_text = NULL, content_html = NULL, content_path = NULL, content_size = NULL,
+             color_rgba = NULL, ocr_text = NULL, tag = '[\"vault\"]', hash = 'vault:' || id,
+             is_pinned = 1, is_sensitive = 1, sensitive_override = 1,
+             sensitive_kind = COALESCE(sensitive_kind, ?3), expires_at = NULL, expiry_auto = 0,
+             delete_after_use = 0
+             WHERE id = ?4",
+            params![payload, label, crate::secret::SecretKind::Manual.as_str(), id],
+        )?;
+        tx.commit()?;
+        // 旧页面的副本还留在 WAL 中，立即写回并清空
+        self.conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))?;
+        self.remove_orphan_blobs(&blobs)?;
+        self.remove_text_files(&texts);
+        println!("🔐 记录已移入保险库: ID {}", id);
+        Ok(())
+    }
+
+    /// 保险库中的条目 (只有名称)，不需要解锁
+    pub fn vault_items(&self) -> Result<Vec<VaultItem>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, vault_label, created_at FROM records WHERE in_vault = 1 ORDER BY created_at DESC"
+        )?;
+        let items = stmt.query_map([], |row| {
+            Ok(VaultItem { id: row.get(0)?, label: row.get(1)?, created_at: row.get(2)? })
+        })?.collect::<rusqlite::Result<_>>()?;
+        Ok(items)
+    }
+
+    /// 解密保险库中的记录，需要先解锁
+    pub fn read_vault(&mut self, id: i64) -> Result<String> {
+        let payload: Vec<u8> = self.conn.query_row(
+            "SELECT vault_payload FROM records WHERE id = ?1 AND in_vault = 1",
+            params![id],
+            |row| row.get(0),
+        ).optional()?.ok_or_else(|| anyhow::anyhow!("保险库中没有该记录: {}", id))?;
+        let key = self.vault.key().ok_or_else(|| anyhow::anyhow!("保险库未解锁"))?;
+        Ok(String::from_utf8(key.open(&payload)?)?)
+    }
+
     /// 清空所有未置顶的记录
     pub fn clear_unpinned(&mut self) -> Result<i64> {
-        let deleted = self.conn.execute("DELETE FROM records WHERE is_pinned = 0", [])?;
+        let blobs = self.blob_paths_of("is_pinned = 0 AND in_vault = 0", &[])?;
+        let texts = self.text_paths_of("is_pinned = 0 AND in_vault = 0", &[])?;
+        let deleted = self.conn.execute("DELETE FROM records WHERE is_pinned = 0 AND in_vault = 0", [])?;
+        self.remove_orphan_blobs(&blobs)?;
+        self.remove_text_files(&texts);
         println!("🗑️ 已清空 {} 条未置顶记录", deleted);
         Ok(deleted as i64)
     }
@@ -540,8 +1724,7 @@ impl Storage {
                 println!("📸 图片已存在，使用已有记录 ID: {}", existing_id);
                 // 读取已存在的缩略图数据返回
                 let (_, thumbnail_path) = self.get_image_paths(existing_id)?;
-                let full_thumb_path = self.image_dir.join(&thumbnail_path);
-                let thumbnail_data = fs::read(full_thumb_path)?;
+                let thumbnail_data = self.read_file(&self.image_dir.join(&thumbnail_path))?;
                 return Ok((existing_id, thumbnail_data));
             }
             Ok(None) => {
@@ -586,12 +1769,15 @@ impl Storage {
         let thumbnail_path = thumbnail_dir.join(&thumb_filename);
 
         // Phase 1: 保存原图（PNG格式）
-        img.save_with_format(&original_path, format)
+        let mut png_buffer = Vec::new();
+        img.write_to(&mut std::io::Cursor::new(&mut png_buffer), format)
+            .context("Failed to encode original image")?;
+        self.write_file(&original_path, &png_buffer)
             .context("Failed to write original image")?;
         println!("✅ 原图已保存: {}", relative_path);
 
-        // 获取保存后的文件大小
-        let file_size = fs::metadata(&original_path)?.len();
+        // 原图大小 (加密前)
+        let file_size = png_buffer.len();
 
         // Phase 2: 生成缩略图（同步，提高分辨率和质量）
         let thumbnail_img = img.thumbnail(800, 600);
@@ -603,10 +1789,14 @@ impl Storage {
             .context("Failed to encode thumbnail")?;
         
         // 保存到文件
-        fs::write(&thumbnail_path, &webp_buffer)
+        self.write_file(&thumbnail_path, &webp_buffer)
             .context("Failed to write thumbnail")?;
         println!("✅ 缩略图已生成: {}", relative_thumb_path);
 
+        // Phase 4: 提取调色板和占位信息
+        let palette = crate::palette::extract(&img, crate::palette::DEFAULT_COLORS);
+        let placeholder = crate::placeholder::compute(width as u32, height as u32, img.as_bytes());
+
         // 插入数据库记录
         let timestamp_micros = Utc::now().timestamp_micros();
         
@@ -614,8 +1804,8 @@ impl Storage {
             "INSERT INTO records (
                 type, hash, created_at, content_text,
                 image_path, thumbnail_path, image_format, image_size,
-                image_hash, width, height, tag
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+                image_hash, width, height, tag, palette, blurhash, average_color
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
             params![
                 ClipType::Image.to_string(),
                 hash_hex, // hash字段用于通用去重
@@ -629,6 +1819,9 @@ impl Storage {
                 width as i64,
                 height as i64,
                 r#"["image"]"#, // tag标签
+                serde_json::to_string(&palette)?,
+                placeholder.as_ref().map(|p| p.blurhash.clone()),
+                placeholder.as_ref().map(|p| p.average_color.clone()),
             ],
         )?;
 
@@ -639,6 +1832,154 @@ impl Storage {
         Ok((id, webp_buffer))
     }
 
+    /// 获取图片记录的调色板，旧记录没有时从原图补算并保存
+    pub fn get_palette(&self, id: i64) -> Result<Vec<Swatch>> {
+        let (type_str, palette): (String, Option<String>) = self.conn.query_row(
+            "SELECT type, palette FROM records WHERE id = ?1",
+            params![id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        if ClipType::from(type_str) != ClipType::Image {
+            return Err(anyhow::anyhow!("记录 {} 不是图片", id));
+        }
+        if let Some(json) = palette {
+            return Ok(serde_json::from_str(&json)?);
+        }
+
+        let (image_path, _) = self.get_image_paths(id)?;
+        let img = image::load_from_memory(&self.read_file(&self.image_dir.join(&image_path))?)
+            .context("Failed to open image")?;
+        let palette = crate::palette::extract(&img, crate::palette::DEFAULT_COLORS);
+        self.conn.execute(
+            "UPDATE records SET palette = ?1 WHERE id = ?2",
+            params![serde_json::to_string(&palette)?, id],
+        )?;
+        Ok(palette)
+    }
+
+    /// 把调色板中的第 index 个颜色保存为一条颜色记录，返回新记录 ID
+    pub fn promote_swatch(&mut self, id: i64, index: usize) -> Result<i64> {
+        let palette = self.get_palette(id)?;
+        let swatch = palette.get(index)
+            .ok_or_else(|| anyhow::anyhow!("调色板中没有第 {} 个颜色", index))?;
+        self.add_text(swatch.color.clone())
+    }
+
+    /// 图片原图的文件路径，供外部工具 (tesseract、zbarimg) 读取
+    ///
+    /// 加密存储时解密到数据目录下的 tmp 目录，返回值释放时删除
+    pub fn image_file(&self, id: i64) -> Result<ImageFile> {
+        let (image_path, _) = self.get_image_paths(id)?;
+        let path = self.image_dir.join(&image_path);
+        if self.cipher.is_none() {
+            return Ok(ImageFile { path, temporary: false });
+        }
+
+        // OCR 和二维码识别可能同时读取同一张图片，各自使用一个临时文件
+        static NEXT_TEMP: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+        let n = NEXT_TEMP.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        let temp_path = self.scratch_dir.join(format!("{}-{}.png", id, n));
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let file = ImageFile { path: temp_path, temporary: true };
+        std::io::Write::write_all(&mut options.open(&file.path)?, &self.read_file(&path)?)?;
+        Ok(file)
+    }
+
+    /// 图片原图或缩略图的内容 (已解密)
+    pub fn image_data(&self, id: i64, thumbnail: bool) -> Result<Vec<u8>> {
+        let (image_path, thumbnail_path) = self.get_image_paths(id)?;
+        let path = if thumbnail { thumbnail_path } else { image_path };
+        self.read_file(&self.image_dir.join(path))
+    }
+
+    /// 保存 OCR 结果 (空字符串表示没有识别出文字)
+    pub fn set_ocr_text(&self, id: i64, text: &str) -> Result<()> {
+        self.conn.execute(
+            "UPDATE records SET ocr_text = ?1 WHERE id = ?2",
+            params![text, id],
+        )?;
+        Ok(())
+    }
+
+    /// 获取图片识别出的文字，未识别或没有文字时返回 None
+    pub fn get_ocr_text(&self, id: i64) -> Result<Option<String>> {
+        let text: Option<String> = self.conn.query_row(
+            "SELECT ocr_text FROM records WHERE id = ?1",
+            params![id],
+            |row| row.get(0),
+        )?;
+        Ok(text.filter(|t| !t.is_empty()))
+    }
+
+    /// 尚未 OCR 的图片记录，最新的在前
+    pub fn pending_ocr(&self) -> Result<Vec<i64>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id FROM records WHERE type = 'image' AND ocr_text IS NULL ORDER BY created_at DESC"
+        )?;
+        let ids = stmt.query_map([], |row| row.get(0))?.collect::<rusqlite::Result<Vec<i64>>>()?;
+        Ok(ids)
+    }
+
+    /// 保存派生文本 (如图片中识别出的二维码内容)，关联到来源记录并附加标签
+    ///
+    /// 内容与已有记录相同时只去重，不改动原记录的来源和标签
+    pub fn add_derived_text(&mut self, source_id: i64, text: String, tag: &str) -> Result<i64> {
+        // 按 add_text 的去重键查找，已有记录 (即使是最新一条) 不改动
+        let key = self.settings.text_normalization.apply(&text);
+        let existed = self.find_id_by_hash(&Self::compute_hash(key.as_bytes()))?.is_some();
+        let id = self.add_text(text)?;
+        if id == 0 || existed { return Ok(id); }
+        self.conn.execute(
+            "UPDATE records SET source_id = ?1,
+             tag = CASE WHEN EXISTS (SELECT 1 FROM json_each(records.tag) WHERE value = ?2)
+                   THEN tag ELSE json_insert(tag, '$[#]', ?2) END
+             WHERE id = ?3",
+            params![source_id, tag, id],
+        )?;
+        Ok(id)
+    }
+
+    /// 把文本类记录渲染为二维码图片记录，返回新记录 ID 和缩略图
+    pub fn generate_qr(&mut self, id: i64) -> Result<(i64, Vec<u8>)> {
+        let text = match self.get_content(id)? {
+            ClipData::Text(text) | ClipData::Color(text) => text,
+            ClipData::Html { text, .. } | ClipData::Rtf { text, .. } => text,
+            _ => return Err(anyhow::anyhow!("只能为文本记录生成二维码")),
+        };
+        let img = crate::barcode::render_qr(&text)?;
+        let (width, height) = img.dimensions();
+        let (qr_id, thumbnail) = self.add_image(width as usize, height as usize, img.into_raw())?;
+        self.conn.execute(
+            "UPDATE records SET source_id = ?1 WHERE id = ?2",
+            params![id, qr_id],
+        )?;
+        Ok((qr_id, thumbnail))
+    }
+
+    /// 保存 SVG：渲染为位图按图片记录保存，源码作为附加表示保留
+    pub fn add_svg(&mut self, markup: String) -> Result<(i64, Vec<u8>)> {
+        let rendered = crate::svg::render(&markup)?;
+        let (id, thumbnail) = self.add_image(rendered.width as usize, rendered.height as usize, rendered.rgba)?;
+        self.add_parts(id, &[
+            ClipPart::text(MIME_SVG, markup.clone()),
+            ClipPart::text(MIME_TEXT, markup),
+        ])?;
+        self.conn.execute(
+            "UPDATE records SET tag = ?1 WHERE id = ?2",
+            params![r#"["image","svg"]"#, id],
+        )?;
+        Ok((id, thumbnail))
+    }
+
+    /// 获取 SVG 记录的源码，非 SVG 记录返回 None
+    pub fn get_svg(&self, id: i64) -> Result<Option<String>> {
+        let part = self.get_parts(id)?.into_iter().find(|p| p.mime == MIME_SVG);
+        Ok(part.and_then(|p| p.text.or_else(|| p.data.and_then(|d| String::from_utf8(d).ok()))))
+    }
+
     /// 根据 hash 查找已存在的图片
     fn find_image_by_hash(&self, hash: &str) -> Result<Option<i64>> {
         let result = self.conn
@@ -0,0 +1,55 @@
+//! 图片占位信息：BlurHash 和平均色，列表在缩略图加载完成前先画出模糊占位
+
+use image::{imageops, ImageBuffer, Rgba as Pixel};
+use serde::{Deserialize, Serialize};
+
+use crate::color::Rgba;
+
+/// 计算前先缩小到该边长以内
+const SAMPLE_SIZE: u32 = 32;
+
+/// 长边方向的 BlurHash 分量数，短边按比例缩减 (最少 3 个)
+const MAX_COMPONENTS: u32 = 4;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Placeholder {
+    pub blurhash: String,
+    pub average_color: String, // #rrggbb，半透明图片为 #rrggbbaa
+}
+
+/// 根据 RGBA 原始数据计算占位信息，数据大小不匹配时返回 None
+pub fn compute(width: u32, height: u32, rgba: &[u8]) -> Option<Placeholder> {
+    if width == 0 || height == 0 {
+        return None;
+    }
+    let image = ImageBuffer::<Pixel<u8>, &[u8]>::from_raw(width, height, rgba)?;
+    let sample = imageops::thumbnail(&image, SAMPLE_SIZE.min(width), SAMPLE_SIZE.min(height));
+
+    let (cx, cy) = if width >= height {
+        (MAX_COMPONENTS, (MAX_COMPONENTS * height / width).max(3))
+    } else {
+        ((MAX_COMPONENTS * width / height).max(3), MAX_COMPONENTS)
+    };
+    let blurhash = blurhash::encode(cx, cy, sample.width(), sample.height(), sample.as_raw()).ok()?;
+
+    Some(Placeholder { blurhash, average_color: average_color(&sample) })
+}
+
+/// 按 alpha 加权的平均色
+fn average_color(sample: &ImageBuffer<Pixel<u8>, Vec<u8>>) -> String {
+    let mut sum = [0u64; 3];
+    let mut alpha = 0u64;
+    for p in sample.pixels() {
+        let a = p[3] as u64;
+        for (total, &value) in sum.iter_mut().zip(&p.0[..3]) {
+            *total += value as u64 * a;
+        }
+        alpha += a;
+    }
+    if alpha == 0 {
+        return Rgba::from_rgb8(0, 0, 0, 0.0).to_hex();
+    }
+    let count = sample.pixels().len().max(1) as f64;
+    let [r, g, b] = sum.map(|v| (v / alpha) as u8);
+    Rgba::from_rgb8(r, g, b, alpha as f64 / 255.0 / count).to_hex()
+}
@@ -0,0 +1,118 @@
+//! 隐私黑名单：前台应用命中规则时丢弃剪贴板事件，并按应用统计被丢弃的次数
+
+use anyhow::{anyhow, Result};
+use globset::{GlobBuilder, GlobMatcher};
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::setting::{BlockRule, MatchKind, RuleTarget};
+use crate::source_app::SourceApp;
+
+/// 剪贴板监听线程和界面共享的黑名单
+pub type SharedGuard = Arc<Mutex<PrivacyGuard>>;
+
+/// 无法识别应用名时的计数键
+const UNKNOWN_APP: &str = "unknown";
+
+enum Matcher {
+    Exact(String), // 已转为小写
+    Glob(GlobMatcher),
+    Regex(Regex),
+}
+
+impl Matcher {
+    fn compile(rule: &BlockRule) -> Result<Self> {
+        Ok(match rule.kind {
+            MatchKind::Exact => Matcher::Exact(rule.pattern.to_lowercase()),
+            MatchKind::Glob => Matcher::Glob(
+                GlobBuilder::new(&rule.pattern)
+                    .case_insensitive(true)
+                    .literal_separator(false)
+                    .build()
+                    .map_err(|e| anyhow!("无效的通配符 \"{}\": {}", rule.pattern, e))?
+                    .compile_matcher(),
+            ),
+            MatchKind::Regex => Matcher::Regex(
+                Regex::new(&rule.pattern).map_err(|e| anyhow!("无效的正则 \"{}\": {}", rule.pattern, e))?,
+            ),
+        })
+    }
+
+    fn is_match(&self, value: &str) -> bool {
+        match self {
+            Matcher::Exact(pattern) => value.to_lowercase() == *pattern,
+            Matcher::Glob(glob) => glob.is_match(value),
+            Matcher::Regex(re) => re.is_match(value),
+        }
+    }
+}
+
+/// 编译后的黑名单及被丢弃事件的计数 (仅本次运行)
+#[derive(Default)]
+pub struct PrivacyGuard {
+    rules: Vec<(BlockRule, Matcher)>,
+    suppressed: HashMap<String, u64>,
+}
+
+impl PrivacyGuard {
+    /// 编译规则，无效的规则打印警告后跳过
+    pub fn new(rules: &[BlockRule]) -> Self {
+        let rules = rules
+            .iter()
+            .filter_map(|rule| match Matcher::compile(rule) {
+                Ok(matcher) => Some((rule.clone(), matcher)),
+                Err(e) => {
+                    eprintln!("⚠️ 跳过隐私规则: {}", e);
+                    None
+                }
+            })
+            .collect();
+        Self { rules, suppressed: HashMap::new() }
+    }
+
+    /// 检查规则是否都能编译，用于保存设置前校验
+    pub fn validate(rules: &[BlockRule]) -> Result<()> {
+        rules.iter().try_for_each(|rule| Matcher::compile(rule).map(|_| ()))
+    }
+
+    /// 替换规则，保留计数
+    pub fn set_rules(&mut self, rules: &[BlockRule]) -> Result<()> {
+        Self::validate(rules)?;
+        self.rules = Self::new(rules).rules;
+        Ok(())
+    }
+
+    /// 命中的第一条规则
+    pub fn matching_rule(&self, app: &SourceApp) -> Option<&BlockRule> {
+        self.rules.iter().find_map(|(rule, matcher)| {
+            let value = match rule.target {
+                RuleTarget::App => app.name.as_deref(),
+                RuleTarget::Title => app.title.as_deref(),
+            }?;
+            matcher.is_match(value).then_some(rule)
+        })
+    }
+
+    /// 判断是否丢弃本次事件，丢弃时计数
+    pub fn should_suppress(&mut self, app: Option<&SourceApp>) -> bool {
+        let Some(app) = app else { return false };
+        let Some(rule) = self.matching_rule(app) else { return false };
+        println!("🔒 隐私规则命中 ({:?} {:?} \"{}\")，忽略本次复制", rule.target, rule.kind, rule.pattern);
+
+        let key = app.name.clone().unwrap_or_else(|| UNKNOWN_APP.to_string());
+        *self.suppressed.entry(key).or_insert(0) += 1;
+        true
+    }
+
+    /// 各应用被丢弃的事件数，按次数降序
+    pub fn suppressed_counts(&self) -> Vec<(String, u64)> {
+        let mut counts: Vec<(String, u64)> = self.suppressed.iter().map(|(k, v)| (k.clone(), *v)).collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        counts
+    }
+
+    pub fn reset_counts(&mut self) {
+        self.suppressed.clear();
+    }
+}
@@ -0,0 +1,299 @@
+//! RTF 解析：提取纯文本（用于搜索）和转换为 HTML（用于预览）
+//!
+//! 只处理剪贴板中常见的子集：段落、换行、粗体 / 斜体 / 下划线、
+//! Unicode 转义 (\uN) 和代码页十六进制转义 (\'hh)，其余控制字忽略
+
+use encoding_rs::Encoding;
+
+/// RTF 转纯文本，段落之间以换行分隔
+pub fn to_plain_text(rtf: &str) -> String {
+    parse(rtf)
+        .iter()
+        .map(|para| para.iter().map(|run| run.text.as_str()).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_end()
+        .to_string()
+}
+
+/// RTF 转 HTML，每个段落一个 `<p>`，保留粗体 / 斜体 / 下划线
+pub fn to_html(rtf: &str) -> String {
+    let mut paragraphs = parse(rtf);
+    while paragraphs.last().is_some_and(|p| p.iter().all(|r| r.text.trim().is_empty())) {
+        paragraphs.pop();
+    }
+
+    let mut html = String::new();
+    for para in &paragraphs {
+        html.push_str("<p>");
+        if para.iter().all(|r| r.text.is_empty()) {
+            html.push_str("<br>");
+        }
+        for run in para {
+            let mut text = escape_html(&run.text).replace('\n', "<br>");
+            if run.underline { text = format!("<u>{}</u>", text); }
+            if run.italic { text = format!("<i>{}</i>", text); }
+            if run.bold { text = format!("<b>{}</b>", text); }
+            html.push_str(&text);
+        }
+        html.push_str("</p>");
+    }
+    html
+}
+
+/// 粗略判断是否为 RTF 文档
+pub fn is_rtf(data: &str) -> bool {
+    data.trim_start().starts_with("{\\rtf")
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Run {
+    text: String,
+    bold: bool,
+    italic: bool,
+    underline: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct GroupState {
+    bold: bool,
+    italic: bool,
+    underline: bool,
+    skip: bool, // 位于不输出文本的目标组 (字体表、图片等)
+    uc: usize,  // \uN 之后需要跳过的替代字符数
+}
+
+impl Default for GroupState {
+    fn default() -> Self {
+        Self { bold: false, italic: false, underline: false, skip: false, uc: 1 }
+    }
+}
+
+/// 不包含正文文本的目标组
+const SKIP_DESTINATIONS: &[&str] = &[
+    "fonttbl", "colortbl", "stylesheet", "info", "pict", "object", "header", "footer",
+    "headerl", "headerr", "headerf", "footerl", "footerr", "footerf", "listtable",
+    "listoverridetable", "rsidtbl", "generator", "xmlnstbl", "themedata",
+    "colorschememapping", "latentstyles", "datastore", "fldinst", "filetbl", "revtbl",
+    "pgdsctbl", "mmathPr", "operator", "author", "title", "comment",
+];
+
+struct Parser {
+    paragraphs: Vec<Vec<Run>>,
+    current: Vec<Run>,
+    state: GroupState,
+    stack: Vec<GroupState>,
+    encoding: &'static Encoding,
+    pending_bytes: Vec<u8>,
+    skip_fallback: usize,
+}
+
+impl Parser {
+    fn new() -> Self {
+        Self {
+            paragraphs: Vec::new(),
+            current: Vec::new(),
+            state: GroupState::default(),
+            stack: Vec::new(),
+            encoding: encoding_rs::WINDOWS_1252,
+            pending_bytes: Vec::new(),
+            skip_fallback: 0,
+        }
+    }
+
+    fn push_text(&mut self, text: &str) {
+        if self.state.skip || text.is_empty() {
+            return;
+        }
+        let (bold, italic, underline) = (self.state.bold, self.state.italic, self.state.underline);
+        match self.current.last_mut() {
+            Some(run) if run.bold == bold && run.italic == italic && run.underline == underline => {
+                run.text.push_str(text);
+            }
+            _ => self.current.push(Run { text: text.to_string(), bold, italic, underline }),
+        }
+    }
+
+    fn push_char(&mut self, c: char) {
+        if self.skip_fallback > 0 {
+            self.skip_fallback -= 1;
+            return;
+        }
+        let mut buf = [0u8; 4];
+        self.push_text(c.encode_utf8(&mut buf));
+    }
+
+    /// 按文档代码页解码累积的 \'hh 字节
+    fn flush_bytes(&mut self) {
+        if self.pending_bytes.is_empty() {
+            return;
+        }
+        let bytes = std::mem::take(&mut self.pending_bytes);
+        let (decoded, _, _) = self.encoding.decode(&bytes);
+        let decoded = decoded.into_owned();
+        self.push_text(&decoded);
+    }
+
+    fn end_paragraph(&mut self) {
+        if self.state.skip {
+            return;
+        }
+        let para = std::mem::take(&mut self.current);
+        self.paragraphs.push(para);
+    }
+
+    fn control_word(&mut self, word: &str, param: Option<i32>) {
+        let on = param.is_none_or(|p| p != 0);
+        match word {
+            "par" | "sect" | "page" | "row" => self.end_paragraph(),
+            "line" => self.push_text("\n"),
+            "tab" | "cell" => self.push_text("\t"),
+            "b" => self.state.bold = on,
+            "i" => self.state.italic = on,
+            "ul" => self.state.underline = on,
+            "ulnone" => self.state.underline = false,
+            "plain" => {
+                self.state.bold = false;
+                self.state.italic = false;
+                self.state.underline = false;
+            }
+            "ansicpg" => {
+                if let Some(cp) = param {
+                    self.encoding = encoding_for_codepage(cp);
+                }
+            }
+            "uc" => self.state.uc = param.unwrap_or(1).max(0) as usize,
+            "u" => {
+                if let Some(mut code) = param {
+                    if code < 0 { code += 65536; }
+                    let c = char::from_u32(code as u32).unwrap_or('\u{FFFD}');
+                    let mut buf = [0u8; 4];
+                    self.push_text(c.encode_utf8(&mut buf));
+                    self.skip_fallback = self.state.uc;
+                }
+            }
+            "emdash" => self.push_text("\u{2014}"),
+            "endash" => self.push_text("\u{2013}"),
+            "bullet" => self.push_text("\u{2022}"),
+            "lquote" => self.push_text("\u{2018}"),
+            "rquote" => self.push_text("\u{2019}"),
+            "ldblquote" => self.push_text("\u{201C}"),
+            "rdblquote" => self.push_text("\u{201D}"),
+            "emspace" | "enspace" | "qmspace" => self.push_text(" "),
+            w if SKIP_DESTINATIONS.contains(&w) => self.state.skip = true,
+            _ => {}
+        }
+    }
+
+    fn finish(mut self) -> Vec<Vec<Run>> {
+        self.flush_bytes();
+        if !self.current.is_empty() {
+            let para = std::mem::take(&mut self.current);
+            self.paragraphs.push(para);
+        }
+        self.paragraphs
+    }
+}
+
+fn encoding_for_codepage(cp: i32) -> &'static Encoding {
+    match cp {
+        874 => encoding_rs::WINDOWS_874,
+        932 => encoding_rs::SHIFT_JIS,
+        936 => encoding_rs::GBK,
+        949 => encoding_rs::EUC_KR,
+        950 => encoding_rs::BIG5,
+        1250 => encoding_rs::WINDOWS_1250,
+        1251 => encoding_rs::WINDOWS_1251,
+        1253 => encoding_rs::WINDOWS_1253,
+        1254 => encoding_rs::WINDOWS_1254,
+        1255 => encoding_rs::WINDOWS_1255,
+        1256 => encoding_rs::WINDOWS_1256,
+        1257 => encoding_rs::WINDOWS_1257,
+        1258 => encoding_rs::WINDOWS_1258,
+        10000 => encoding_rs::MACINTOSH,
+        65001 => encoding_rs::UTF_8,
+        _ => encoding_rs::WINDOWS_1252,
+    }
+}
+
+fn parse(rtf: &str) -> Vec<Vec<Run>> {
+    let mut parser = Parser::new();
+    let chars: Vec<char> = rtf.chars().collect();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        // \'hh 之外的任何 token 都会结束一段代码页字节序列
+        let is_hex_escape = c == '\\' && chars.get(i + 1) == Some(&'\'');
+        if !is_hex_escape {
+            parser.flush_bytes();
+        }
+
+        match c {
+            '{' => {
+                parser.stack.push(parser.state);
+                i += 1;
+            }
+            '}' => {
+                if let Some(state) = parser.stack.pop() {
+                    parser.state = state;
+                }
+                i += 1;
+            }
+            '\\' => {
+                i += 1;
+                let Some(&next) = chars.get(i) else { break };
+                if next.is_ascii_alphabetic() {
+                    let start = i;
+                    while i < chars.len() && chars[i].is_ascii_alphabetic() { i += 1; }
+                    let word: String = chars[start..i].iter().collect();
+
+                    let num_start = i;
+                    if i < chars.len() && chars[i] == '-' { i += 1; }
+                    while i < chars.len() && chars[i].is_ascii_digit() { i += 1; }
+                    let param = chars[num_start..i].iter().collect::<String>().parse::<i32>().ok();
+
+                    // 控制字后的单个空格是分隔符
+                    if i < chars.len() && chars[i] == ' ' { i += 1; }
+                    parser.control_word(&word, param);
+                } else {
+                    i += 1;
+                    match next {
+                        '\'' => {
+                            let hex: String = chars.iter().skip(i).take(2).collect();
+                            i += hex.chars().count();
+                            if let Ok(byte) = u8::from_str_radix(&hex, 16) {
+                                if parser.skip_fallback > 0 {
+                                    parser.skip_fallback -= 1;
+                                } else if !parser.state.skip {
+                                    parser.pending_bytes.push(byte);
+                                }
+                            }
+                        }
+                        '*' => parser.state.skip = true,
+                        '~' => parser.push_char('\u{00A0}'),
+                        '_' => parser.push_char('-'),
+                        '-' => {}
+                        '\r' | '\n' => parser.end_paragraph(),
+                        other => parser.push_char(other),
+                    }
+                }
+            }
+            '\r' | '\n' => i += 1,
+            other => {
+                parser.push_char(other);
+                i += 1;
+            }
+        }
+    }
+
+    parser.finish()
+}
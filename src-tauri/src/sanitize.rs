@@ -0,0 +1,45 @@
+//! HTML 预览净化：基于白名单移除脚本、事件属性、样式和危险链接，
+//! 远程图片替换为占位图，保证预览时不会发起任何网络请求
+
+use ammonia::{Builder, UrlRelative};
+use std::borrow::Cow;
+
+/// 远程 / 外部图片的占位图 (内联 SVG，不产生网络请求)
+pub const IMAGE_PLACEHOLDER: &str = "data:image/svg+xml;base64,PHN2ZyB4bWxucz0iaHR0cDovL3d3dy53My5vcmcvMjAwMC9zdmciIHdpZHRoPSI0OCIgaGVpZ2h0PSIzMiIgdmlld0JveD0iMCAwIDQ4IDMyIj48cmVjdCB3aWR0aD0iNDgiIGhlaWdodD0iMzIiIHJ4PSI0IiBmaWxsPSIjZTVlN2ViIi8+PHBhdGggZD0iTTggMjRsOC0xMCA2IDcgNC01IDYgOHoiIGZpbGw9IiM5Y2EzYWYiLz48L3N2Zz4=";
+
+/// 允许直接内联显示的图片类型
+const INLINE_IMAGE_PREFIXES: &[&str] = &[
+    "data:image/png", "data:image/jpeg", "data:image/gif", "data:image/webp", "data:image/bmp",
+];
+
+/// 净化 HTML，返回可以安全嵌入预览的片段
+pub fn clean_html(html: &str) -> String {
+    builder().clean(html).to_string()
+}
+
+fn builder() -> Builder<'static> {
+    let mut builder = Builder::default();
+    builder
+        .url_schemes(["http", "https", "mailto", "data"].into_iter().collect())
+        .url_relative(UrlRelative::Deny)
+        .link_rel(Some("noopener noreferrer nofollow"))
+        .attribute_filter(filter_attribute);
+    builder
+}
+
+fn filter_attribute<'u>(element: &str, attribute: &str, value: &'u str) -> Option<Cow<'u, str>> {
+    let lower = value.trim().to_ascii_lowercase();
+    match (element, attribute) {
+        // 只允许内联的位图，其余图片一律用占位图代替
+        ("img", "src") => {
+            if INLINE_IMAGE_PREFIXES.iter().any(|p| lower.starts_with(p)) {
+                Some(Cow::Borrowed(value))
+            } else {
+                Some(Cow::Borrowed(IMAGE_PLACEHOLDER))
+            }
+        }
+        // data: 只用于图片
+        (_, "href" | "cite") if lower.starts_with("data:") => None,
+        _ => Some(Cow::Borrowed(value)),
+    }
+}
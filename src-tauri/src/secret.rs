@@ -0,0 +1,219 @@
+//! 敏感内容检测：API 密钥、JWT、私钥、信用卡号和高熵密码
+//!
+//! 命中的文本记录会被标记为敏感：列表中遮盖预览、不进入全文索引，并在一段时间后自动删除
+
+use regex::Regex;
+use std::sync::LazyLock;
+
+use crate::classify::{
+    CodeDetector, Detector, EmailDetector, IpDetector, NumberDetector, PathDetector, TimestampDetector, UrlDetector,
+    UuidDetector,
+};
+
+/// 敏感内容的类型，名称会写入数据库
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecretKind {
+    PrivateKey,
+    AwsKey,
+    Jwt,
+    ApiToken,
+    Credential, // password=... 之类的赋值
+    CreditCard,
+    Password,   // 没有明显格式，但字符种类多、熵高的单个词
+    Manual,     // 用户手动标记
+}
+
+const ALL_KINDS: &[SecretKind] = &[
+    SecretKind::PrivateKey,
+    SecretKind::AwsKey,
+    SecretKind::Jwt,
+    SecretKind::ApiToken,
+    SecretKind::Credential,
+    SecretKind::CreditCard,
+    SecretKind::Password,
+    SecretKind::Manual,
+];
+
+impl SecretKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SecretKind::PrivateKey => "private_key",
+            SecretKind::AwsKey => "aws_key",
+            SecretKind::Jwt => "jwt",
+            SecretKind::ApiToken => "api_token",
+            SecretKind::Credential => "credential",
+            SecretKind::CreditCard => "credit_card",
+            SecretKind::Password => "password",
+            SecretKind::Manual => "manual",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        ALL_KINDS.iter().copied().find(|kind| kind.as_str() == name)
+    }
+
+    /// 遮盖预览中显示的名称
+    pub fn label(&self) -> &'static str {
+        match self {
+            SecretKind::PrivateKey => "私钥",
+            SecretKind::AwsKey => "AWS 密钥",
+            SecretKind::Jwt => "JWT",
+            SecretKind::ApiToken => "API 令牌",
+            SecretKind::Credential => "凭据",
+            SecretKind::CreditCard => "信用卡号",
+            SecretKind::Password => "密码",
+            SecretKind::Manual => "敏感内容",
+        }
+    }
+}
+
+/// 密码规则只检查该长度范围内的单个词
+const PASSWORD_LEN: std::ops::RangeInclusive<usize> = 12..=64;
+
+/// 密码规则要求的最低香农熵 (比特 / 字符)
+const MIN_PASSWORD_ENTROPY: f64 = 3.5;
+
+static PRIVATE_KEY_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"-----BEGIN (?:[A-Z0-9]+ )*PRIVATE KEY(?: BLOCK)?-----").unwrap()
+});
+
+static AWS_KEY_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(?:AKIA|ASIA|AGPA|AIDA|AROA)[0-9A-Z]{16}\b|(?i)aws_secret_access_key\s*[:=]\s*\S{40}").unwrap()
+});
+
+static JWT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\beyJ[A-Za-z0-9_-]{8,}\.eyJ[A-Za-z0-9_-]{8,}\.[A-Za-z0-9_-]{16,}").unwrap()
+});
+
+static API_TOKEN_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(concat!(
+        r"\bgh[pousr]_[A-Za-z0-9]{36,}\b",         // GitHub
+        r"|\bgithub_pat_[A-Za-z0-9_]{22,}\b",
+        r"|\bglpat-[A-Za-z0-9_-]{20,}\b",          // GitLab
+        r"|\bxox[abposr]-[A-Za-z0-9-]{10,}\b",     // Slack
+        r"|\b[sr]k_(?:live|test)_[A-Za-z0-9]{16,}\b", // Stripe
+        r"|\bsk-(?:proj-|ant-)?[A-Za-z0-9_-]{20,}\b", // OpenAI / Anthropic
+        r"|\bAIza[0-9A-Za-z_-]{35}\b",             // Google
+        r"|\bnpm_[A-Za-z0-9]{36}\b",
+    ))
+    .unwrap()
+});
+
+static CREDENTIAL_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)(?:api[_-]?key|secret(?:[_-]?key)?|access[_-]?token|auth[_-]?token|password|passwd|pwd)\s*[:=]\s*["']?[^\s"']{8,}"#).unwrap()
+});
+
+/// 函数调用形状的代码片段，如 Vec::with_capacity(16)
+static CALL_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^[A-Za-z_][\w.:<>]*\(.*\);?$").unwrap()
+});
+
+static CARD_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b\d(?:[ -]?\d){12,18}\b").unwrap()
+});
+
+/// 检测文本中的敏感内容，返回第一个命中的类型
+pub fn detect(text: &str) -> Option<SecretKind> {
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    if PRIVATE_KEY_RE.is_match(text) {
+        return Some(SecretKind::PrivateKey);
+    }
+    if AWS_KEY_RE.is_match(text) {
+        return Some(SecretKind::AwsKey);
+    }
+    if JWT_RE.is_match(text) {
+        return Some(SecretKind::Jwt);
+    }
+    if API_TOKEN_RE.is_match(text) {
+        return Some(SecretKind::ApiToken);
+    }
+    if CREDENTIAL_RE.is_match(text) {
+        return Some(SecretKind::Credential);
+    }
+    if CARD_RE.find_iter(text).any(|m| is_card_number(m.as_str())) {
+        return Some(SecretKind::CreditCard);
+    }
+    if looks_like_password(text) {
+        return Some(SecretKind::Password);
+    }
+    None
+}
+
+/// 去掉分隔符后为 13-19 位且通过 Luhn 校验
+fn is_card_number(candidate: &str) -> bool {
+    let digits: Vec<u32> = candidate.chars().filter_map(|c| c.to_digit(10)).collect();
+    if !(13..=19).contains(&digits.len()) || digits.iter().all(|&d| d == digits[0]) {
+        return false;
+    }
+    luhn(&digits)
+}
+
+/// Luhn 校验：从右往左每隔一位乘 2
+pub fn luhn(digits: &[u32]) -> bool {
+    let sum: u32 = digits.iter().rev().enumerate().map(|(i, &d)| {
+        if i % 2 == 1 {
+            let doubled = d * 2;
+            if doubled > 9 { doubled - 9 } else { doubled }
+        } else {
+            d
+        }
+    }).sum();
+    sum.is_multiple_of(10)
+}
+
+/// 单个词，包含数字且至少三类字符，熵足够高；排除链接、路径、时间戳、代码等有明确格式的值
+fn looks_like_password(text: &str) -> bool {
+    if !PASSWORD_LEN.contains(&text.chars().count()) || text.chars().any(char::is_whitespace) {
+        return false;
+    }
+    let structured: [&dyn Detector; 8] = [
+        &UrlDetector, &EmailDetector, &PathDetector, &UuidDetector, &IpDetector, &TimestampDetector, &NumberDetector,
+        &CodeDetector,
+    ];
+    if CALL_RE.is_match(text) || structured.iter().any(|detector| detector.detect(text)) {
+        return false;
+    }
+    let has_digit = text.chars().any(|c| c.is_ascii_digit());
+    let classes = [
+        text.chars().any(|c| c.is_lowercase()),
+        text.chars().any(|c| c.is_uppercase()),
+        has_digit,
+        text.chars().any(|c| !c.is_alphanumeric()),
+    ];
+    has_digit && classes.iter().filter(|&&c| c).count() >= 3 && shannon_entropy(text) >= MIN_PASSWORD_ENTROPY
+}
+
+/// 香农熵 (比特 / 字符)
+pub fn shannon_entropy(text: &str) -> f64 {
+    let mut counts = std::collections::HashMap::new();
+    let mut total = 0usize;
+    for c in text.chars() {
+        *counts.entry(c).or_insert(0usize) += 1;
+        total += 1;
+    }
+    if total == 0 {
+        return 0.0;
+    }
+    counts.values().map(|&n| {
+        let p = n as f64 / total as f64;
+        -p * p.log2()
+    }).sum()
+}
+
+/// 敏感记录在列表中显示的预览，信用卡号保留末四位
+pub fn mask(kind: Option<&str>, text: &str) -> String {
+    let kind = kind.and_then(SecretKind::from_name).unwrap_or(SecretKind::Manual);
+    match kind {
+        SecretKind::CreditCard => {
+            let digits: String = CARD_RE.find_iter(text)
+                .find(|m| is_card_number(m.as_str()))
+                .map(|m| m.as_str().chars().filter(|c| c.is_ascii_digit()).collect())
+                .unwrap_or_default();
+            format!("[{}] •••• {}", kind.label(), &digits[digits.len().saturating_sub(4)..])
+        }
+        _ => format!("[{}] ••••••••", kind.label()),
+    }
+}
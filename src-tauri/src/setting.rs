@@ -0,0 +1,198 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use crate::conceal::ConcealedPolicy;
+
+const SETTINGS_FILE: &str = "settings.json";
+
+/// 数据目录：$HOME/Documents/pastee
+pub fn data_dir() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("Documents")
+        .join("pastee")
+}
+
+/// 剪贴板监听线程和界面共享的自定义格式白名单，修改设置后立即生效
+pub type SharedFormats = Arc<Mutex<Vec<CustomFormat>>>;
+
+/// 需要原样捕获的自定义剪贴板格式
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CustomFormat {
+    pub mime: String,     // MIME 类型，支持结尾通配符，如 "application/x-vnd.*"
+    pub max_bytes: usize, // 单条内容的大小上限，超过则不捕获
+}
+
+impl CustomFormat {
+    pub fn new(mime: &str, max_bytes: usize) -> Self {
+        Self { mime: mime.to_string(), max_bytes }
+    }
+
+    pub fn matches(&self, mime: &str) -> bool {
+        match self.mime.strip_suffix('*') {
+            Some(prefix) => mime.starts_with(prefix),
+            None => self.mime == mime,
+        }
+    }
+}
+
+/// 隐私黑名单规则匹配的字段
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleTarget {
+    /// 应用名 (X11 上为 WM_CLASS 类名)
+    App,
+    /// 窗口标题
+    Title,
+}
+
+/// 隐私黑名单规则的匹配方式，exact 和 glob 不区分大小写
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchKind {
+    Exact,
+    Glob,
+    Regex,
+}
+
+/// 隐私黑名单规则：前台应用命中时不捕获剪贴板
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BlockRule {
+    pub target: RuleTarget,
+    pub kind: MatchKind,
+    pub pattern: String,
+}
+
+impl BlockRule {
+    pub fn app(kind: MatchKind, pattern: &str) -> Self {
+        Self { target: RuleTarget::App, kind, pattern: pattern.to_string() }
+    }
+
+    pub fn title(kind: MatchKind, pattern: &str) -> Self {
+        Self { target: RuleTarget::Title, kind, pattern: pattern.to_string() }
+    }
+}
+
+/// 文本去重 / 颜色识别时使用的规范化方式，原文始终按原样保存
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TextNormalization {
+    /// 逐字节比较
+    Exact,
+    /// 忽略首尾空白
+    #[default]
+    Trim,
+    /// 忽略首尾空白，并把连续空白视为一个空格
+    CollapseWhitespace,
+}
+
+impl TextNormalization {
+    /// 计算用于去重的规范化键
+    pub fn apply<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        match self {
+            Self::Exact => Cow::Borrowed(text),
+            Self::Trim => Cow::Borrowed(text.trim()),
+            Self::CollapseWhitespace => {
+                Cow::Owned(text.split_whitespace().collect::<Vec<_>>().join(" "))
+            }
+        }
+    }
+}
+
+/// 用户设置，保存在数据目录下的 settings.json
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Settings {
+    /// 自定义格式白名单
+    pub custom_formats: Vec<CustomFormat>,
+    /// 超过该字节数的文本压缩后存放到 texts 目录
+    pub large_text_threshold: usize,
+    /// 文本去重键的规范化方式
+    pub text_normalization: TextNormalization,
+    /// 是否对图片做 OCR
+    pub ocr_enabled: bool,
+    /// tesseract 识别语言，如 "eng+chi_sim"；修改后重启生效
+    pub ocr_languages: Option<String>,
+    /// 是否识别图片中的二维码 / 条码
+    pub barcode_enabled: bool,
+    /// 隐私黑名单
+    pub privacy_blocklist: Vec<BlockRule>,
+    /// 带密码管理器隐藏标记的内容如何处理 (只支持 Linux 上的 KDE 标记)
+    pub concealed_policy: ConcealedPolicy,
+    /// 临时记录在内存中保留的秒数
+    pub ephemeral_ttl_secs: u64,
+    /// 是否检测密钥、信用卡号等敏感内容
+    pub secret_detection: bool,
+    /// 敏感记录保留的秒数，到期后自动删除 (置顶的除外)
+    pub sensitive_ttl_secs: u64,
+    /// 一次性记录粘贴回剪贴板后保留的秒数，到期后删除记录并清空剪贴板
+    pub one_time_grace_secs: u64,
+    /// 口令加密的存储空闲多少分钟后自动锁定，0 表示不自动锁定；密钥在系统钥匙串中时不自动锁定
+    pub auto_lock_minutes: u64,
+    /// 保险库解锁后保持的秒数
+    pub vault_unlock_secs: u64,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            custom_formats: vec![
+                CustomFormat::new("image/svg+xml", 5 * 1024 * 1024),
+                CustomFormat::new("text/uri-list", 1024 * 1024),
+                CustomFormat::new("application/x-vnd.*", 16 * 1024 * 1024),
+            ],
+            large_text_threshold: 256 * 1024,
+            text_normalization: TextNormalization::default(),
+            ocr_enabled: true,
+            ocr_languages: None,
+            barcode_enabled: true,
+            privacy_blocklist: vec![
+                // 密码管理器
+                BlockRule::app(MatchKind::Exact, "1Password"),
+                BlockRule::app(MatchKind::Glob, "*keepass*"),
+                BlockRule::app(MatchKind::Exact, "Bitwarden"),
+                BlockRule::app(MatchKind::Exact, "Seahorse"),
+                // 正在执行 sudo 的终端
+                BlockRule::title(MatchKind::Regex, r"(?i)\bsudo\b"),
+            ],
+            concealed_policy: ConcealedPolicy::default(),
+            ephemeral_ttl_secs: 30,
+            secret_detection: true,
+            sensitive_ttl_secs: 5 * 60,
+            one_time_grace_secs: 30,
+            auto_lock_minutes: 15,
+            vault_unlock_secs: 2 * 60,
+        }
+    }
+}
+
+impl Settings {
+    /// 读取设置；文件不存在或解析失败时使用默认值
+    pub fn load<P: AsRef<Path>>(data_dir: P) -> Self {
+        let path = data_dir.as_ref().join(SETTINGS_FILE);
+        match fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json).unwrap_or_else(|e| {
+                eprintln!("⚠️ 设置文件解析失败，使用默认设置: {}", e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, data_dir: P) -> Result<()> {
+        let data_dir = data_dir.as_ref();
+        fs::create_dir_all(data_dir).context("Failed to create data dir")?;
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(data_dir.join(SETTINGS_FILE), json).context("Failed to write settings")?;
+        Ok(())
+    }
+
+    /// 查找匹配的自定义格式规则
+    pub fn custom_format_for(&self, mime: &str) -> Option<&CustomFormat> {
+        self.custom_formats.iter().find(|f| f.matches(mime))
+    }
+}
@@ -0,0 +1,174 @@
+//! 来源应用识别：记录复制内容时处于前台的应用
+
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+/// 复制内容时处于前台的应用
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SourceApp {
+    pub name: Option<String>,     // 应用名 (X11 上为 WM_CLASS 的类名，如 "firefox")
+    pub title: Option<String>,    // 窗口标题
+    pub exe_path: Option<String>, // 可执行文件路径
+}
+
+impl SourceApp {
+    pub fn named(name: &str) -> Self {
+        Self { name: Some(name.to_string()), ..Self::default() }
+    }
+
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none() && self.title.is_none() && self.exe_path.is_none()
+    }
+}
+
+/// 获取当前前台应用
+pub trait AppDetector: Send {
+    /// 无法识别时返回 None
+    fn active_app(&self) -> Option<SourceApp>;
+}
+
+/// 根据当前平台选择默认实现
+pub fn default_detector() -> Box<dyn AppDetector> {
+    #[cfg(target_os = "linux")]
+    {
+        if std::env::var_os("DISPLAY").is_some() {
+            if let Some(detector) = X11Detector::connect() {
+                return Box::new(detector);
+            }
+        }
+    }
+    Box::new(NullDetector)
+}
+
+/// 不支持的平台
+pub struct NullDetector;
+
+impl AppDetector for NullDetector {
+    fn active_app(&self) -> Option<SourceApp> {
+        None
+    }
+}
+
+/// 返回预先设定的应用，用于测试
+#[derive(Default)]
+pub struct FakeDetector {
+    app: Mutex<Option<SourceApp>>,
+}
+
+impl FakeDetector {
+    pub fn new(app: Option<SourceApp>) -> Self {
+        Self { app: Mutex::new(app) }
+    }
+
+    /// 切换前台应用
+    pub fn set(&self, app: Option<SourceApp>) {
+        if let Ok(mut current) = self.app.lock() {
+            *current = app;
+        }
+    }
+}
+
+impl AppDetector for FakeDetector {
+    fn active_app(&self) -> Option<SourceApp> {
+        self.app.lock().ok()?.clone()
+    }
+}
+
+/// X11：读取根窗口的 _NET_ACTIVE_WINDOW，再读该窗口的 WM_CLASS / _NET_WM_NAME / _NET_WM_PID
+#[cfg(target_os = "linux")]
+pub struct X11Detector {
+    conn: x11rb::rust_connection::RustConnection,
+    root: u32,
+    atoms: x11_atoms::Atoms,
+}
+
+#[cfg(target_os = "linux")]
+impl X11Detector {
+    /// 无法连接 X 服务器时返回 None
+    pub fn connect() -> Option<Self> {
+        use x11rb::connection::Connection;
+
+        let (conn, screen_num) = x11rb::connect(None).ok()?;
+        let root = conn.setup().roots[screen_num].root;
+        let atoms = x11_atoms::Atoms::new(&conn).ok()?;
+        Some(Self { conn, root, atoms })
+    }
+
+    fn property(&self, window: u32, property: u32, kind: u32) -> Option<Vec<u8>> {
+        use x11rb::protocol::xproto::ConnectionExt;
+
+        let reply = self.conn
+            .get_property(false, window, property, kind, 0, u32::MAX / 4)
+            .ok()?
+            .reply()
+            .ok()?;
+        (!reply.value.is_empty()).then_some(reply.value)
+    }
+
+    fn property32(&self, window: u32, property: u32, kind: u32) -> Option<u32> {
+        let value = self.property(window, property, kind)?;
+        Some(u32::from_ne_bytes(value.get(..4)?.try_into().ok()?))
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl AppDetector for X11Detector {
+    fn active_app(&self) -> Option<SourceApp> {
+        use x11rb::protocol::xproto::AtomEnum;
+
+        let window = self.property32(self.root, self.atoms.net_active_window, AtomEnum::WINDOW.into())?;
+        if window == 0 {
+            return None;
+        }
+
+        // WM_CLASS 为 "实例名\0类名\0"，取类名
+        let name = self.property(window, AtomEnum::WM_CLASS.into(), AtomEnum::STRING.into())
+            .and_then(|value| {
+                let parts: Vec<String> = value.split(|&b| b == 0)
+                    .filter(|s| !s.is_empty())
+                    .map(|s| String::from_utf8_lossy(s).to_string())
+                    .collect();
+                parts.last().cloned()
+            });
+        let title = self.property(window, self.atoms.net_wm_name, self.atoms.utf8_string)
+            .or_else(|| self.property(window, AtomEnum::WM_NAME.into(), AtomEnum::STRING.into()))
+            .map(|value| String::from_utf8_lossy(&value).to_string());
+        let exe_path = self.property32(window, self.atoms.net_wm_pid, AtomEnum::CARDINAL.into())
+            .and_then(|pid| std::fs::read_link(format!("/proc/{}/exe", pid)).ok())
+            .map(|path| path.to_string_lossy().to_string());
+
+        let app = SourceApp { name, title, exe_path };
+        (!app.is_empty()).then_some(app)
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod x11_atoms {
+    use x11rb::protocol::xproto::{Atom, ConnectionExt};
+
+    pub struct Atoms {
+        pub net_active_window: Atom,
+        pub net_wm_name: Atom,
+        pub net_wm_pid: Atom,
+        pub utf8_string: Atom,
+    }
+
+    impl Atoms {
+        pub fn new(conn: &impl ConnectionExt) -> anyhow::Result<Self> {
+            let intern = |name: &str| -> anyhow::Result<Atom> {
+                Ok(conn.intern_atom(false, name.as_bytes())?.reply()?.atom)
+            };
+            Ok(Self {
+                net_active_window: intern("_NET_ACTIVE_WINDOW")?,
+                net_wm_name: intern("_NET_WM_NAME")?,
+                net_wm_pid: intern("_NET_WM_PID")?,
+                utf8_string: intern("UTF8_STRING")?,
+            })
+        }
+    }
+}
@@ -0,0 +1,76 @@
+//! SVG：识别复制的 SVG 源码，用 resvg (纯 Rust) 渲染为位图
+
+use anyhow::{anyhow, Result};
+use regex::Regex;
+use resvg::tiny_skia::{Pixmap, Transform};
+use resvg::usvg::{self, fontdb, ImageHrefResolver};
+use std::sync::{Arc, LazyLock};
+
+/// 渲染结果的长边不小于该值 (小图标放大后再保存)
+const MIN_SIZE: f32 = 512.0;
+
+/// 渲染结果的长边不超过该值
+const MAX_SIZE: f32 = 4096.0;
+
+/// 允许出现在 <svg> 之前的内容：XML 声明、注释、DOCTYPE
+static SVG_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?s)^\s*(?:<\?xml[^>]*\?>\s*)?(?:<!--.*?-->\s*)*(?:<!DOCTYPE\s+svg[^>]*>\s*)?(?:<!--.*?-->\s*)*<svg[\s>/]").unwrap()
+});
+
+/// 系统字体只加载一次，渲染 <text> 时使用
+static FONTS: LazyLock<Arc<fontdb::Database>> = LazyLock::new(|| {
+    let mut db = fontdb::Database::new();
+    db.load_system_fonts();
+    Arc::new(db)
+});
+
+/// 渲染出的 RGBA 位图 (非预乘 alpha)
+pub struct Rendered {
+    pub width: u32,
+    pub height: u32,
+    pub rgba: Vec<u8>,
+}
+
+/// 文本是否为 SVG 源码
+pub fn is_svg(text: &str) -> bool {
+    let trimmed = text.trim_start_matches('\u{feff}').trim_end();
+    trimmed.ends_with('>') && trimmed.contains("svg") && SVG_RE.is_match(trimmed)
+}
+
+/// 渲染 SVG，外部文件引用一律忽略，只加载内嵌的 data: 图片
+pub fn render(markup: &str) -> Result<Rendered> {
+    let options = usvg::Options {
+        fontdb: FONTS.clone(),
+        image_href_resolver: ImageHrefResolver {
+            resolve_data: ImageHrefResolver::default_data_resolver(),
+            resolve_string: Box::new(|_, _| None),
+        },
+        ..usvg::Options::default()
+    };
+    let tree = usvg::Tree::from_str(markup, &options).map_err(|e| anyhow!("SVG 解析失败: {}", e))?;
+
+    let size = tree.size();
+    let longest = size.width().max(size.height());
+    let scale = if longest < MIN_SIZE {
+        MIN_SIZE / longest
+    } else if longest > MAX_SIZE {
+        MAX_SIZE / longest
+    } else {
+        1.0
+    };
+    let width = (size.width() * scale).ceil().max(1.0) as u32;
+    let height = (size.height() * scale).ceil().max(1.0) as u32;
+
+    let mut pixmap = Pixmap::new(width, height).ok_or_else(|| anyhow!("无法创建 {}x{} 画布", width, height))?;
+    resvg::render(&tree, Transform::from_scale(scale, scale), &mut pixmap.as_mut());
+
+    let rgba = pixmap
+        .pixels()
+        .iter()
+        .flat_map(|p| {
+            let c = p.demultiply();
+            [c.red(), c.green(), c.blue(), c.alpha()]
+        })
+        .collect();
+    Ok(Rendered { width, height, rgba })
+}
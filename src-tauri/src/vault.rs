@@ -0,0 +1,68 @@
+//! 保险库：少量置顶的机密 (如测试环境的账号) 用单独的口令逐条加密，不出现在列表预览和搜索结果中
+//!
+//! 读取前需要解锁，解锁状态到期后自动失效
+
+use anyhow::{anyhow, Result};
+use chrono::{Duration, Utc};
+use serde::Serialize;
+
+use crate::crypto::StoreKey;
+
+/// 首次解锁时用口令加密这段内容保存，之后解锁时解密它来校验口令
+pub const VERIFIER_PLAINTEXT: &[u8] = b"pastee-vault";
+
+/// 保险库列表中的条目，只有名称，不含内容
+#[derive(Debug, Clone, Serialize)]
+pub struct VaultItem {
+    pub id: i64,
+    pub label: Option<String>,
+    pub created_at: i64,
+}
+
+/// 解锁状态：密钥只保存在内存中，到期或手动锁定后丢弃
+#[derive(Default)]
+pub struct VaultSession {
+    key: Option<StoreKey>,
+    expires_at: Option<i64>, // 微秒时间戳
+}
+
+impl VaultSession {
+    /// 解锁 `ttl` 时长，返回到期时间；时长超出范围时返回错误且保持原状态
+    pub fn unlock(&mut self, key: StoreKey, ttl: Duration) -> Result<i64> {
+        let expires_at = Utc::now()
+            .checked_add_signed(ttl)
+            .ok_or_else(|| anyhow!("保险库解锁时长超出范围"))?
+            .timestamp_micros();
+        self.key = Some(key);
+        self.expires_at = Some(expires_at);
+        Ok(expires_at)
+    }
+
+    /// 丢弃密钥，之前处于解锁状态时返回 true
+    pub fn lock(&mut self) -> bool {
+        self.expires_at = None;
+        self.key.take().is_some()
+    }
+
+    /// 解锁到期时丢弃密钥，状态发生变化时返回 true
+    pub fn refresh(&mut self) -> bool {
+        match self.expires_at {
+            Some(at) if at <= Utc::now().timestamp_micros() => {
+                println!("🔐 保险库解锁已到期");
+                self.lock()
+            }
+            _ => false,
+        }
+    }
+
+    /// 未到期的密钥
+    pub fn key(&mut self) -> Option<&StoreKey> {
+        self.refresh();
+        self.key.as_ref()
+    }
+
+    pub fn expires_at(&mut self) -> Option<i64> {
+        self.refresh();
+        self.expires_at
+    }
+}
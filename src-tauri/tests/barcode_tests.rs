@@ -0,0 +1,170 @@
+/// 二维码 / 条码识别与二维码生成测试 (识别使用假识别器)
+
+mod common;
+
+use anyhow::Result;
+use image::{Rgba, RgbaImage};
+use pastee_lib::barcode::{self, CodeDecoder, DecodedCode, ZbarCli};
+use pastee_lib::persist::{ClipFilter, ClipType, Storage};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use common::{create_test_dir, get_test_data_dir};
+
+struct FakeDecoder;
+
+impl CodeDecoder for FakeDecoder {
+    fn name(&self) -> &'static str {
+        "fake"
+    }
+
+    fn decode(&self, _image: &Path) -> Result<Vec<DecodedCode>> {
+        Ok(vec![
+            DecodedCode { symbology: "QR-Code".to_string(), text: "https://example.com/pair?code=42".to_string() },
+            DecodedCode { symbology: "EAN-13".to_string(), text: "9780201379624".to_string() },
+        ])
+    }
+}
+
+#[test]
+fn test_parse_zbar_xml() {
+    let xml = r#"<barcodes xmlns='http://zbar.sourceforge.net/2008/barcode'>
+<source href='shot.png'>
+<index num='0'>
+<symbol type='QR-Code' quality='1' orientation='UP'><polygon points='+6,6 +6,50 +50,50 +50,6'/><data><![CDATA[WIFI:S:home;T:WPA;P:secret;;]]></data></symbol>
+<symbol type='EAN-13' quality='74' orientation='UP'><data><![CDATA[9780201379624]]></data></symbol>
+<symbol type='QR-Code' quality='1'><data format='base64' length='5'><![CDATA[aGVs
+bG8=]]></data></symbol>
+</index>
+</source>
+</barcodes>"#;
+    let codes = barcode::parse_zbar_xml(xml);
+    assert_eq!(codes.len(), 3);
+    assert_eq!(codes[0], DecodedCode { symbology: "QR-Code".to_string(), text: "WIFI:S:home;T:WPA;P:secret;;".to_string() });
+    assert_eq!(codes[1].symbology, "EAN-13");
+    assert_eq!(codes[1].tag(), "barcode");
+    assert_eq!(codes[2].text, "hello");
+    assert!(barcode::parse_zbar_xml("").is_empty());
+}
+
+#[test]
+fn test_decoded_codes_become_linked_clips() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let storage = Mutex::new(Storage::new(&data_dir).unwrap());
+    storage.lock().unwrap().set_code_decoder(Arc::new(FakeDecoder));
+
+    let img = RgbaImage::from_pixel(8, 8, Rgba([255, 255, 255, 255]));
+    let (image_id, _) = storage.lock().unwrap().add_image(8, 8, img.into_raw()).unwrap();
+    let derived = barcode::process(&storage, image_id).unwrap();
+    assert_eq!(derived.len(), 2);
+
+    let store = storage.lock().unwrap();
+    let items = store.get_recent_filtered(10, 0, &ClipFilter::derived_from(image_id)).unwrap();
+    assert_eq!(items.len(), 2);
+    assert!(items.iter().all(|i| i.source_id == Some(image_id)));
+
+    let url = items.iter().find(|i| i.preview.starts_with("https://")).unwrap();
+    assert!(url.tags.contains(&"qrcode".to_string()));
+    assert!(url.tags.contains(&"url".to_string()));
+    let ean = items.iter().find(|i| i.preview == "9780201379624").unwrap();
+    assert!(ean.tags.contains(&"barcode".to_string()));
+
+    // 按标签也能找到
+    let qr = store.get_recent_filtered(10, 0, &ClipFilter::tag("qrcode")).unwrap();
+    assert_eq!(qr.len(), 1);
+
+    // 删除来源后派生记录保留，关联置空
+    store.delete_record(image_id).unwrap();
+    let items = store.get_recent(10, 0).unwrap();
+    assert_eq!(items.len(), 2);
+    assert!(items.iter().all(|i| i.source_id.is_none()));
+}
+
+#[test]
+fn test_decoded_code_matching_existing_clip() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let storage = Mutex::new(Storage::new(&data_dir).unwrap());
+    storage.lock().unwrap().set_code_decoder(Arc::new(FakeDecoder));
+
+    // 用户先复制过同样的文本
+    let copied = storage.lock().unwrap().add_text("9780201379624".to_string()).unwrap();
+    let img = RgbaImage::from_pixel(8, 8, Rgba([255, 255, 255, 255]));
+    let (image_id, _) = storage.lock().unwrap().add_image(8, 8, img.into_raw()).unwrap();
+    let derived = barcode::process(&storage, image_id).unwrap();
+    assert!(derived.contains(&copied));
+
+    // 已有记录不被标记为派生记录
+    let store = storage.lock().unwrap();
+    let item = store.get_recent(10, 0).unwrap().into_iter().find(|i| i.id == copied).unwrap();
+    assert_eq!(item.source_id, None);
+    assert!(!item.tags.contains(&"barcode".to_string()));
+    let linked = store.get_recent_filtered(10, 0, &ClipFilter::derived_from(image_id)).unwrap();
+    assert_eq!(linked.len(), 1);
+}
+
+#[test]
+fn test_derived_text_matching_newest_clip() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let mut storage = Storage::new(&data_dir).unwrap();
+
+    // 同样的文本是最新的一条记录时同样只去重
+    let img = RgbaImage::from_pixel(8, 8, Rgba([255, 255, 255, 255]));
+    let (image_id, _) = storage.add_image(8, 8, img.into_raw()).unwrap();
+    let copied = storage.add_text("9780201379624".to_string()).unwrap();
+    assert_eq!(storage.add_derived_text(image_id, "9780201379624".to_string(), "barcode").unwrap(), copied);
+
+    let item = storage.get_recent(1, 0).unwrap().remove(0);
+    assert_eq!(item.id, copied);
+    assert_eq!(item.source_id, None);
+    assert!(!item.tags.contains(&"barcode".to_string()));
+
+    // 新内容关联到来源记录
+    let derived = storage.add_derived_text(image_id, "https://example.com".to_string(), "barcode").unwrap();
+    let item = storage.get_recent(1, 0).unwrap().remove(0);
+    assert_eq!(item.id, derived);
+    assert_eq!(item.source_id, Some(image_id));
+    assert!(item.tags.contains(&"barcode".to_string()));
+}
+
+#[test]
+fn test_generate_qr() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let mut storage = Storage::new(&data_dir).unwrap();
+
+    let text_id = storage.add_text("https://example.com".to_string()).unwrap();
+    let (qr_id, thumbnail) = storage.generate_qr(text_id).unwrap();
+    assert!(!thumbnail.is_empty());
+
+    let items = storage.get_recent(1, 0).unwrap();
+    assert_eq!(items[0].id, qr_id);
+    assert_eq!(items[0].content_type, ClipType::Image);
+    assert_eq!(items[0].source_id, Some(text_id));
+
+    let img = image::open(storage.image_file(qr_id).unwrap()).unwrap().to_rgba8();
+    assert!(img.width() >= 512 && img.width() == img.height());
+    // 只有黑白两种颜色，左上角是静区 (白色)
+    assert!(img.pixels().all(|p| p[0] == 0 || p[0] == 255));
+    assert_eq!(img.get_pixel(0, 0)[0], 255);
+
+    // 同样的文本生成同一张图片
+    assert_eq!(storage.generate_qr(text_id).unwrap().0, qr_id);
+    // 图片记录不能生成二维码
+    assert!(storage.generate_qr(qr_id).is_err());
+}
+
+#[test]
+fn test_decoder_disabled_or_missing() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let storage = Mutex::new(Storage::new(&data_dir).unwrap());
+
+    let img = RgbaImage::from_pixel(4, 4, Rgba([0, 0, 0, 255]));
+    let (id, _) = storage.lock().unwrap().add_image(4, 4, img.into_raw()).unwrap();
+    assert!(barcode::process(&storage, id).unwrap().is_empty());
+
+    let decoder = ZbarCli::new().with_program("/nonexistent/zbarimg");
+    assert!(decoder.decode(Path::new("missing.png")).is_err());
+}
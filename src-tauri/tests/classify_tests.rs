@@ -0,0 +1,172 @@
+/// 内容分类测试
+/// 逐个验证各检测器，以及智能标签的存储、搜索和过滤
+
+mod common;
+
+use pastee_lib::classify::{Classifier, Detector};
+use pastee_lib::persist::{Storage, ClipFilter};
+use common::{create_test_dir, get_test_data_dir};
+
+fn tags_of(text: &str) -> Vec<String> {
+    Classifier::default().classify(text)
+}
+
+fn assert_tagged(samples: &[&str], tag: &str) {
+    for sample in samples {
+        assert!(tags_of(sample).contains(&tag.to_string()), "'{}' should be tagged {}", sample, tag);
+    }
+}
+
+fn assert_not_tagged(samples: &[&str], tag: &str) {
+    for sample in samples {
+        assert!(!tags_of(sample).contains(&tag.to_string()), "'{}' should not be tagged {}", sample, tag);
+    }
+}
+
+#[test]
+fn test_url() {
+    assert_tagged(&["https://example.com", "http://a.b/c?d=1#e", "www.rust-lang.org", "  ftp://files.example.org/x  "], "url");
+    assert_not_tagged(&["example", "see https://example.com here", "https://"], "url");
+}
+
+#[test]
+fn test_email() {
+    assert_tagged(&["alice@example.com", "first.last+tag@sub.example.co.uk", "mailto:bob@example.org"], "email");
+    assert_not_tagged(&["alice@", "@example.com", "alice at example.com"], "email");
+}
+
+#[test]
+fn test_phone() {
+    assert_tagged(&["+86 138 0013 8000", "13800138000", "(555) 123-4567", "+1-202-555-0143"], "phone");
+    assert_not_tagged(&["12345", "2024-01-15", "1.2.3.4", "10.20.30.40"], "phone");
+}
+
+#[test]
+fn test_path() {
+    assert_tagged(&["/usr/local/bin", "~/Documents/notes.md", "./src/main.rs", "C:\\Windows\\System32", "\\\\server\\share", "/Users/me/My Documents/a.txt"], "path");
+    assert_not_tagged(&["/", "// comment", "https://example.com/a", "a/b"], "path");
+}
+
+#[test]
+fn test_json() {
+    assert_tagged(&[r#"{"a": 1, "b": [true, null]}"#, "[1, 2, 3]", "{}"], "json");
+    assert_not_tagged(&["{a: 1}", "[1, 2", "\"string\"", "42"], "json");
+}
+
+#[test]
+fn test_xml() {
+    assert_tagged(&["<?xml version=\"1.0\"?><a/>", "<note><to>Tove</to></note>", "<br/>", "<svg:rect width=\"1\"></svg:rect>"], "xml");
+    assert_not_tagged(&["<not closed>", "a < b > c", "<a></b>"], "xml");
+}
+
+#[test]
+fn test_sql() {
+    assert_tagged(&[
+        "SELECT id, name FROM users WHERE id = 1",
+        "select *\nfrom t",
+        "INSERT INTO t (a) VALUES (1)",
+        "UPDATE t SET a = 1",
+        "DELETE FROM t",
+        "CREATE TABLE t (id INTEGER)",
+        "WITH x AS (SELECT 1) SELECT * FROM x",
+    ], "sql");
+    assert_not_tagged(&["select a good name", "please update the doc"], "sql");
+}
+
+#[test]
+fn test_uuid() {
+    assert_tagged(&["550e8400-e29b-41d4-a716-446655440000", "{550E8400-E29B-41D4-A716-446655440000}"], "uuid");
+    assert_not_tagged(&["550e8400-e29b-41d4-a716", "550e8400e29b41d4a716446655440000x"], "uuid");
+}
+
+#[test]
+fn test_ip() {
+    assert_tagged(&["192.168.1.1", "::1", "2001:db8::ff00:42:8329", "10.0.0.0/8", "127.0.0.1:8080", "[::1]:443"], "ip");
+    assert_not_tagged(&["256.1.1.1", "1.2.3", "10.0.0.0/33"], "ip");
+}
+
+#[test]
+fn test_timestamp() {
+    assert_tagged(&["1700000000", "1700000000123"], "timestamp");
+    assert_not_tagged(&["123", "9999999999", "0000000000"], "timestamp");
+}
+
+#[test]
+fn test_number() {
+    assert_tagged(&["42", "-3.14", "1,234,567.89", "6.02e23", "0xFF"], "number");
+    assert_not_tagged(&["1,23", "12a", "1.2.3"], "number");
+}
+
+#[test]
+fn test_code() {
+    assert_tagged(&[
+        "fn main() {\n    println!(\"hi\");\n}",
+        "def add(a, b):\n    return a + b",
+        "const x = () => 1;",
+        "#include <stdio.h>\nint main() { return 0; }",
+    ], "code");
+    assert_not_tagged(&["Hello World", "Just a normal sentence, with punctuation.", "今天天气不错"], "code");
+}
+
+#[test]
+fn test_plain_text_has_no_smart_tags() {
+    assert!(tags_of("Hello World").is_empty());
+    assert!(tags_of("   ").is_empty());
+}
+
+struct TicketDetector;
+
+impl Detector for TicketDetector {
+    fn tag(&self) -> &'static str { "ticket" }
+    fn detect(&self, text: &str) -> bool {
+        text.starts_with("JIRA-")
+    }
+}
+
+#[test]
+fn test_custom_detector() {
+    let mut classifier = Classifier::empty();
+    classifier.register(Box::new(TicketDetector));
+    assert_eq!(classifier.classify("JIRA-42"), vec!["ticket"]);
+    assert!(classifier.classify("https://example.com").is_empty(), "Empty classifier has no defaults");
+}
+
+#[test]
+fn test_smart_tags_stored_and_filtered() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let mut storage = Storage::new(&data_dir).unwrap();
+
+    storage.add_text("https://example.com".to_string()).unwrap();
+    storage.add_text("alice@example.com".to_string()).unwrap();
+    storage.add_text("plain words".to_string()).unwrap();
+    storage.add_html("https://example.org".to_string(), "<a href=\"https://example.org\">https://example.org</a>".to_string()).unwrap();
+
+    let items = storage.get_recent(10, 0).unwrap();
+    let email = items.iter().find(|i| i.preview == "alice@example.com").unwrap();
+    assert_eq!(email.tags, vec!["text", "email"]);
+
+    // 按标签过滤
+    let urls = storage.get_recent_filtered(10, 0, &ClipFilter::tag("url")).unwrap();
+    assert_eq!(urls.len(), 2, "Text and HTML clips should both be tagged url");
+
+    // 查询词等于标签名时按标签命中
+    let results = storage.search("email").unwrap();
+    assert_eq!(results.len(), 1);
+
+    // 搜索与过滤组合
+    let results = storage.search_filtered("example", &ClipFilter::tag("email")).unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].preview, "alice@example.com");
+}
+
+#[test]
+fn test_register_detector_on_storage() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let mut storage = Storage::new(&data_dir).unwrap();
+    storage.register_detector(Box::new(TicketDetector));
+
+    storage.add_text("JIRA-7".to_string()).unwrap();
+    assert_eq!(storage.get_recent_filtered(10, 0, &ClipFilter::tag("ticket")).unwrap().len(), 1);
+}
@@ -3,6 +3,7 @@
 
 mod common;
 
+use pastee_lib::color;
 use pastee_lib::persist::Storage;
 use common::{create_test_dir, get_test_data_dir};
 
@@ -174,7 +175,7 @@ fn test_invalid_hex() {
         "#GG00FF",     // 非法字符
         "#F",          // 长度错误
         "#FF",         // 长度错误
-        "#FFFF",       // 长度错误
+        "#FFFFFFFFF",  // 长度错误
         "#FFFFF",      // 长度错误
         "#FFFFFFF",    // 长度错误
         "FF00FF",      // 缺少 #
@@ -200,9 +201,11 @@ fn test_invalid_rgb() {
     
     let invalid_rgb = vec![
         "rgb(255, 0)",         // 参数不足
-        "rgb(255, 0, 255, 0)", // 参数过多
-        "rgb(255 0 255)",      // 缺少逗号
+        "rgb(255, 0, 255, 0, 1)", // 参数过多
+        "rgb(255, 0 255)",     // 逗号和空格混用
         "rgb 255, 0, 255",     // 缺少括号
+        "rgb(300, 0, 0)",      // 超出范围
+        "rgb(255, 0, 50%)",    // 旧语法中数字和百分比混用
     ];
     
     for invalid in invalid_rgb {
@@ -266,3 +269,102 @@ fn test_case_insensitive() {
         );
     }
 }
+
+#[test]
+fn test_modern_css_syntax() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let mut storage = Storage::new(&data_dir).unwrap();
+    
+    let colors = vec![
+        "#F0F8",                        // CSS Color 4 的 4 位写法
+        "rgb(255 0 255)",
+        "rgb(0 0 0 / 50%)",
+        "rgba(10% 20% 30% / 0.5)",
+        "hsl(120deg 50% 50%)",
+        "hsl(0.5turn 100% 50% / 1)",
+        "hwb(200 10% 20%)",
+        "lab(50% 40 59.5)",
+        "lch(52.2% 72.2 50)",
+        "oklab(0.63 0.22 0.13)",
+        "oklch(62.8% 0.2577 29.23)",
+        "rebeccapurple",
+        "Tomato",
+        "transparent",
+    ];
+    
+    for color in colors {
+        storage.add_text(color.to_string()).unwrap();
+        let items = storage.get_recent(1, 0).unwrap();
+        
+        assert!(
+            items[0].tags.contains(&"color".to_string()),
+            "Modern color '{}' should be detected as color",
+            color
+        );
+    }
+}
+
+#[test]
+fn test_out_of_range_components() {
+    let invalid = vec![
+        "rgb(256 0 0)",
+        "rgb(0 0 0 / 1.5)",
+        "hsl(120 101% 50%)",
+        "hwb(0 -10% 0%)",
+        "lab(120 0 0)",
+        "oklch(1.5 0.1 120)",
+        "oklch(50% 0.9 120)",
+        "hwb(0, 10%, 20%)",  // hwb 没有逗号语法
+        "notacolor",
+    ];
+    
+    for text in invalid {
+        assert!(!color::is_color(text), "'{}' should NOT be a valid color", text);
+    }
+}
+
+#[test]
+fn test_color_normalization() {
+    let cases = vec![
+        ("#F0F", "#ff00ffff"),
+        ("rgb(255 0 0 / 50%)", "#ff000080"),
+        ("hsl(120, 100%, 50%)", "#00ff00ff"),
+        ("hwb(0 0% 0%)", "#ff0000ff"),
+        ("hwb(0 60% 60%)", "#808080ff"),
+        ("lab(54.29 80.8 69.89)", "#ff0000ff"),
+        ("oklch(62.8% 0.2577 29.23)", "#ff0000ff"),
+        ("oklab(1 0 0)", "#ffffffff"),
+        ("navy", "#000080ff"),
+        ("transparent", "#00000000"),
+    ];
+    
+    for (text, expected) in cases {
+        let parsed = color::parse(text).unwrap_or_else(|| panic!("'{}' should parse", text));
+        assert_eq!(parsed.to_hex8(), expected, "Normalized value of '{}'", text);
+    }
+}
+
+#[test]
+fn test_color_conversions() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let mut storage = Storage::new(&data_dir).unwrap();
+    
+    let id = storage.add_text("rgb(255, 0, 0)".to_string()).unwrap();
+    let formats = storage.get_color(id).unwrap().unwrap().formats();
+    assert_eq!(formats.hex, "#ff0000");
+    assert_eq!(formats.rgb, "rgb(255, 0, 0)");
+    assert_eq!(formats.hsl, "hsl(0, 100%, 50%)");
+    assert_eq!(formats.oklch, "oklch(62.8% 0.2577 29.23)");
+    
+    let id = storage.add_text("#00000080".to_string()).unwrap();
+    let formats = storage.get_color(id).unwrap().unwrap().formats();
+    assert_eq!(formats.hex, "#00000080");
+    assert_eq!(formats.rgb, "rgba(0, 0, 0, 0.502)");
+    assert_eq!(formats.oklch, "oklch(0% 0 0 / 0.502)");
+    
+    // 非颜色记录
+    let id = storage.add_text("plain".to_string()).unwrap();
+    assert!(storage.get_color(id).unwrap().is_none());
+}
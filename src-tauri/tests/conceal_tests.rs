@@ -0,0 +1,75 @@
+/// 密码管理器隐藏标记与临时记录测试
+
+mod common;
+
+use pastee_lib::conceal::{find_marker, ConcealedPolicy, KDE_PASSWORD_HINT};
+use pastee_lib::persist::Storage;
+use pastee_lib::setting::Settings;
+use pastee_lib::source_app::SourceApp;
+use common::{create_test_dir, get_test_data_dir};
+
+fn targets(names: &[&str]) -> Vec<String> {
+    names.iter().map(|n| n.to_string()).collect()
+}
+
+#[test]
+fn test_find_marker() {
+    // KDE 标记需要值为 secret
+    let kde = targets(&["text/plain", KDE_PASSWORD_HINT]);
+    assert_eq!(find_marker(&kde, |_| Some(b"secret\n".to_vec())), Some(KDE_PASSWORD_HINT));
+    assert_eq!(find_marker(&kde, |_| Some(b"public".to_vec())), None);
+    assert_eq!(find_marker(&kde, |_| None), None);
+
+    // 只识别 Linux 后端能列出的标记
+    let mac = targets(&["public.utf8-plain-text", "org.nspasteboard.ConcealedType"]);
+    assert_eq!(find_marker(&mac, |_| panic!("不应读取")), None);
+
+    assert_eq!(find_marker(&targets(&["text/plain", "text/html"]), |_| None), None);
+}
+
+#[test]
+fn test_ephemeral_clips_not_persisted() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let mut storage = Storage::new(&data_dir).unwrap();
+    assert_eq!(storage.settings().concealed_policy, ConcealedPolicy::Skip);
+
+    let id = storage.add_ephemeral("hunter2-password".to_string(), Some(SourceApp::named("KeePassXC"))).unwrap();
+    let clips = storage.ephemeral_clips();
+    assert_eq!(clips.len(), 1);
+    assert_eq!(clips[0].id, id);
+    assert!(!clips[0].preview.contains("hunter2"));
+    assert_eq!(storage.get_ephemeral(id).as_deref(), Some("hunter2-password"));
+
+    // 原文不进入数据库，也不随序列化返回给界面
+    assert_eq!(storage.get_total_count().unwrap(), 0);
+    let json = serde_json::to_string(&clips[0]).unwrap();
+    assert!(!json.contains("hunter2"));
+
+    // 重新打开后临时记录消失
+    drop(storage);
+    let mut storage = Storage::new(&data_dir).unwrap();
+    assert!(storage.ephemeral_clips().is_empty());
+}
+
+#[test]
+fn test_ephemeral_clips_expire() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let mut storage = Storage::new(&data_dir).unwrap();
+
+    let kept = storage.add_ephemeral("long-lived".to_string(), None).unwrap();
+    let settings = Settings { ephemeral_ttl_secs: 0, ..Settings::default() };
+    storage.update_settings(settings).unwrap();
+    let expired = storage.add_ephemeral("short-lived".to_string(), None).unwrap();
+
+    assert_eq!(storage.purge_ephemeral(), vec![expired]);
+    assert!(storage.get_ephemeral(expired).is_none());
+    assert_eq!(storage.get_ephemeral(kept).as_deref(), Some("long-lived"));
+    assert!(storage.purge_ephemeral().is_empty());
+
+    // 时长过大时返回错误而不是溢出
+    let settings = Settings { ephemeral_ttl_secs: u64::MAX, ..Settings::default() };
+    storage.update_settings(settings).unwrap();
+    assert!(storage.add_ephemeral("forever".to_string(), None).is_err());
+}
@@ -0,0 +1,121 @@
+/// 私有格式测试
+/// 验证白名单匹配、blob 落盘存储和原样恢复
+
+mod common;
+
+use pastee_lib::clipboard::clip_formats;
+use pastee_lib::persist::{Storage, ClipType, ClipData, ClipPart, MIME_HTML, MIME_TEXT};
+use pastee_lib::setting::{CustomFormat, Settings};
+use common::{create_test_dir, get_test_data_dir};
+
+const SHAPE_MIME: &str = "application/x-vnd.figma-shapes";
+
+fn shape_bytes() -> Vec<u8> {
+    // 包含 0 字节和非 UTF-8 字节，确保按原始字节保存
+    vec![0x00, 0xFF, 0x10, 0x80, b'{', b'}', 0x00]
+}
+
+#[test]
+fn test_custom_format_matching() {
+    let exact = CustomFormat::new("image/svg+xml", 1024);
+    assert!(exact.matches("image/svg+xml"));
+    assert!(!exact.matches("image/svg"));
+
+    let wildcard = CustomFormat::new("application/x-vnd.*", 1024);
+    assert!(wildcard.matches(SHAPE_MIME));
+    assert!(!wildcard.matches("application/json"));
+
+    let settings = Settings::default();
+    assert!(settings.custom_format_for("image/svg+xml").is_some());
+    assert!(settings.custom_format_for("text/plain").is_none());
+}
+
+#[test]
+fn test_settings_roundtrip() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+
+    // 文件不存在时使用默认值
+    assert_eq!(Settings::load(&data_dir), Settings::default());
+
+    let mut settings = Settings::default();
+    settings.custom_formats = vec![CustomFormat::new("application/x-test", 42)];
+    settings.save(&data_dir).unwrap();
+    assert_eq!(Settings::load(&data_dir), settings);
+}
+
+#[test]
+fn test_add_custom_roundtrip() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let mut storage = Storage::new(&data_dir).unwrap();
+
+    let id = storage.add_custom(SHAPE_MIME.to_string(), shape_bytes()).unwrap();
+    let items = storage.get_recent(1, 0).unwrap();
+    assert_eq!(items[0].content_type, ClipType::Custom);
+    assert!(items[0].preview.contains(SHAPE_MIME));
+
+    match storage.get_content(id).unwrap() {
+        ClipData::Custom { mime, data } => {
+            assert_eq!(mime, SHAPE_MIME);
+            assert_eq!(data, shape_bytes(), "Blob should come back byte-for-byte");
+        }
+        _ => panic!("Should return Custom ClipData"),
+    }
+
+    // blob 保存在磁盘上
+    let blob_files = walk(&data_dir.join("blobs"));
+    assert_eq!(blob_files.len(), 1, "Blob should be stored on disk");
+}
+
+#[test]
+fn test_blob_removed_with_record() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let mut storage = Storage::new(&data_dir).unwrap();
+
+    let text_id = storage.add_text("shape label".to_string()).unwrap();
+    storage.add_parts(text_id, &[ClipPart::binary(SHAPE_MIME, shape_bytes())]).unwrap();
+    let custom_id = storage.add_custom(SHAPE_MIME.to_string(), shape_bytes()).unwrap();
+
+    // 相同内容只保存一份
+    assert_eq!(walk(&data_dir.join("blobs")).len(), 1);
+
+    // 仍被引用时不删除文件
+    storage.delete_record(text_id).unwrap();
+    assert_eq!(walk(&data_dir.join("blobs")).len(), 1);
+
+    storage.delete_record(custom_id).unwrap();
+    assert!(walk(&data_dir.join("blobs")).is_empty(), "Orphan blob should be removed");
+}
+
+#[test]
+fn test_clip_formats_order() {
+    let data = ClipData::Text("label".to_string());
+    let parts = vec![
+        ClipPart::text(MIME_HTML, "<b>label</b>".to_string()),
+        ClipPart::binary(SHAPE_MIME, shape_bytes()),
+        ClipPart::text(MIME_TEXT, "duplicate".to_string()),
+    ];
+
+    let formats = clip_formats(&data, &parts);
+    let mimes: Vec<&str> = formats.iter().map(|(m, _)| m.as_str()).collect();
+    assert_eq!(mimes, vec![MIME_TEXT, MIME_HTML, SHAPE_MIME]);
+    assert_eq!(formats[0].1, b"label".to_vec(), "Primary representation should win");
+    assert_eq!(formats[2].1, shape_bytes());
+}
+
+fn walk(dir: &std::path::Path) -> Vec<std::path::PathBuf> {
+    let mut files = Vec::new();
+    if let Ok(entries) = std::fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                files.extend(walk(&path));
+            } else {
+                files.push(path);
+            }
+        }
+    }
+    files
+}
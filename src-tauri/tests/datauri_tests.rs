@@ -0,0 +1,105 @@
+/// data URI / base64 图片文本测试
+
+mod common;
+
+use base64::{engine::general_purpose, Engine as _};
+use image::{Rgba, RgbaImage};
+use pastee_lib::datauri::{self, EncodedImage};
+use pastee_lib::persist::{ClipData, ClipType, Storage, MIME_TEXT};
+use common::{create_test_dir, get_test_data_dir};
+
+fn png_bytes() -> Vec<u8> {
+    let img = RgbaImage::from_pixel(3, 2, Rgba([0, 0, 255, 255]));
+    let mut png = Vec::new();
+    img.write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png).unwrap();
+    png
+}
+
+#[test]
+fn test_decode_data_uri() {
+    let png = png_bytes();
+    let b64 = general_purpose::STANDARD.encode(&png);
+
+    let uri = format!("data:image/png;base64,{}", b64);
+    assert_eq!(datauri::decode_image(&uri), Some(EncodedImage::Raster(png.clone())));
+    // 大小写、换行和缺少 padding 都能识别
+    let wrapped = format!("  DATA:image/PNG;BASE64,{}\n", b64.trim_end_matches('=').as_bytes()
+        .chunks(40).map(|c| std::str::from_utf8(c).unwrap()).collect::<Vec<_>>().join("\n"));
+    assert_eq!(datauri::decode_image(&wrapped), Some(EncodedImage::Raster(png)));
+
+    let svg = "data:image/svg+xml,%3Csvg%20xmlns%3D%22http%3A%2F%2Fwww.w3.org%2F2000%2Fsvg%22%2F%3E";
+    assert_eq!(
+        datauri::decode_image(svg),
+        Some(EncodedImage::Svg(r#"<svg xmlns="http://www.w3.org/2000/svg"/>"#.to_string()))
+    );
+
+    // 非图片 MIME 或内容与声明不符
+    assert_eq!(datauri::decode_image("data:text/plain;base64,aGVsbG8="), None);
+    assert_eq!(datauri::decode_image("data:image/png;base64,aGVsbG8gd29ybGQ="), None);
+    assert_eq!(datauri::decode_image("data:image/png;base64"), None);
+}
+
+#[test]
+fn test_decode_raw_base64() {
+    let png = png_bytes();
+    let b64 = general_purpose::STANDARD.encode(&png);
+    assert_eq!(datauri::decode_image(&b64), Some(EncodedImage::Raster(png.clone())));
+    let url_safe = general_purpose::URL_SAFE_NO_PAD.encode(&png);
+    assert_eq!(datauri::decode_image(&url_safe), Some(EncodedImage::Raster(png)));
+
+    // 普通文本和非图片的 base64
+    assert_eq!(datauri::decode_image("SGVsbG8gV29ybGQ="), None);
+    let text = general_purpose::STANDARD.encode("this is just a long sentence encoded as base64, not an image");
+    assert_eq!(datauri::decode_image(&text), None);
+    assert_eq!(datauri::decode_image("The quick brown fox jumps over the lazy dog again and again and again"), None);
+}
+
+#[test]
+fn test_add_text_routes_images() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let mut storage = Storage::new(&data_dir).unwrap();
+
+    let uri = format!("data:image/png;base64,{}", general_purpose::STANDARD.encode(png_bytes()));
+    let id = storage.add_text(uri.clone()).unwrap();
+
+    let items = storage.get_recent(1, 0).unwrap();
+    assert_eq!(items[0].id, id);
+    assert_eq!(items[0].content_type, ClipType::Image);
+    assert_eq!(items[0].preview, "[图片] 3x2 PNG");
+    match storage.get_content(id).unwrap() {
+        ClipData::Image(png) => {
+            let img = image::load_from_memory(&png).unwrap().to_rgba8();
+            assert_eq!(img.get_pixel(0, 0), &Rgba([0, 0, 255, 255]));
+        }
+        other => panic!("Expected image, got {:?}", other),
+    }
+
+    // 原文保留为纯文本表示
+    let parts = storage.get_parts(id).unwrap();
+    let text = parts.iter().find(|p| p.mime == MIME_TEXT).unwrap();
+    assert_eq!(text.text.as_deref(), Some(uri.as_str()));
+
+    // 声明为图片但无法解码时按普通文本保存
+    let broken = "data:image/png;base64,iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAA";
+    let id = storage.add_text(broken.to_string()).unwrap();
+    assert!(matches!(storage.get_content(id).unwrap(), ClipData::Text(t) if t == broken));
+}
+
+#[test]
+fn test_add_text_routes_svg_data_uri() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let mut storage = Storage::new(&data_dir).unwrap();
+
+    let markup = r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10"><circle cx="5" cy="5" r="5"/></svg>"#;
+    let uri = format!("data:image/svg+xml;base64,{}", general_purpose::STANDARD.encode(markup));
+    let id = storage.add_text(uri.clone()).unwrap();
+
+    let items = storage.get_recent(1, 0).unwrap();
+    assert_eq!(items[0].content_type, ClipType::Image);
+    assert!(items[0].tags.contains(&"svg".to_string()));
+    assert_eq!(storage.get_svg(id).unwrap().as_deref(), Some(markup));
+    let parts = storage.get_parts(id).unwrap();
+    assert_eq!(parts.iter().find(|p| p.mime == MIME_TEXT).unwrap().text.as_deref(), Some(uri.as_str()));
+}
@@ -3,7 +3,8 @@
 
 mod common;
 
-use pastee_lib::persist::{Storage, ClipType, ClipData};
+use pastee_lib::persist::{Storage, ClipType, ClipData, ClipPart, MIME_TEXT};
+use pastee_lib::setting::{Settings, TextNormalization};
 use common::{create_test_dir, get_test_data_dir, test_color_samples, test_non_color_samples};
 
 #[test]
@@ -224,6 +225,51 @@ fn test_empty_text() {
     assert_eq!(result, 0, "Whitespace-only text should return 0");
 }
 
+#[test]
+fn test_text_stored_verbatim() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let mut storage = Storage::new(&data_dir).unwrap();
+    
+    // 缩进和末尾换行都应保留
+    let yaml = "  key:\n    - item\n".to_string();
+    let id = storage.add_text(yaml.clone()).unwrap();
+    match storage.get_content(id).unwrap() {
+        ClipData::Text(text) => assert_eq!(text, yaml, "Text should be stored verbatim"),
+        _ => panic!("Should return Text ClipData"),
+    }
+}
+
+#[test]
+fn test_text_normalized_dedupe() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let mut storage = Storage::new(&data_dir).unwrap();
+    
+    // 默认忽略首尾空白去重，保留最近一次的原文
+    let id1 = storage.add_text("hello".to_string()).unwrap();
+    let id2 = storage.add_text("  hello\n".to_string()).unwrap();
+    assert_eq!(id1, id2, "Texts differing only in surrounding whitespace should dedupe");
+    match storage.get_content(id1).unwrap() {
+        ClipData::Text(text) => assert_eq!(text, "  hello\n", "Latest original text should win"),
+        _ => panic!("Should return Text ClipData"),
+    }
+    
+    // 逐字节模式下视为不同记录
+    let mut settings = Settings::default();
+    settings.text_normalization = TextNormalization::Exact;
+    storage.update_settings(settings).unwrap();
+    let id3 = storage.add_text("hello ".to_string()).unwrap();
+    assert_ne!(id1, id3, "Exact mode should not ignore whitespace");
+}
+
+#[test]
+fn test_text_normalization_modes() {
+    assert_eq!(TextNormalization::Exact.apply(" a  b "), " a  b ");
+    assert_eq!(TextNormalization::Trim.apply(" a  b \n"), "a  b");
+    assert_eq!(TextNormalization::CollapseWhitespace.apply(" a \t\n b "), "a b");
+}
+
 #[test]
 fn test_database_migration() {
     let temp_dir = create_test_dir();
@@ -238,3 +284,42 @@ fn test_database_migration() {
     let storage2 = Storage::new(&data_dir);
     assert!(storage2.is_ok(), "Should reopen existing database successfully");
 }
+
+#[test]
+fn test_record_parts() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let mut storage = Storage::new(&data_dir).unwrap();
+    
+    // 浏览器复制：HTML 为主表示，纯文本作为附加表示
+    let id = storage.add_html("Hello World".to_string(), "<p>Hello <b>World</b></p>".to_string()).unwrap();
+    storage.add_parts(id, &[ClipPart::text(MIME_TEXT, "Hello  World\n".to_string())]).unwrap();
+    
+    // HTML 记录另有自动生成的 Markdown 表示
+    let parts = storage.get_parts(id).unwrap();
+    assert_eq!(parts.len(), 2, "Should have text and markdown parts");
+    let text_part = parts.iter().find(|p| p.mime == MIME_TEXT).expect("Should have text part");
+    assert_eq!(text_part.text.as_deref(), Some("Hello  World\n"), "Alternate text should be kept verbatim");
+    
+    // 同一 MIME 再次写入应覆盖
+    storage.add_parts(id, &[ClipPart::text(MIME_TEXT, "Hello World".to_string())]).unwrap();
+    assert_eq!(storage.get_parts(id).unwrap().len(), 2, "Same MIME should be replaced");
+    
+    // 删除主记录时附加表示一起删除
+    storage.delete_record(id).unwrap();
+    assert!(storage.get_parts(id).unwrap().is_empty(), "Parts should be deleted with record");
+}
+
+#[test]
+fn test_search_alternate_text() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let mut storage = Storage::new(&data_dir).unwrap();
+    
+    let id = storage.add_files(vec!["/tmp/report.pdf".to_string()]).unwrap();
+    storage.add_parts(id, &[ClipPart::text(MIME_TEXT, "quarterly summary".to_string())]).unwrap();
+    
+    let results = storage.search("quarterly").unwrap();
+    assert_eq!(results.len(), 1, "Should find record by alternate text");
+    assert_eq!(results[0].id, id);
+}
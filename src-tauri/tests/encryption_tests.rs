@@ -0,0 +1,99 @@
+/// 静态加密测试：就地加密、锁定与解锁
+
+mod common;
+
+use std::sync::Arc;
+
+use pastee_lib::crypto::{self, Cipher, StoreKey};
+use pastee_lib::persist::{ClipPart, Storage};
+use common::{create_test_dir, get_test_data_dir};
+
+const SALT: &[u8] = b"pastee-test-salt";
+
+fn key(passphrase: &str) -> Arc<StoreKey> {
+    Arc::new(StoreKey::derive(passphrase, SALT).unwrap())
+}
+
+#[test]
+fn test_seal_roundtrip() {
+    let key = key("correct horse");
+    let sealed = key.seal(b"hello").unwrap();
+    assert!(crypto::is_sealed(&sealed));
+    assert_eq!(key.open(&sealed).unwrap(), b"hello");
+    // 每次使用新的随机数
+    assert_ne!(key.seal(b"hello").unwrap(), sealed);
+
+    assert!(self::key("wrong").open(&sealed).is_err());
+    let mut tampered = sealed.clone();
+    *tampered.last_mut().unwrap() ^= 1;
+    assert!(key.open(&tampered).is_err());
+}
+
+#[test]
+fn test_encrypt_in_place() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let mut storage = Storage::new(&data_dir).unwrap();
+
+    let text_id = storage.add_text("staging password is hunter2".to_string()).unwrap();
+    storage.add_parts(text_id, &[ClipPart::binary("application/x-test", vec![7; 64])]).unwrap();
+    let rgba: Vec<u8> = (0..16 * 16).flat_map(|i| [i as u8, 0, 255 - i as u8, 255]).collect();
+    let (image_id, _) = storage.add_image(16, 16, rgba).unwrap();
+    let png = storage.image_data(image_id, false).unwrap();
+    assert!(!storage.is_encrypted());
+
+    storage.encrypt(key("correct horse")).unwrap();
+    assert!(storage.is_encrypted());
+
+    let db = std::fs::read(data_dir.join("clippy.db")).unwrap();
+    assert!(!db.starts_with(b"SQLite format 3"));
+    let (image_path, _) = storage.get_image_paths(image_id).unwrap();
+    assert!(crypto::is_sealed(&std::fs::read(data_dir.join("images").join(image_path)).unwrap()));
+
+    // 加密后内容照常读取和搜索
+    assert_eq!(storage.image_data(image_id, false).unwrap(), png);
+    let decrypted = storage.image_file(image_id).unwrap();
+    assert!(decrypted.starts_with(data_dir.join("tmp")));
+    assert_eq!(std::fs::read(&*decrypted).unwrap(), png);
+    let decrypted_path = decrypted.to_path_buf();
+    drop(decrypted);
+    assert!(!decrypted_path.exists());
+    assert_eq!(storage.get_parts(text_id).unwrap()[0].data, Some(vec![7; 64]));
+    assert_eq!(storage.search("hunter2").unwrap().len(), 1);
+
+    // 重复调用只补加密剩余文件
+    storage.encrypt(key("correct horse")).unwrap();
+    assert_eq!(storage.get_total_count().unwrap(), 2);
+}
+
+#[test]
+fn test_lock_and_unlock() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let mut storage = Storage::new(&data_dir).unwrap();
+    storage.add_text("vpn token 8812".to_string()).unwrap();
+    assert!(storage.lock_store().is_err());
+    storage.encrypt(key("correct horse")).unwrap();
+
+    storage.lock_store().unwrap();
+    assert!(storage.is_locked());
+    assert!(storage.get_recent(10, 0).unwrap().is_empty());
+    let rgba = vec![255; 4 * 4 * 4];
+    assert!(storage.add_image(4, 4, rgba).is_err());
+
+    assert!(storage.unlock_store(key("wrong")).is_err());
+    assert!(storage.is_locked());
+    storage.unlock_store(key("correct horse")).unwrap();
+    assert_eq!(storage.get_recent(10, 0).unwrap()[0].preview, "vpn token 8812");
+    drop(storage);
+
+    // 重新打开时保持锁定，直到提供密钥；上次残留的解密文件被清理
+    let leftover = data_dir.join("tmp").join("1-0.png");
+    std::fs::write(&leftover, b"plaintext").unwrap();
+    let mut storage = Storage::new(&data_dir).unwrap();
+    assert!(!leftover.exists());
+    assert!(storage.is_locked());
+    assert_eq!(storage.get_total_count().unwrap(), 0);
+    storage.unlock_store(key("correct horse")).unwrap();
+    assert_eq!(storage.get_total_count().unwrap(), 1);
+}
@@ -0,0 +1,76 @@
+/// HTML 转纯文本测试
+/// 验证实体解码、块级结构换行和不可见内容过滤
+
+mod common;
+
+use pastee_lib::html;
+use pastee_lib::persist::{Storage, ClipData};
+use common::{create_test_dir, get_test_data_dir};
+
+#[test]
+fn test_html_entities_decoded() {
+    let text = html::to_plain_text("<p>Tom&nbsp;&amp;&nbsp;Jerry &lt;3 &quot;ok&quot;</p>");
+    assert_eq!(text, "Tom & Jerry <3 \"ok\"");
+}
+
+#[test]
+fn test_html_attribute_with_angle_bracket() {
+    let text = html::to_plain_text(r#"<a title="a > b" href="x">link</a> text"#);
+    assert_eq!(text, "link text");
+}
+
+#[test]
+fn test_html_skips_script_and_style() {
+    let text = html::to_plain_text(
+        "<style>p { color: red; }</style><p>visible</p><script>var a = '<b>';</script><!-- hidden -->",
+    );
+    assert_eq!(text, "visible");
+}
+
+#[test]
+fn test_html_block_structure() {
+    let text = html::to_plain_text("<h1>Title</h1><p>First <b>para</b></p><div>line</div><div>line2</div>");
+    assert_eq!(text, "Title\n\nFirst para\n\nline\nline2");
+
+    let text = html::to_plain_text("<ul><li>one</li><li>two</li></ul><p>a<br>b</p>");
+    assert_eq!(text, "one\ntwo\n\na\nb");
+}
+
+#[test]
+fn test_html_table() {
+    let text = html::to_plain_text(
+        "<table><tr><th>Name</th><th>Age</th></tr><tr><td>Ann</td><td>30</td></tr></table>",
+    );
+    assert_eq!(text, "Name\tAge\nAnn\t30");
+}
+
+#[test]
+fn test_html_whitespace_and_pre() {
+    let text = html::to_plain_text("<p>  a \n\n  b  </p><pre>  keep\n    indent</pre>");
+    assert_eq!(text, "a b\n\n  keep\n    indent");
+}
+
+#[test]
+fn test_html_clipboard_fragment() {
+    // 浏览器复制时常见的片段格式
+    let text = html::to_plain_text(
+        "<html><body><!--StartFragment--><span style=\"x\">Hello</span> <i>World</i><!--EndFragment--></body></html>",
+    );
+    assert_eq!(text, "Hello World");
+}
+
+#[test]
+fn test_html_text_searchable() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let mut storage = Storage::new(&data_dir).unwrap();
+
+    let source = "<p>Fish&amp;Chips</p><p>menu</p>";
+    let id = storage.add_html(html::to_plain_text(source), source.to_string()).unwrap();
+
+    assert_eq!(storage.search("Fish&Chips").unwrap().len(), 1);
+    match storage.get_content(id).unwrap() {
+        ClipData::Html { text, .. } => assert_eq!(text, "Fish&Chips\n\nmenu"),
+        _ => panic!("Should return Html ClipData"),
+    }
+}
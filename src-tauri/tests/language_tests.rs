@@ -0,0 +1,124 @@
+/// 编程语言检测与语法高亮测试
+
+mod common;
+
+use pastee_lib::highlight;
+use pastee_lib::language;
+use pastee_lib::persist::{Storage, ClipFilter};
+use common::{create_test_dir, get_test_data_dir};
+
+fn assert_language(samples: &[&str], expected: &str) {
+    for sample in samples {
+        assert_eq!(language::detect(sample), Some(expected), "Sample should be {}:\n{}", expected, sample);
+    }
+}
+
+#[test]
+fn test_detect_rust() {
+    assert_language(&[
+        "fn main() {\n    let mut v = Vec::new();\n    println!(\"{:?}\", v);\n}",
+        "pub struct Point { x: i32 }\nimpl Point {\n    pub fn new() -> Self { Point { x: 0 } }\n}",
+    ], "rust");
+}
+
+#[test]
+fn test_detect_python() {
+    assert_language(&[
+        "def add(a, b):\n    return a + b\n",
+        "import os\n\nclass Foo(Base):\n    def __init__(self):\n        self.x = None\n",
+        "#!/usr/bin/env python3\nprint('hi')",
+    ], "python");
+}
+
+#[test]
+fn test_detect_javascript_and_typescript() {
+    assert_language(&[
+        "const add = (a, b) => a + b;\nconsole.log(add(1, 2));",
+        "function greet(name) {\n  if (name === undefined) return;\n  document.title = name;\n}",
+    ], "javascript");
+    assert_language(&[
+        "interface User {\n  name: string;\n  age: number;\n}\nconst u: User = { name: 'a', age: 1 };",
+        "export type Id = string;\nfunction f(x: number): void {\n  console.log(x as any);\n}",
+    ], "typescript");
+}
+
+#[test]
+fn test_detect_other_languages() {
+    assert_language(&["package main\n\nimport \"fmt\"\n\nfunc main() {\n\tx := 1\n\tfmt.Println(x)\n}"], "go");
+    assert_language(&["public class Hello {\n    public static void main(String[] args) {\n        System.out.println(\"hi\");\n    }\n}"], "java");
+    assert_language(&["#include <stdio.h>\n\nint main(void) {\n    printf(\"hi\\n\");\n    return 0;\n}"], "c");
+    assert_language(&["#include <iostream>\n\nint main() {\n    std::cout << \"hi\" << std::endl;\n}"], "cpp");
+    assert_language(&["using System;\n\nnamespace App {\n    class P { public string Name { get; set; } }\n}"], "csharp");
+    assert_language(&["require 'json'\n\ndef greet(name)\n  puts \"hi #{name}\"\nend\n\n[1, 2].each do |x|\n  puts x\nend"], "ruby");
+    assert_language(&["<?php\n$name = 'a';\necho $name;"], "php");
+    assert_language(&["#!/bin/bash\nset -e\necho hi", "cat log.txt | grep error | sort | uniq -c"], "shell");
+    assert_language(&[".button {\n  color: red;\n  padding: 4px;\n}\n@media (max-width: 600px) {\n  .button { display: none; }\n}"], "css");
+}
+
+#[test]
+fn test_detect_structured() {
+    assert_language(&[r#"{"name": "pastee", "version": 1}"#], "json");
+    assert_language(&["<note><to>Tove</to></note>"], "xml");
+    assert_language(&["<div class=\"a\"><span>hi</span></div>"], "html");
+    assert_language(&["SELECT id FROM users WHERE name = 'a'"], "sql");
+}
+
+#[test]
+fn test_detect_none_for_prose() {
+    for sample in ["Hello World", "The quick brown fox jumps over the lazy dog.", "今天天气不错", ""] {
+        assert_eq!(language::detect(sample), None, "Prose should not be code: {}", sample);
+    }
+}
+
+#[test]
+fn test_highlight_tokens() {
+    let result = highlight::highlight("fn main() {\n    let x = 1;\n}\n", Some("rust"), None).unwrap();
+    assert_eq!(result.language, "rust");
+    assert_eq!(result.lines.len(), 3);
+    assert!(!result.truncated);
+
+    // 拼回去与原文一致
+    let joined: Vec<String> = result.lines.iter()
+        .map(|line| line.iter().map(|s| s.text.as_str()).collect())
+        .collect();
+    assert_eq!(joined, vec!["fn main() {", "    let x = 1;", "}"]);
+
+    // 关键字和普通文本颜色不同
+    let first = &result.lines[0];
+    assert!(first.len() > 1, "Line should be split into several spans");
+    let fn_span = first.iter().find(|s| s.text.trim() == "fn").expect("fn should be its own span");
+    let name_span = first.iter().find(|s| s.text.contains("main")).unwrap();
+    assert_ne!(fn_span.color, name_span.color);
+    assert!(fn_span.color.starts_with('#'));
+}
+
+#[test]
+fn test_highlight_plain_and_unknown_theme() {
+    let result = highlight::highlight("just text", None, None).unwrap();
+    assert_eq!(result.language, "text");
+    assert_eq!(result.lines[0].len(), 1);
+
+    assert!(highlight::highlight("x", Some("rust"), Some("no-such-theme")).is_err());
+    assert!(highlight::themes().contains(&highlight::DEFAULT_THEME.to_string()));
+}
+
+#[test]
+fn test_language_stored_on_clip() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let mut storage = Storage::new(&data_dir).unwrap();
+
+    let code_id = storage.add_text("def add(a, b):\n    return a + b".to_string()).unwrap();
+    let text_id = storage.add_text("Hello World".to_string()).unwrap();
+
+    assert_eq!(storage.get_language(code_id).unwrap().as_deref(), Some("python"));
+    assert_eq!(storage.get_language(text_id).unwrap(), None);
+
+    let items = storage.get_recent_filtered(10, 0, &ClipFilter::tag("code")).unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].language.as_deref(), Some("python"));
+
+    // 手动修正
+    storage.set_language(code_id, Some("ruby")).unwrap();
+    assert_eq!(storage.get_language(code_id).unwrap().as_deref(), Some("ruby"));
+}
@@ -0,0 +1,129 @@
+/// 超大文本外置存储测试
+/// 验证压缩落盘、前缀索引和分段读取
+
+mod common;
+
+use pastee_lib::persist::{Storage, ClipData, TEXT_PREFIX_BYTES};
+use pastee_lib::setting::Settings;
+use common::{create_test_dir, get_test_data_dir};
+
+fn storage_with_threshold(data_dir: &std::path::Path, threshold: usize) -> Storage {
+    let mut storage = Storage::new(data_dir).unwrap();
+    let mut settings = Settings::default();
+    settings.large_text_threshold = threshold;
+    storage.update_settings(settings).unwrap();
+    storage
+}
+
+fn big_log(lines: usize) -> String {
+    (0..lines).map(|i| format!("[{:06}] 日志 line {}", i, i)).collect::<Vec<_>>().join("\n")
+}
+
+#[test]
+fn test_small_text_stays_inline() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let mut storage = Storage::new(&data_dir).unwrap();
+
+    let id = storage.add_text("short".to_string()).unwrap();
+    assert_eq!(storage.external_text_size(id).unwrap(), None);
+}
+
+#[test]
+fn test_large_text_externalized() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let mut storage = storage_with_threshold(&data_dir, 1024);
+
+    let text = big_log(20_000);
+    let id = storage.add_text(text.clone()).unwrap();
+
+    assert_eq!(storage.external_text_size(id).unwrap(), Some(text.len()));
+
+    // 完整内容可以还原
+    match storage.get_content(id).unwrap() {
+        ClipData::Text(restored) => assert_eq!(restored, text),
+        _ => panic!("Should return Text ClipData"),
+    }
+
+    // 前缀可以搜索，前缀之后的内容不进入索引
+    assert_eq!(storage.search("[000010]").unwrap().len(), 1);
+    assert!(text.len() > TEXT_PREFIX_BYTES * 2);
+    assert_eq!(storage.search("[019999]").unwrap().len(), 0);
+
+    // 压缩文件在 texts 目录
+    assert!(data_dir.join("texts").read_dir().unwrap().next().is_some());
+}
+
+#[test]
+fn test_read_text_range_pages() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let mut storage = storage_with_threshold(&data_dir, 1024);
+
+    let text = big_log(5_000);
+    let id = storage.add_text(text.clone()).unwrap();
+
+    // 按 1000 字节一页读完，拼起来应与原文一致
+    let mut offset = 0;
+    let mut restored = String::new();
+    loop {
+        let chunk = storage.read_text_range(id, offset, 1000).unwrap();
+        assert_eq!(chunk.offset, offset, "Pages should be contiguous");
+        assert_eq!(chunk.total_size, text.len());
+        restored.push_str(&chunk.text);
+        offset = chunk.next_offset;
+        if chunk.eof { break; }
+    }
+    assert_eq!(restored, text);
+}
+
+#[test]
+fn test_read_text_range_char_boundary() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let mut storage = storage_with_threshold(&data_dir, 8);
+
+    let text = "你好世界你好世界".to_string(); // 每个字符 3 字节
+    let id = storage.add_text(text.clone()).unwrap();
+
+    // 从字符中间开始，应对齐到下一个字符
+    let chunk = storage.read_text_range(id, 1, 4).unwrap();
+    assert_eq!(chunk.offset, 3);
+    assert_eq!(chunk.text, "好世");
+    assert_eq!(chunk.next_offset, 9);
+}
+
+#[test]
+fn test_read_text_range_out_of_bounds() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let mut storage = storage_with_threshold(&data_dir, 1024);
+
+    let inline_id = storage.add_text("short text".to_string()).unwrap();
+    let external_id = storage.add_text(big_log(1_000)).unwrap();
+
+    // 前端传入的偏移量和长度过大时返回空的末尾分段，而不是溢出
+    for id in [inline_id, external_id] {
+        let chunk = storage.read_text_range(id, usize::MAX - 2, usize::MAX).unwrap();
+        assert!(chunk.text.is_empty());
+        assert_eq!(chunk.offset, chunk.total_size);
+        assert!(chunk.eof);
+    }
+}
+
+#[test]
+fn test_external_text_removed_with_record() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let mut storage = storage_with_threshold(&data_dir, 1024);
+
+    let id = storage.add_text(big_log(1_000)).unwrap();
+    storage.delete_record(id).unwrap();
+
+    let files: Vec<_> = std::fs::read_dir(data_dir.join("texts")).unwrap()
+        .flatten()
+        .flat_map(|month| std::fs::read_dir(month.path()).unwrap().flatten())
+        .collect();
+    assert!(files.is_empty(), "Text file should be deleted with record");
+}
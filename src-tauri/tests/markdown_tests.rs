@@ -0,0 +1,80 @@
+/// HTML 转 Markdown 测试
+/// 验证常见结构的转换，以及 Markdown 表示的存储与搜索
+
+mod common;
+
+use pastee_lib::markdown;
+use pastee_lib::persist::{Storage, MIME_MARKDOWN};
+use common::{create_test_dir, get_test_data_dir};
+
+#[test]
+fn test_markdown_headings_and_emphasis() {
+    let md = markdown::from_html("<h1>Title</h1><p>Some <b>bold</b> and <em>italic</em> text.</p><h3>Sub</h3>");
+    assert_eq!(md, "# Title\n\nSome **bold** and *italic* text.\n\n### Sub");
+}
+
+#[test]
+fn test_markdown_links_and_images() {
+    let md = markdown::from_html(
+        r#"<p>See <a href="https://example.com/a b">the docs</a> or <a href="https://x.dev">https://x.dev</a>.</p><img src="cat.png" alt="A cat">"#,
+    );
+    assert_eq!(md, "See [the docs](https://example.com/a%20b) or <https://x.dev>.\n\n![A cat](cat.png)");
+
+    // javascript: 链接只保留文字
+    let md = markdown::from_html(r#"<a href="javascript:alert(1)">click</a>"#);
+    assert_eq!(md, "click");
+}
+
+#[test]
+fn test_markdown_lists() {
+    let md = markdown::from_html("<ul><li>one</li><li>two<ul><li>nested</li></ul></li></ul>");
+    assert_eq!(md, "- one\n- two\n  - nested");
+
+    let md = markdown::from_html(r#"<ol start="3"><li><p>three</p></li><li>four</li></ol>"#);
+    assert_eq!(md, "3. three\n4. four");
+}
+
+#[test]
+fn test_markdown_code() {
+    let md = markdown::from_html(
+        r#"<p>Run <code>cargo test</code></p><pre><code class="language-rust">fn main() {
+    println!("hi");
+}</code></pre>"#,
+    );
+    assert_eq!(md, "Run `cargo test`\n\n```rust\nfn main() {\n    println!(\"hi\");\n}\n```");
+}
+
+#[test]
+fn test_markdown_table() {
+    let md = markdown::from_html(
+        "<table><thead><tr><th>Name</th><th>Note</th></tr></thead><tbody><tr><td>a|b</td><td><i>x</i></td></tr><tr><td>c</td></tr></tbody></table>",
+    );
+    assert_eq!(md, "| Name | Note |\n| --- | --- |\n| a\\|b | *x* |\n| c |  |");
+}
+
+#[test]
+fn test_markdown_blockquote_and_escape() {
+    let md = markdown::from_html("<blockquote><p>quoted</p><p>more</p></blockquote><p>2 * 3 = [x]</p>");
+    assert_eq!(md, "> quoted\n>\n> more\n\n2 \\* 3 = \\[x\\]");
+}
+
+#[test]
+fn test_markdown_stored_and_searchable() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let mut storage = Storage::new(&data_dir).unwrap();
+
+    let html = "<h2>Release</h2><ul><li>fixed</li></ul>";
+    let id = storage.add_html("Release fixed".to_string(), html.to_string()).unwrap();
+
+    let parts = storage.get_parts(id).unwrap();
+    let part = parts.iter().find(|p| p.mime == MIME_MARKDOWN).expect("Markdown part should be stored");
+    assert_eq!(part.text.as_deref(), Some("## Release\n\n- fixed"));
+    assert_eq!(storage.get_markdown(id).unwrap().as_deref(), Some("## Release\n\n- fixed"));
+
+    // Markdown 语法也可以搜索
+    assert_eq!(storage.search("## Release").unwrap().len(), 1);
+
+    let text_id = storage.add_text("plain".to_string()).unwrap();
+    assert_eq!(storage.get_markdown(text_id).unwrap(), None);
+}
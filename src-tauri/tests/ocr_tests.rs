@@ -0,0 +1,112 @@
+/// 图片 OCR 测试 (使用假引擎，不依赖本机 tesseract)
+
+mod common;
+
+use anyhow::Result;
+use image::{Rgba, RgbaImage};
+use pastee_lib::ocr::{self, OcrEngine, TesseractCli};
+use pastee_lib::persist::Storage;
+use pastee_lib::setting::Settings;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use common::{create_test_dir, get_test_data_dir};
+
+/// 按图片尺寸返回固定文字
+struct FakeOcr;
+
+impl OcrEngine for FakeOcr {
+    fn name(&self) -> &'static str {
+        "fake"
+    }
+
+    fn recognize(&self, image: &Path) -> Result<String> {
+        let (width, _) = image::image_dimensions(image)?;
+        Ok(match width {
+            10 => "Error: connection refused\nerrno 111".to_string(),
+            _ => String::new(),
+        })
+    }
+}
+
+fn add_image(storage: &Mutex<Storage>, width: u32, height: u32) -> i64 {
+    let img = RgbaImage::from_pixel(width, height, Rgba([255, 255, 255, 255]));
+    storage.lock().unwrap().add_image(width as usize, height as usize, img.into_raw()).unwrap().0
+}
+
+#[test]
+fn test_ocr_text_is_searchable() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let storage = Mutex::new(Storage::new(&data_dir).unwrap());
+    storage.lock().unwrap().set_ocr_engine(Arc::new(FakeOcr));
+
+    let id = add_image(&storage, 10, 10);
+    let blank = add_image(&storage, 12, 12);
+    ocr::process(&storage, id).unwrap();
+    ocr::process(&storage, blank).unwrap();
+
+    let store = storage.lock().unwrap();
+    assert_eq!(store.get_ocr_text(id).unwrap().as_deref(), Some("Error: connection refused\nerrno 111"));
+    assert_eq!(store.get_ocr_text(blank).unwrap(), None);
+
+    let results = store.search("connection refused").unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].id, id);
+
+    // 全文索引包含 OCR 列
+    let conn = rusqlite::Connection::open(data_dir.join("clippy.db")).unwrap();
+    let hit: i64 = conn.query_row(
+        "SELECT rowid FROM records_fts WHERE records_fts MATCH 'ocr_text:refused'",
+        [],
+        |row| row.get(0),
+    ).unwrap();
+    assert_eq!(hit, id);
+}
+
+#[test]
+fn test_process_pending() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let storage = Mutex::new(Storage::new(&data_dir).unwrap());
+
+    // 没有引擎时不处理
+    let id = add_image(&storage, 10, 10);
+    ocr::process(&storage, id).unwrap();
+    assert_eq!(ocr::process_pending(&storage).unwrap(), 0);
+    assert_eq!(storage.lock().unwrap().pending_ocr().unwrap(), vec![id]);
+
+    storage.lock().unwrap().set_ocr_engine(Arc::new(FakeOcr));
+    assert_eq!(ocr::process_pending(&storage).unwrap(), 1);
+    assert!(storage.lock().unwrap().pending_ocr().unwrap().is_empty());
+    assert!(storage.lock().unwrap().get_ocr_text(id).unwrap().is_some());
+}
+
+#[test]
+fn test_ocr_disabled_in_settings() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let storage = Mutex::new(Storage::new(&data_dir).unwrap());
+    {
+        let mut store = storage.lock().unwrap();
+        store.set_ocr_engine(Arc::new(FakeOcr));
+        store.update_settings(Settings { ocr_enabled: false, ..Settings::default() }).unwrap();
+    }
+
+    let id = add_image(&storage, 10, 10);
+    ocr::process(&storage, id).unwrap();
+    assert_eq!(storage.lock().unwrap().get_ocr_text(id).unwrap(), None);
+    assert!(storage.lock().unwrap().search("refused").unwrap().is_empty());
+}
+
+#[test]
+fn test_normalize_and_missing_tesseract() {
+    assert_eq!(ocr::normalize("  line one  \n\n\nline two\n\u{c}\n"), "  line one\nline two");
+    assert!(TesseractCli::detect(None).is_none() || which_tesseract());
+
+    let engine = TesseractCli::new(None).with_program("/nonexistent/tesseract");
+    assert!(engine.recognize(Path::new("missing.png")).is_err());
+}
+
+fn which_tesseract() -> bool {
+    std::process::Command::new("tesseract").arg("--version").output().is_ok()
+}
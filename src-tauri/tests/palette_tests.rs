@@ -0,0 +1,75 @@
+/// 图片调色板提取测试
+
+mod common;
+
+use image::{DynamicImage, Rgba, RgbaImage};
+use pastee_lib::palette;
+use pastee_lib::persist::{ClipData, ClipType, Storage};
+use common::{create_test_dir, get_test_data_dir};
+
+/// 左边 3/4 红色，右边 1/4 蓝色
+fn two_color_image(width: u32, height: u32) -> RgbaImage {
+    RgbaImage::from_fn(width, height, |x, _| {
+        if x < width * 3 / 4 { Rgba([255, 0, 0, 255]) } else { Rgba([0, 0, 255, 255]) }
+    })
+}
+
+#[test]
+fn test_extract_dominant_colors() {
+    let img = DynamicImage::ImageRgba8(two_color_image(200, 100));
+    let swatches = palette::extract(&img, 6);
+
+    assert_eq!(swatches.len(), 2, "Solid regions should merge into two swatches: {:?}", swatches);
+    assert_eq!(swatches[0].color, "#ff0000");
+    assert_eq!(swatches[1].color, "#0000ff");
+    assert!(swatches[0].ratio > swatches[1].ratio);
+    let total: f64 = swatches.iter().map(|s| s.ratio).sum();
+    assert!((total - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_extract_limits_and_transparency() {
+    // 渐变图片最多返回指定数量的颜色
+    let gradient = RgbaImage::from_fn(64, 64, |x, y| Rgba([(x * 4) as u8, (y * 4) as u8, 128, 255]));
+    let swatches = palette::extract(&DynamicImage::ImageRgba8(gradient), 4);
+    assert!(!swatches.is_empty() && swatches.len() <= 4);
+
+    // 透明像素不参与统计
+    let transparent = RgbaImage::from_fn(10, 10, |x, _| {
+        if x < 5 { Rgba([0, 255, 0, 255]) } else { Rgba([255, 255, 255, 0]) }
+    });
+    let swatches = palette::extract(&DynamicImage::ImageRgba8(transparent), 6);
+    assert_eq!(swatches.len(), 1);
+    assert_eq!(swatches[0].color, "#00ff00");
+
+    let empty = RgbaImage::from_pixel(4, 4, Rgba([0, 0, 0, 0]));
+    assert!(palette::extract(&DynamicImage::ImageRgba8(empty), 6).is_empty());
+}
+
+#[test]
+fn test_image_palette_stored_and_promoted() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let mut storage = Storage::new(&data_dir).unwrap();
+
+    let img = two_color_image(40, 20);
+    let (id, _) = storage.add_image(40, 20, img.into_raw()).unwrap();
+
+    let swatches = storage.get_palette(id).unwrap();
+    assert_eq!(swatches.len(), 2);
+    assert_eq!(swatches[0].color, "#ff0000");
+
+    // 把第二个颜色保存为颜色记录
+    let color_id = storage.promote_swatch(id, 1).unwrap();
+    let items = storage.get_recent(1, 0).unwrap();
+    assert_eq!(items[0].id, color_id);
+    assert_eq!(items[0].content_type, ClipType::Color);
+    match storage.get_content(color_id).unwrap() {
+        ClipData::Color(value) => assert_eq!(value, "#0000ff"),
+        other => panic!("Expected color, got {:?}", other),
+    }
+
+    assert!(storage.promote_swatch(id, 10).is_err());
+    let text_id = storage.add_text("not an image".to_string()).unwrap();
+    assert!(storage.get_palette(text_id).is_err());
+}
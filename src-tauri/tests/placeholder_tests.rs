@@ -0,0 +1,56 @@
+/// 图片占位信息 (BlurHash / 平均色) 测试
+
+mod common;
+
+use image::{Rgba, RgbaImage};
+use pastee_lib::persist::{ClipFilter, Storage};
+use pastee_lib::placeholder;
+use common::{create_test_dir, get_test_data_dir};
+
+#[test]
+fn test_compute_placeholder() {
+    let img = RgbaImage::from_pixel(64, 32, Rgba([255, 0, 0, 255]));
+    let p = placeholder::compute(64, 32, img.as_raw()).unwrap();
+    assert_eq!(p.average_color, "#ff0000");
+    // 宽图使用 4x3 分量，首字符编码分量数
+    assert_eq!(p.blurhash.len(), 6 + 2 * (4 * 3 - 1));
+    let decoded = blurhash::decode(&p.blurhash, 4, 4, 1.0).unwrap();
+    assert!(decoded[0] > 240 && decoded[1] < 15 && decoded[2] < 15);
+
+    // 半透明图片的平均色带 alpha
+    let half = RgbaImage::from_fn(10, 10, |x, _| {
+        if x < 5 { Rgba([0, 0, 255, 255]) } else { Rgba([0, 0, 0, 0]) }
+    });
+    let p = placeholder::compute(10, 10, half.as_raw()).unwrap();
+    assert_eq!(p.average_color, "#0000ff80");
+
+    // 数据大小不匹配
+    assert!(placeholder::compute(10, 10, &[0u8; 12]).is_none());
+    assert!(placeholder::compute(0, 0, &[]).is_none());
+}
+
+#[test]
+fn test_image_items_include_placeholder() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let mut storage = Storage::new(&data_dir).unwrap();
+
+    storage.add_text("plain text".to_string()).unwrap();
+    let img = RgbaImage::from_pixel(16, 16, Rgba([0, 128, 0, 255]));
+    let (id, _) = storage.add_image(16, 16, img.into_raw()).unwrap();
+
+    let items = storage.get_recent(10, 0).unwrap();
+    let image = items.iter().find(|i| i.id == id).unwrap();
+    assert_eq!(image.average_color.as_deref(), Some("#008000"));
+    // 正方形图片使用 4x4 分量
+    assert!(image.blurhash.as_ref().is_some_and(|h| h.len() == 6 + 2 * (4 * 4 - 1)));
+
+    // 非图片记录没有占位信息
+    let text = items.iter().find(|i| i.id != id).unwrap();
+    assert!(text.blurhash.is_none() && text.average_color.is_none());
+
+    // 过滤查询同样返回占位信息
+    let images = storage.get_recent_filtered(10, 0, &ClipFilter::tag("image")).unwrap();
+    assert_eq!(images.len(), 1);
+    assert!(images[0].blurhash.is_some());
+}
@@ -0,0 +1,68 @@
+/// 隐私黑名单测试
+
+use pastee_lib::privacy::PrivacyGuard;
+use pastee_lib::setting::{BlockRule, MatchKind, Settings};
+use pastee_lib::source_app::SourceApp;
+
+#[test]
+fn test_exact_glob_and_regex_rules() {
+    let guard = PrivacyGuard::new(&[
+        BlockRule::app(MatchKind::Exact, "Bitwarden"),
+        BlockRule::app(MatchKind::Glob, "*bank*"),
+        BlockRule::title(MatchKind::Regex, r"(?i)\bsudo\b"),
+    ]);
+
+    // 精确匹配和通配符都不区分大小写
+    assert!(guard.matching_rule(&SourceApp::named("bitwarden")).is_some());
+    assert!(guard.matching_rule(&SourceApp::named("Bitwarden Desktop")).is_none());
+    assert!(guard.matching_rule(&SourceApp::named("MyBankApp")).is_some());
+
+    // 正则只作用于窗口标题
+    let terminal = SourceApp::named("Alacritty");
+    assert!(guard.matching_rule(&terminal.clone().with_title("~/src")).is_none());
+    assert!(guard.matching_rule(&terminal.clone().with_title("SUDO apt upgrade")).is_some());
+    assert!(guard.matching_rule(&terminal.with_title("pseudocode.md")).is_none());
+    assert!(guard.matching_rule(&SourceApp::default().with_title("sudo -i")).is_some());
+}
+
+#[test]
+fn test_invalid_rules() {
+    let invalid = [
+        BlockRule::app(MatchKind::Exact, "firefox"),
+        BlockRule::title(MatchKind::Regex, "(unclosed"),
+    ];
+    assert!(PrivacyGuard::validate(&invalid).is_err());
+    assert!(PrivacyGuard::validate(&invalid[..1]).is_ok());
+
+    // 启动时跳过无效规则，其余规则照常生效
+    let mut guard = PrivacyGuard::new(&invalid);
+    assert!(guard.matching_rule(&SourceApp::named("firefox")).is_some());
+
+    // 运行时修改失败则保留原规则
+    assert!(guard.set_rules(&[BlockRule::app(MatchKind::Glob, "[a-")]).is_err());
+    assert!(guard.matching_rule(&SourceApp::named("firefox")).is_some());
+}
+
+#[test]
+fn test_suppressed_counts() {
+    let mut guard = PrivacyGuard::new(&Settings::default().privacy_blocklist);
+
+    assert!(guard.should_suppress(Some(&SourceApp::named("KeePassXC"))));
+    assert!(guard.should_suppress(Some(&SourceApp::named("KeePassXC"))));
+    assert!(guard.should_suppress(Some(&SourceApp::named("xterm").with_title("sudo vim /etc/hosts"))));
+    assert!(!guard.should_suppress(Some(&SourceApp::named("firefox"))));
+    assert!(!guard.should_suppress(None));
+
+    assert_eq!(guard.suppressed_counts(), vec![
+        ("KeePassXC".to_string(), 2),
+        ("xterm".to_string(), 1),
+    ]);
+
+    // 修改规则不影响已有计数
+    guard.set_rules(&[]).unwrap();
+    assert!(!guard.should_suppress(Some(&SourceApp::named("KeePassXC"))));
+    assert_eq!(guard.suppressed_counts().len(), 2);
+
+    guard.reset_counts();
+    assert!(guard.suppressed_counts().is_empty());
+}
@@ -0,0 +1,91 @@
+/// RTF 解析测试
+/// 验证纯文本提取、HTML 转换和 RTF 记录的存取
+
+mod common;
+
+use pastee_lib::persist::{Storage, ClipType, ClipData};
+use pastee_lib::rtf;
+use common::{create_test_dir, get_test_data_dir};
+
+const SAMPLE: &str = r"{\rtf1\ansi\ansicpg1252\deff0{\fonttbl{\f0\fswiss Helvetica;}}{\colortbl;\red255\green0\blue0;}
+\f0\fs24 Hello \b bold\b0  and \i italic\i0 .\par
+Second line\line same paragraph\par
+}";
+
+#[test]
+fn test_rtf_plain_text() {
+    let text = rtf::to_plain_text(SAMPLE);
+    assert_eq!(text, "Hello bold and italic.\nSecond line\nsame paragraph");
+}
+
+#[test]
+fn test_rtf_skips_destinations() {
+    let text = rtf::to_plain_text(SAMPLE);
+    assert!(!text.contains("Helvetica"), "Font table should be skipped");
+    assert!(!text.contains("red255"), "Color table should be skipped");
+
+    let text = rtf::to_plain_text(r"{\rtf1{\*\generator Riched20 10.0;}visible}");
+    assert_eq!(text, "visible", "Ignorable destinations should be skipped");
+}
+
+#[test]
+fn test_rtf_unicode_and_hex_escapes() {
+    // \u 后的替代字符 (?) 应被跳过
+    let text = rtf::to_plain_text(r"{\rtf1\uc1 \u20320?\u22909?}");
+    assert_eq!(text, "你好");
+
+    let text = rtf::to_plain_text(r"{\rtf1\ansi\ansicpg1252 caf\'e9 \'93quoted\'94}");
+    assert_eq!(text, "café “quoted”");
+
+    // GBK 双字节
+    let text = rtf::to_plain_text(r"{\rtf1\ansi\ansicpg936 \'c4\'e3\'ba\'c3}");
+    assert_eq!(text, "你好");
+}
+
+#[test]
+fn test_rtf_escaped_symbols() {
+    let text = rtf::to_plain_text(r"{\rtf1 a\{b\}c\\d\tab e}");
+    assert_eq!(text, "a{b}c\\d\te");
+}
+
+#[test]
+fn test_rtf_to_html() {
+    let html = rtf::to_html(SAMPLE);
+    assert_eq!(
+        html,
+        "<p>Hello <b>bold</b> and <i>italic</i>.</p><p>Second line<br>same paragraph</p>"
+    );
+
+    let html = rtf::to_html(r"{\rtf1 <script>&}");
+    assert_eq!(html, "<p>&lt;script&gt;&amp;</p>", "Text should be escaped");
+}
+
+#[test]
+fn test_is_rtf() {
+    assert!(rtf::is_rtf(SAMPLE));
+    assert!(!rtf::is_rtf("plain text"));
+}
+
+#[test]
+fn test_add_rtf_record() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let mut storage = Storage::new(&data_dir).unwrap();
+
+    let id = storage.add_rtf(SAMPLE.to_string()).unwrap();
+    let items = storage.get_recent(1, 0).unwrap();
+    assert_eq!(items[0].content_type, ClipType::Rtf);
+    assert!(items[0].tags.contains(&"rtf".to_string()));
+
+    // 可以通过提取出的纯文本搜索
+    let results = storage.search("italic").unwrap();
+    assert_eq!(results.len(), 1);
+
+    match storage.get_content(id).unwrap() {
+        ClipData::Rtf { text, rtf } => {
+            assert!(text.starts_with("Hello bold"));
+            assert_eq!(rtf, SAMPLE, "RTF source should be restored verbatim");
+        }
+        _ => panic!("Should return Rtf ClipData"),
+    }
+}
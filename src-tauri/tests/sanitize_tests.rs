@@ -0,0 +1,53 @@
+/// HTML 预览净化测试
+/// 验证脚本、事件属性、危险链接和远程资源被移除
+
+use pastee_lib::sanitize::{self, IMAGE_PLACEHOLDER};
+
+#[test]
+fn test_sanitize_removes_scripts_and_handlers() {
+    let clean = sanitize::clean_html(
+        r#"<p onclick="steal()">Hi<script>alert(1)</script></p><style>body{}</style><img src=x onerror="alert(1)">"#,
+    );
+    assert!(!clean.contains("script"));
+    assert!(!clean.contains("onclick"));
+    assert!(!clean.contains("onerror"));
+    assert!(!clean.contains("body{}"));
+    assert!(clean.contains("<p>Hi</p>"));
+}
+
+#[test]
+fn test_sanitize_dangerous_urls() {
+    let clean = sanitize::clean_html(r#"<a href="javascript:alert(1)">a</a><a href="data:text/html,<b>x</b>">b</a>"#);
+    assert!(!clean.contains("javascript"));
+    assert!(!clean.contains("data:text/html"));
+
+    let clean = sanitize::clean_html(r#"<a href="https://example.com">ok</a>"#);
+    assert!(clean.contains(r#"href="https://example.com""#));
+    assert!(clean.contains("noopener"), "Links should get rel=noopener");
+}
+
+#[test]
+fn test_sanitize_remote_images_replaced() {
+    let clean = sanitize::clean_html(r#"<img src="https://tracker.example/pixel.gif" alt="logo">"#);
+    assert!(!clean.contains("tracker.example"), "Remote image should not be loaded");
+    assert!(clean.contains(IMAGE_PLACEHOLDER));
+    assert!(clean.contains(r#"alt="logo""#));
+
+    // 内联位图保留
+    let inline = "data:image/png;base64,iVBORw0KGgo=";
+    let clean = sanitize::clean_html(&format!(r#"<img src="{}">"#, inline));
+    assert!(clean.contains(inline));
+
+    // 内联 SVG 可能包含脚本，同样替换
+    let clean = sanitize::clean_html(r#"<img src="data:image/svg+xml;base64,PHN2Zz4=">"#);
+    assert!(!clean.contains("PHN2Zz4="));
+}
+
+#[test]
+fn test_sanitize_remote_styles_and_frames() {
+    let clean = sanitize::clean_html(
+        r#"<div style="background:url(https://evil.example/a.png)">x</div><iframe src="https://evil.example"></iframe><link rel="stylesheet" href="https://evil.example/a.css">"#,
+    );
+    assert!(!clean.contains("evil.example"));
+    assert!(clean.contains("x"));
+}
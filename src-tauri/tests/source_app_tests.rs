@@ -0,0 +1,77 @@
+/// 来源应用记录与过滤测试
+
+mod common;
+
+use pastee_lib::persist::{ClipFilter, Storage};
+use pastee_lib::source_app::{AppDetector, FakeDetector, SourceApp};
+use common::{create_test_dir, get_test_data_dir};
+
+#[test]
+fn test_fake_detector() {
+    let detector = FakeDetector::new(Some(SourceApp::named("firefox").with_title("Docs")));
+    assert_eq!(detector.active_app().unwrap().name.as_deref(), Some("firefox"));
+    detector.set(None);
+    assert!(detector.active_app().is_none());
+    assert!(SourceApp::default().is_empty());
+}
+
+#[test]
+fn test_source_app_recorded_and_filtered() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let mut storage = Storage::new(&data_dir).unwrap();
+    let detector = FakeDetector::default();
+
+    let capture = |storage: &mut Storage, text: &str| {
+        let id = storage.add_text(text.to_string()).unwrap();
+        if let Some(app) = detector.active_app() {
+            storage.set_source_app(id, &app).unwrap();
+        }
+        id
+    };
+
+    detector.set(Some(SourceApp {
+        name: Some("firefox".to_string()),
+        title: Some("Rust docs - Mozilla Firefox".to_string()),
+        exe_path: Some("/usr/lib/firefox/firefox".to_string()),
+    }));
+    let web1 = capture(&mut storage, "https://doc.rust-lang.org");
+    let web2 = capture(&mut storage, "fn main() {}");
+    detector.set(Some(SourceApp::named("Alacritty").with_title("~/src")));
+    let term = capture(&mut storage, "cargo build --release");
+    detector.set(None);
+    let unknown = capture(&mut storage, "no app");
+
+    let items = storage.get_recent(10, 0).unwrap();
+    let item = |id| items.iter().find(|i| i.id == id).unwrap();
+    let app = item(web1).source_app.as_ref().unwrap();
+    assert_eq!(app.name.as_deref(), Some("firefox"));
+    assert_eq!(app.title.as_deref(), Some("Rust docs - Mozilla Firefox"));
+    assert_eq!(app.exe_path.as_deref(), Some("/usr/lib/firefox/firefox"));
+    assert!(item(unknown).source_app.is_none());
+
+    // 按应用过滤，不区分大小写
+    let web = storage.get_recent_filtered(10, 0, &ClipFilter::app("Firefox")).unwrap();
+    let mut ids: Vec<i64> = web.iter().map(|i| i.id).collect();
+    ids.sort();
+    assert_eq!(ids, vec![web1, web2]);
+
+    // 搜索同样支持按应用过滤
+    let results = storage.search_filtered("cargo", &ClipFilter::app("alacritty")).unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].id, term);
+    assert_eq!(results[0].source_app.as_ref().unwrap().title.as_deref(), Some("~/src"));
+    assert!(storage.search_filtered("cargo", &ClipFilter::app("firefox")).unwrap().is_empty());
+
+    assert_eq!(
+        storage.list_apps().unwrap(),
+        vec![("firefox".to_string(), 2), ("Alacritty".to_string(), 1)]
+    );
+}
+
+#[test]
+fn test_filter_deserializes_app() {
+    let filter: ClipFilter = serde_json::from_str(r#"{"app": "code"}"#).unwrap();
+    assert_eq!(filter.app.as_deref(), Some("code"));
+    assert!(filter.tag.is_none());
+}
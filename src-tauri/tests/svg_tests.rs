@@ -0,0 +1,73 @@
+/// SVG 识别与渲染测试
+
+mod common;
+
+use pastee_lib::persist::{ClipData, ClipType, Storage, MIME_SVG, MIME_TEXT};
+use pastee_lib::svg;
+use common::{create_test_dir, get_test_data_dir};
+
+const RED_SQUARE: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" width="16" height="8" viewBox="0 0 16 8"><rect width="8" height="8" fill="#ff0000"/></svg>"##;
+
+#[test]
+fn test_detect_svg() {
+    assert!(svg::is_svg(RED_SQUARE));
+    assert!(svg::is_svg("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<!-- icon -->\n<!DOCTYPE svg PUBLIC \"-//W3C//DTD SVG 1.1//EN\" \"x\">\n<svg xmlns=\"http://www.w3.org/2000/svg\"/>\n"));
+    assert!(svg::is_svg("\u{feff}  <svg>\n</svg>"));
+
+    assert!(!svg::is_svg("<div><svg></svg></div>"));
+    assert!(!svg::is_svg("<svgfoo></svgfoo>"));
+    assert!(!svg::is_svg("Use the <svg> element for icons"));
+    assert!(!svg::is_svg("plain text"));
+}
+
+#[test]
+fn test_render_scales_small_icons() {
+    let rendered = svg::render(RED_SQUARE).unwrap();
+    // 长边放大到 512
+    assert_eq!((rendered.width, rendered.height), (512, 256));
+    assert_eq!(rendered.rgba.len(), 512 * 256 * 4);
+    // 左半边红色，右半边透明
+    assert_eq!(&rendered.rgba[..4], &[255, 0, 0, 255]);
+    let right = (100 * 512 + 400) * 4;
+    assert_eq!(rendered.rgba[right + 3], 0);
+
+    assert!(svg::render("<svg").is_err());
+}
+
+#[test]
+fn test_render_ignores_external_files() {
+    let markup = r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="600" height="600"><image xlink:href="/etc/hostname" width="600" height="600"/></svg>"#;
+    let rendered = svg::render(markup).unwrap();
+    assert!(rendered.rgba.chunks(4).all(|p| p[3] == 0));
+}
+
+#[test]
+fn test_svg_clip_keeps_markup() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let mut storage = Storage::new(&data_dir).unwrap();
+
+    let (id, thumbnail) = storage.add_svg(RED_SQUARE.to_string()).unwrap();
+    assert!(!thumbnail.is_empty());
+
+    let items = storage.get_recent(1, 0).unwrap();
+    assert_eq!(items[0].content_type, ClipType::Image);
+    assert!(items[0].tags.contains(&"svg".to_string()));
+
+    // 可以恢复为 PNG 位图
+    match storage.get_content(id).unwrap() {
+        ClipData::Image(png) => {
+            let img = image::load_from_memory(&png).unwrap();
+            assert_eq!((img.width(), img.height()), (512, 256));
+        }
+        other => panic!("Expected image, got {:?}", other),
+    }
+    // 也可以恢复为源码
+    assert_eq!(storage.get_svg(id).unwrap().as_deref(), Some(RED_SQUARE));
+    let parts = storage.get_parts(id).unwrap();
+    assert!(parts.iter().any(|p| p.mime == MIME_SVG));
+    assert!(parts.iter().any(|p| p.mime == MIME_TEXT));
+
+    let text_id = storage.add_text("not svg".to_string()).unwrap();
+    assert_eq!(storage.get_svg(text_id).unwrap(), None);
+}
@@ -0,0 +1,141 @@
+/// 保险库测试：移入、解锁读取和到期锁定
+
+mod common;
+
+use std::sync::Arc;
+
+use pastee_lib::crypto::StoreKey;
+use pastee_lib::persist::{ClipPart, Storage};
+use pastee_lib::setting::Settings;
+use common::{create_test_dir, get_test_data_dir};
+
+#[test]
+fn test_move_to_vault() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let mut storage = Storage::new(&data_dir).unwrap();
+
+    let id = storage.add_text("staging db: admin / s3cr3t-Pa55".to_string()).unwrap();
+    storage.add_parts(id, &[ClipPart::text("text/html", "<b>s3cr3t-Pa55</b>".to_string())]).unwrap();
+    storage.add_text("ordinary note".to_string()).unwrap();
+
+    // 未解锁时不能移入
+    assert!(storage.move_to_vault(id, None).is_err());
+    storage.vault_unlock("vault pass").unwrap();
+    storage.move_to_vault(id, Some("staging".to_string())).unwrap();
+
+    // 不出现在列表、计数和搜索结果中，明文内容已清除
+    let items = storage.get_recent(10, 0).unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(storage.get_total_count().unwrap(), 1);
+    assert!(storage.search("s3cr3t").unwrap().is_empty());
+    assert!(storage.get_content(id).is_err());
+    assert!(storage.get_parts(id).unwrap().is_empty());
+
+    let vault = storage.vault_items().unwrap();
+    assert_eq!(vault.len(), 1);
+    assert_eq!(vault[0].label.as_deref(), Some("staging"));
+    assert_eq!(storage.read_vault(id).unwrap(), "staging db: admin / s3cr3t-Pa55");
+
+    // 再次复制同样内容成为普通记录
+    let again = storage.add_text("staging db: admin / s3cr3t-Pa55".to_string()).unwrap();
+    assert_ne!(again, id);
+    assert_eq!(storage.vault_items().unwrap().len(), 1);
+}
+
+#[test]
+fn test_vault_plaintext_not_left_on_disk() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let mut storage = Storage::new(&data_dir).unwrap();
+
+    let secret = "prod root / Zq8-unlikely-Needle-77";
+    let id = storage.add_text(secret.to_string()).unwrap();
+    storage.vault_unlock("vault pass").unwrap();
+    storage.move_to_vault(id, None).unwrap();
+
+    // 清空的明文不残留在数据库空闲页和 WAL 中
+    for name in ["clippy.db", "clippy.db-wal"] {
+        let Ok(bytes) = std::fs::read(data_dir.join(name)) else { continue };
+        let found = bytes.windows(secret.len()).any(|w| w == secret.as_bytes());
+        assert!(!found, "{} still contains the plaintext", name);
+    }
+}
+
+#[test]
+fn test_vault_passphrase() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let mut storage = Storage::new(&data_dir).unwrap();
+    let id = storage.add_text("api key for staging".to_string()).unwrap();
+
+    assert!(storage.vault_unlock("").is_err());
+    storage.vault_unlock("vault pass").unwrap();
+    storage.move_to_vault(id, None).unwrap();
+    assert!(storage.vault_lock());
+    assert!(storage.read_vault(id).is_err());
+
+    assert!(storage.vault_unlock("wrong pass").is_err());
+    assert!(storage.vault_expires_at().is_none());
+    storage.vault_unlock("vault pass").unwrap();
+    assert_eq!(storage.read_vault(id).unwrap(), "api key for staging");
+}
+
+#[test]
+fn test_vault_unlock_expires() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let mut storage = Storage::new(&data_dir).unwrap();
+    storage.update_settings(Settings { vault_unlock_secs: 0, ..Settings::default() }).unwrap();
+    let id = storage.add_text("expires right away".to_string()).unwrap();
+
+    storage.vault_unlock("vault pass").unwrap();
+    assert!(storage.refresh_vault());
+    assert!(!storage.refresh_vault());
+    assert!(storage.move_to_vault(id, None).is_err());
+
+    // 时长超出范围时返回错误，保持锁定
+    storage.update_settings(Settings { vault_unlock_secs: u64::MAX, ..Settings::default() }).unwrap();
+    assert!(storage.vault_unlock("vault pass").is_err());
+    assert!(storage.vault_expires_at().is_none());
+}
+
+#[test]
+fn test_vault_survives_unpin_and_clear() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let mut storage = Storage::new(&data_dir).unwrap();
+    let id = storage.add_text("deploy token".to_string()).unwrap();
+    storage.add_text("ordinary note".to_string()).unwrap();
+    storage.vault_unlock("vault pass").unwrap();
+    storage.move_to_vault(id, None).unwrap();
+
+    // 保险库记录不能取消置顶，清空未置顶记录时保留
+    assert!(storage.toggle_pin(id).is_err());
+    assert_eq!(storage.clear_unpinned().unwrap(), 1);
+    assert_eq!(storage.read_vault(id).unwrap(), "deploy token");
+}
+
+#[test]
+fn test_vault_requires_unlocked_store() {
+    let temp_dir = create_test_dir();
+    let data_dir = get_test_data_dir(&temp_dir);
+    let mut storage = Storage::new(&data_dir).unwrap();
+    let id = storage.add_text("db password".to_string()).unwrap();
+    storage.vault_unlock("vault pass").unwrap();
+    storage.move_to_vault(id, None).unwrap();
+
+    let key = || Arc::new(StoreKey::derive("store pass", b"pastee-test-salt").unwrap());
+    storage.encrypt(key()).unwrap();
+    storage.lock_store().unwrap();
+
+    // 锁定期间不能设置或解锁保险库
+    assert!(storage.vault_unlock("any pass").is_err());
+    assert!(storage.vault_expires_at().is_none());
+
+    storage.unlock_store(key()).unwrap();
+    assert!(storage.vault_expires_at().is_none());
+    assert!(storage.vault_unlock("any pass").is_err());
+    storage.vault_unlock("vault pass").unwrap();
+    assert_eq!(storage.read_vault(id).unwrap(), "db password");
+}
@@ -3,25 +3,50 @@ import { listen } from "@tauri-apps/api/event";
 
 export interface ClipItemData {
     id: number;
-    content_type: "Text" | "Image" | "Html" | "Files" | "Color";
+    content_type: "Text" | "Image" | "Html" | "Files" | "Color" | "Rtf" | "Custom";
     preview: string;
     created_at: number;
     is_pinned: boolean;
     tags: string[];
+    language: string | null;
+    blurhash: string | null;       // 图片占位：BlurHash
+    average_color: string | null;  // 图片占位：平均色
+    source_id: number | null;      // 派生记录的来源记录
+    source_app: SourceApp | null;  // 复制时的前台应用
+    is_sensitive: boolean;         // 敏感内容：预览已遮盖，不参与搜索
+    sensitive_kind: string | null; // 如 "aws_key" / "credit_card"
+    expires_at: number | null;     // 到期自动删除的时间 (微秒时间戳)
+    delete_after_use: boolean;     // 一次性记录：粘贴回剪贴板后自动删除
+}
+
+export interface SourceApp {
+    name: string | null;      // 应用名，如 "firefox"
+    title: string | null;     // 窗口标题
+    exe_path: string | null;  // 可执行文件路径
+}
+
+/**
+ * 列表 / 搜索过滤条件
+ * tag: 智能标签，如 url / email / json / code
+ */
+export interface ClipFilter {
+    tag?: string;
+    source_id?: number;  // 只返回由该记录派生的记录
+    app?: string;        // 只返回来自该应用的记录
 }
 
 /**
  * 获取最近的剪贴板项
  */
-export const getRecentClips = (limit: number = 20, offset: number = 0): Promise<ClipItemData[]> => {
-    return invoke<ClipItemData[]>("get_recent_clips", { limit, offset });
+export const getRecentClips = (limit: number = 20, offset: number = 0, filter?: ClipFilter): Promise<ClipItemData[]> => {
+    return invoke<ClipItemData[]>("get_recent_clips", { limit, offset, filter });
 };
 
 /**
  * 搜索剪贴板项
  */
-export const searchClips = (query: string): Promise<ClipItemData[]> => {
-    return invoke<ClipItemData[]>("search_clips", { query });
+export const searchClips = (query: string, filter?: ClipFilter): Promise<ClipItemData[]> => {
+    return invoke<ClipItemData[]>("search_clips", { query, filter });
 };
 
 /**
@@ -31,6 +56,157 @@ export const getClipContent = (id: number): Promise<any> => {
     return invoke("get_clip_content", { id });
 };
 
+export interface ColorFormats {
+    hex: string;
+    rgb: string;
+    hsl: string;
+    oklch: string;
+}
+
+/**
+ * 把颜色记录转换为多种 CSS 写法
+ */
+export const convertColor = (id: number): Promise<ColorFormats> => {
+    return invoke<ColorFormats>("convert_color", { id });
+};
+
+export interface Swatch {
+    color: string;  // #rrggbb
+    ratio: number;  // 占比 (0-1)
+}
+
+/**
+ * 获取图片记录的调色板（按占比降序）
+ */
+export const getPalette = (id: number): Promise<Swatch[]> => {
+    return invoke<Swatch[]>("get_palette", { id });
+};
+
+/**
+ * 把调色板中的一个颜色保存为颜色记录，返回新记录 ID
+ */
+export const promoteSwatch = (id: number, index: number): Promise<number> => {
+    return invoke<number>("promote_swatch", { id, index });
+};
+
+/**
+ * 出现过的来源应用及其记录数 [应用名, 数量]
+ */
+export const listSourceApps = (): Promise<[string, number][]> => {
+    return invoke<[string, number][]>("list_source_apps");
+};
+
+/**
+ * 只在内存中暂存的记录：带密码管理器隐藏标记的内容，或隐身模式下复制的内容
+ */
+export interface EphemeralClip {
+    id: number;
+    preview: string; // 隐藏标记和敏感内容已遮盖
+    source_app: SourceApp | null;
+    expires_at: number | null; // 微秒时间戳；null 表示隐身模式下的记录
+}
+
+export const getEphemeralClips = (): Promise<EphemeralClip[]> => {
+    return invoke<EphemeralClip[]>("get_ephemeral_clips");
+};
+
+/**
+ * 把临时记录写回剪贴板
+ */
+export const copyEphemeralClip = (id: number): Promise<void> => {
+    return invoke("copy_ephemeral_clip", { id });
+};
+
+/**
+ * 本次运行中被隐私黑名单忽略的复制次数 [应用名, 次数]
+ */
+export const getSuppressedCounts = (): Promise<[string, number][]> => {
+    return invoke<[string, number][]>("get_suppressed_counts");
+};
+
+/**
+ * 清空隐私黑名单计数
+ */
+export const resetSuppressedCounts = (): Promise<void> => {
+    return invoke("reset_suppressed_counts");
+};
+
+/**
+ * 把文本记录生成为二维码图片记录，返回新记录 ID
+ */
+export const generateQr = (id: number): Promise<number> => {
+    return invoke<number>("generate_qr", { id });
+};
+
+/**
+ * 获取图片 OCR 识别出的文字（未识别或没有文字时为 null）
+ */
+export const getOcrText = (id: number): Promise<string | null> => {
+    return invoke<string | null>("get_ocr_text", { id });
+};
+
+export interface HighlightSpan {
+    text: string;
+    color: string;
+    bold: boolean;
+    italic: boolean;
+    underline: boolean;
+}
+
+export interface Highlighted {
+    language: string;
+    background: string;
+    lines: HighlightSpan[][];
+    truncated: boolean;
+}
+
+/**
+ * 获取代码片段的语法高亮片段
+ */
+export const getHighlighted = (id: number, theme?: string): Promise<Highlighted> => {
+    return invoke<Highlighted>("get_highlighted", { id, theme });
+};
+
+/**
+ * 手动指定代码片段的语言，传 null 清除
+ */
+export const setClipLanguage = (id: number, language: string | null): Promise<void> => {
+    return invoke("set_clip_language", { id, language });
+};
+
+/**
+ * 获取净化后的 HTML 预览（HTML / RTF 记录）
+ */
+export const getClipPreviewHtml = (id: number): Promise<string> => {
+    return invoke<string>("get_clip_preview_html", { id });
+};
+
+export interface TextChunk {
+    text: string;
+    offset: number;
+    next_offset: number;
+    total_size: number;
+    eof: boolean;
+}
+
+/**
+ * 分段读取超大文本（按 UTF-8 字节偏移）
+ */
+export const getClipTextRange = (id: number, offset: number, length: number): Promise<TextChunk> => {
+    return invoke<TextChunk>("get_clip_text_range", { id, offset, length });
+};
+
+/**
+ * 读取 / 更新用户设置
+ */
+export const getSettings = (): Promise<any> => {
+    return invoke("get_settings");
+};
+
+export const updateSettings = (settings: any): Promise<void> => {
+    return invoke("update_settings", { settings });
+};
+
 /**
  * 切换置顶状态
  */
@@ -45,12 +221,46 @@ export const deleteClip = (id: number): Promise<void> => {
     return invoke("delete_clip", { id });
 };
 
+/**
+ * 设置记录在多少分钟后自动删除，不传 minutes 时取消；返回新的到期时间 (微秒时间戳)
+ */
+export const setClipExpiry = (id: number, minutes?: number): Promise<number | null> => {
+    return invoke<number | null>("set_clip_expiry", { id, minutes: minutes ?? null });
+};
+
+/**
+ * 标记 / 取消一次性记录：粘贴回剪贴板后短时间内删除，并清空剪贴板
+ */
+export const setClipDeleteAfterUse = (id: number, enabled: boolean): Promise<void> => {
+    return invoke("set_clip_delete_after_use", { id, enabled });
+};
+
+/**
+ * 手动标记 / 取消敏感内容
+ */
+export const setClipSensitive = (id: number, sensitive: boolean): Promise<void> => {
+    return invoke("set_clip_sensitive", { id, sensitive });
+};
+
+/**
+ * 将剪贴板项恢复到系统剪贴板（包含所有附加表示）
+ * format 为 "markdown" 时以 Markdown 纯文本写入；
+ * SVG 记录可用 "svg" 只写入源码、"png" 只写入位图
+ */
+export const copyToClipboard = (id: number, format?: "markdown" | "svg" | "png"): Promise<void> => {
+    return invoke("copy_to_clipboard", { id, format });
+};
+
 /**
  * 获取图片URL
  */
 export const getImageUrl = async (id: number, thumbnail: boolean = false): Promise<string> => {
     const { convertFileSrc } = await import("@tauri-apps/api/core");
     const relativePath = await invoke<string>("get_image_url", { id, thumbnail });
+    // 加密存储直接返回缩略图的 data URL (不提供原图)
+    if (relativePath.startsWith("data:")) {
+        return relativePath;
+    }
     const { documentDir } = await import("@tauri-apps/api/path");
     const docDir = await documentDir();
     const fullPath = `${docDir}/pastee/images/${relativePath}`;
@@ -72,3 +282,142 @@ export const onClipboardNewClip = (callback: (data: any) => void): Promise<() =>
         callback(event.payload);
     });
 };
+
+/**
+ * 记录状态：正常 / 暂停到指定时间 (微秒时间戳) / 无限期暂停 / 隐身模式
+ */
+export type CaptureState =
+    | { mode: "active" }
+    | { mode: "paused_until"; until: number }
+    | { mode: "paused_indefinitely" }
+    | { mode: "incognito" };
+
+export const getCaptureState = (): Promise<CaptureState> => {
+    return invoke<CaptureState>("get_capture_state");
+};
+
+/**
+ * 暂停记录；不传 minutes 时无限期暂停
+ */
+export const pauseCapture = (minutes?: number): Promise<CaptureState> => {
+    return invoke<CaptureState>("pause_capture", { minutes: minutes ?? null });
+};
+
+export const resumeCapture = (): Promise<CaptureState> => {
+    return invoke<CaptureState>("resume_capture");
+};
+
+/**
+ * 进入 / 退出隐身模式，退出时丢弃隐身期间的记录
+ */
+export const setIncognito = (enabled: boolean): Promise<CaptureState> => {
+    return invoke<CaptureState>("set_incognito", { enabled });
+};
+
+/**
+ * 监听记录状态变化 (命令、托盘菜单、快捷键和定时恢复都会触发)
+ */
+export const onCaptureStateChange = (callback: (state: CaptureState) => void): Promise<() => void> => {
+    return listen<CaptureState>("capture://state", (event) => {
+        callback(event.payload);
+    });
+};
+
+export interface StoreStatus {
+    encrypted: boolean;
+    locked: boolean;
+    key_source: "passphrase" | "keyring" | null;
+}
+
+export const getStoreStatus = (): Promise<StoreStatus> => {
+    return invoke<StoreStatus>("get_store_status");
+};
+
+/**
+ * 就地加密现有历史；不传 passphrase 时密钥保存在系统钥匙串
+ */
+export const enableEncryption = (passphrase?: string): Promise<void> => {
+    return invoke("enable_encryption", { passphrase: passphrase ?? null });
+};
+
+/**
+ * 解锁加密存储；钥匙串模式下不需要口令
+ */
+export const unlockStore = (passphrase?: string): Promise<void> => {
+    return invoke("unlock_store", { passphrase: passphrase ?? null });
+};
+
+export const lockStore = (): Promise<void> => {
+    return invoke("lock_store");
+};
+
+/**
+ * 监听锁定状态变化 (包括空闲自动锁定)
+ */
+export const onStoreStateChange = (callback: (state: { locked: boolean }) => void): Promise<() => void> => {
+    return listen<{ locked: boolean }>("store://state", (event) => {
+        callback(event.payload);
+    });
+};
+
+/**
+ * 监听锁定期间暂存的复制超出上限、最早一条被丢弃
+ */
+export const onClipDropped = (callback: (info: { limit: number }) => void): Promise<() => void> => {
+    return listen<{ limit: number }>("store://clip-dropped", (event) => {
+        callback(event.payload);
+    });
+};
+
+export interface VaultItem {
+    id: number;
+    label: string | null;
+    created_at: number;
+}
+
+export interface VaultState {
+    unlocked: boolean;
+    expires_at: number | null;
+}
+
+/**
+ * 解锁保险库，返回到期时间；第一次调用时设置保险库口令
+ */
+export const vaultUnlock = (passphrase: string): Promise<number> => {
+    return invoke<number>("vault_unlock", { passphrase });
+};
+
+export const vaultLock = (): Promise<void> => {
+    return invoke("vault_lock");
+};
+
+/**
+ * 保险库解锁的到期时间，未解锁时为 null
+ */
+export const getVaultStatus = (): Promise<number | null> => {
+    return invoke<number | null>("get_vault_status");
+};
+
+/**
+ * 把文本记录移入保险库 (需要先解锁)，之后不再出现在列表和搜索中
+ */
+export const moveToVault = (id: number, label?: string): Promise<void> => {
+    return invoke("move_to_vault", { id, label: label ?? null });
+};
+
+export const listVault = (): Promise<VaultItem[]> => {
+    return invoke<VaultItem[]>("list_vault");
+};
+
+export const copyVaultClip = (id: number): Promise<void> => {
+    return invoke("copy_vault_clip", { id });
+};
+
+/**
+ * 监听保险库解锁 / 锁定 (包括到期自动锁定)
+ */
+export const onVaultStateChange = (callback: (state: VaultState) => void): Promise<() => void> => {
+    return listen<VaultState>("vault://state", (event) => {
+        callback(event.payload);
+    });
+};
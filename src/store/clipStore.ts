@@ -11,6 +11,11 @@ interface ClipItem {
   tags: string[];
   loading?: boolean; // 图片处理中标识
   temp_id?: number;  // 临时 ID，用于匹配处理中的图片
+  blurhash?: string | null;      // 图片占位：BlurHash
+  average_color?: string | null; // 图片占位：平均色
+  is_sensitive?: boolean;        // 敏感内容：预览已遮盖
+  expires_at?: number | null;    // 到期自动删除的时间 (微秒时间戳)
+  delete_after_use?: boolean;    // 一次性记录
 }
 
 interface ClipStore {
@@ -148,7 +153,7 @@ export const useClipStore = create<ClipStore>((set, get) => ({
     
     // 监听图片处理开始事件
     const unlistenImagePending = await listen<any>('clipboard://image-pending', (event) => {
-      const { temp_id } = event.payload;
+      const { temp_id, blurhash, average_color } = event.payload;
       const { allClips } = get();
       
       // 添加一个占位项到列表顶部
@@ -160,7 +165,9 @@ export const useClipStore = create<ClipStore>((set, get) => ({
         created_at: Date.now() * 1000,
         is_pinned: false,
         tags: ['image'],
-        loading: true
+        loading: true,
+        blurhash,
+        average_color
       };
       
       set({ allClips: [placeholderItem, ...allClips] });
@@ -200,12 +207,24 @@ export const useClipStore = create<ClipStore>((set, get) => ({
       set({ allClips: filteredClips });
     });
     
+    // 监听到期记录被删除的事件
+    const unlistenExpired = await listen<{ ids: number[] }>('clipboard://clips-expired', (event) => {
+      const expired = new Set(event.payload.ids);
+      const { allClips, searchResults, totalCount } = get();
+      set({
+        allClips: allClips.filter(item => !expired.has(item.id)),
+        searchResults: searchResults.filter(item => !expired.has(item.id)),
+        totalCount: Math.max(0, totalCount - expired.size)
+      });
+    });
+    
     // 返回清理函数
     return () => {
       unlistenNormal();
       unlistenImagePending();
       unlistenImageReady();
       unlistenImageError();
+      unlistenExpired();
     };
   },
 }));